@@ -1,14 +1,63 @@
-import { invoke } from '@tauri-apps/api/core'
-import { listen, type UnlistenFn } from '@tauri-apps/api/event'
+import { Channel, invoke } from '@tauri-apps/api/core'
+import { emit, listen, type UnlistenFn } from '@tauri-apps/api/event'
 import type {
+  AdapterCapabilities,
+  LocalAdapterAddress,
+  AdapterInfo,
+  Appearance,
+  BatchReadResult,
+  BatteryLevel,
   BluetoothCharacteristic,
+  BluetoothDescriptor,
   BluetoothDevice,
+  BluetoothPhy,
   BluetoothService,
   BluetoothValue,
+  ValueCondition,
+  CharacteristicCapabilities,
+  CharacteristicDetails,
+  CharacteristicEventPayload,
+  DebugSnapshot,
+  ActiveTasks,
+  CharacteristicTask,
+  DescriptorValue,
+  DescriptorReadResult,
   DeviceEventPayload,
+  DeviceRequestPayload,
+  ReconnectFailedPayload,
+  AvailabilityChangedPayload,
+  EventEnvelope,
+  DeviceRevisions,
+  DuplicateNotificationPolicy,
   GattServerInfo,
+  LastDevice,
+  BluetoothError,
+  BluetoothErrorName,
+  DiscoveryError,
+  MonitorInfo,
   NotificationEventPayload,
+  NotificationStats,
+  CharacteristicStats,
+  PhyInfo,
+  PermissionStatus,
+  ConnectionState,
+  PingResult,
+  PollAggregate,
+  ReadChunk,
+  ReadLongSummary,
+  ReadValueRequest,
   RequestDeviceOptions,
+  RssiChangedPayload,
+  AdvertisementReceivedPayload,
+  SelectionHandlerInfo,
+  SelectionUpdatePayload,
+  SelectionInfo,
+  SequenceWrite,
+  ServiceChangedPayload,
+  StandardDescriptorValue,
+  ValueFormat,
+  WriteSequenceResult,
+  WriteTypeKind,
 } from './types'
 
 /**
@@ -16,15 +65,43 @@ import type {
  */
 const NAMESPACE = 'plugin:web-bluetooth'
 
+/**
+ * How often value listeners report themselves for `PluginConfig::auto_unsubscribe`.
+ */
+const LISTENER_HEARTBEAT_MS = 5000
+
 /**
  * Event names emitted by the plugin.
  *
  * - `characteristicValueChanged`: emits {@link NotificationEventPayload}
  * - `gattServerDisconnected`: emits {@link DeviceEventPayload}
+ * - `gattServerConnected`: emits {@link DeviceEventPayload} after an automatic reconnect
+ * - `reconnectFailed`: emits {@link ReconnectFailedPayload}
+ * - `notificationsEnded`: emits {@link CharacteristicEventPayload}
+ * - `serviceChanged`: emits {@link ServiceChangedPayload}
+ * - `rssiChanged`: emits {@link RssiChangedPayload}
+ * - `advertisementReceived`: emits {@link AdvertisementReceivedPayload}
+ * - `deviceDiscovered`: emits {@link BluetoothDevice} for each newly matched device of a background scan
+ * - `deviceUpdated`: emits {@link BluetoothDevice} when a device found by a background scan changes
+ * - `availabilityChanged`: emits {@link AvailabilityChangedPayload} when the adapter appears, disappears or is
+ *   powered on or off
+ * - `requestDevice`: emits {@link DeviceRequestPayload} to the picker window of an `EventSelectionHandler`
+ * - `event`: emits {@link EventEnvelope} for every device event above, when unified events are enabled
  */
 export const EVENTS = {
   characteristicValueChanged: 'web-bluetooth://characteristic-value-changed',
   gattServerDisconnected: 'web-bluetooth://gattserver-disconnected',
+  gattServerConnected: 'web-bluetooth://gattserver-connected',
+  reconnectFailed: 'web-bluetooth://reconnect-failed',
+  notificationsEnded: 'web-bluetooth://notifications-ended',
+  serviceChanged: 'web-bluetooth://service-changed',
+  rssiChanged: 'web-bluetooth://rssi-changed',
+  advertisementReceived: 'web-bluetooth://advertisement-received',
+  deviceDiscovered: 'web-bluetooth://device-discovered',
+  deviceUpdated: 'web-bluetooth://device-updated',
+  availabilityChanged: 'web-bluetooth://availability-changed',
+  requestDevice: 'web-bluetooth://request-device',
+  event: 'web-bluetooth://event',
 } as const
 
 /**
@@ -39,14 +116,106 @@ const call = async <T>(command: string, payload?: Record<string, unknown>): Prom
 }
 
 /**
- * Check whether Web Bluetooth is available on the host.
+ * Register application-defined names for UUIDs.
+ *
+ * Registered names can be passed anywhere a UUID is accepted and are reported in the `alias` field of
+ * services and characteristics. They last for the rest of the session. Standard GATT names such as
+ * `heart_rate` are rejected, since an alias would shadow them.
+ *
+ * @param aliases Map of UUID (full, 16-bit or 32-bit form) to name.
+ */
+export async function registerUuidAliases(aliases: Record<string, string>): Promise<void> {
+  await call('register_uuid_aliases', { request: { aliases } })
+}
+
+/**
+ * Check whether Web Bluetooth is available on the host. Use {@link onAvailabilityChanged} to follow changes.
  *
- * @returns `true` when the platform has a usable Bluetooth adapter.
+ * @returns `true` when the platform has a Bluetooth adapter that is not powered off.
  */
 export async function getAvailability(): Promise<boolean> {
   return call<boolean>('get_availability')
 }
 
+/**
+ * Show the OS Bluetooth permission prompt now, if it has not been shown yet, instead of on the first
+ * incidental Bluetooth call. Does not start a scan.
+ *
+ * @returns `'granted'` on platforms without a prompt.
+ */
+export async function requestPermission(): Promise<PermissionStatus> {
+  return call<PermissionStatus>('request_permission')
+}
+
+/**
+ * Change the scan timeout used by requests that omit `scanTimeoutMs`, for the rest of the session.
+ *
+ * @param timeoutMs New default in milliseconds; at least 1000.
+ */
+export async function setDefaultScanTimeout(timeoutMs: number): Promise<void> {
+  await call('set_default_scan_timeout', { request: { timeoutMs } })
+}
+
+/**
+ * Read the scan timeout used by requests that omit `scanTimeoutMs`.
+ *
+ * @returns The default in milliseconds.
+ */
+export async function getDefaultScanTimeout(): Promise<number> {
+  return call<number>('get_default_scan_timeout')
+}
+
+/**
+ * Query controller features such as extended advertising or coded PHY support.
+ *
+ * This is a best-effort read: fields are left undefined when the platform does not report them.
+ *
+ * @returns Known adapter capabilities.
+ */
+export async function getAdapterCapabilities(): Promise<AdapterCapabilities> {
+  return call<AdapterCapabilities>('get_adapter_capabilities')
+}
+
+/**
+ * Read the local adapter's address and friendly name, e.g. to correlate support logs across
+ * machines. Only Linux (BlueZ) exposes them; elsewhere the fields are `null`.
+ *
+ * @returns The controller address, whether it is a random private address, and its name.
+ */
+export async function getLocalAdapterAddress(): Promise<LocalAdapterAddress> {
+  return call<LocalAdapterAddress>('get_local_adapter_address')
+}
+
+/**
+ * Describe the adapter in use, e.g. for a settings or troubleshooting screen.
+ *
+ * @returns Its identifier, name and address where available, and whether it is powered.
+ */
+export async function getAdapterInfo(): Promise<AdapterInfo> {
+  return call<AdapterInfo>('get_adapter_info')
+}
+
+/**
+ * List the host's Bluetooth adapters, e.g. to let the user pick one for `PluginConfig::adapter`.
+ *
+ * @returns Every adapter, with the one in use marked `selected`.
+ */
+export async function getAdapters(): Promise<AdapterInfo[]> {
+  return call<AdapterInfo[]>('get_adapters')
+}
+
+/**
+ * Switch to another adapter without restarting, e.g. after plugging in a dongle. Every device is
+ * disconnected and subscriptions, watchers and background scans stop; device ids derived from platform
+ * ids may differ on the new adapter. Rejects while a {@link requestDevice} call is running.
+ *
+ * @param adapter The adapter's `index` from {@link getAdapters}, or its platform id, name or address.
+ * @returns The adapter now in use.
+ */
+export async function setAdapter(adapter: number | string): Promise<AdapterInfo> {
+  return call<AdapterInfo>('set_adapter', { request: { adapter } })
+}
+
 /**
  * Return all known Bluetooth devices.
  *
@@ -56,6 +225,26 @@ export async function getDevices(): Promise<BluetoothDevice[]> {
   return call<BluetoothDevice[]>('get_devices')
 }
 
+/**
+ * Return the cached devices that are currently connected, e.g. to rebuild UI state after a reload.
+ *
+ * @returns Connected devices, in no particular order.
+ */
+export async function getConnectedDevices(): Promise<BluetoothDevice[]> {
+  return call<BluetoothDevice[]>('get_connected_devices')
+}
+
+/**
+ * Return devices bonded with the host operating system, even when they are out of range.
+ *
+ * Devices are marked `paired`. Only supported on Linux (BlueZ); rejects with `UnsupportedPlatform` elsewhere.
+ *
+ * @returns Bonded devices known to the OS.
+ */
+export async function getBondedDevices(): Promise<BluetoothDevice[]> {
+  return call<BluetoothDevice[]>('get_bonded_devices')
+}
+
 /**
  * Ask the user to select a Bluetooth device using the provided filters.
  *
@@ -66,14 +255,229 @@ export async function requestDevice(options: RequestDeviceOptions): Promise<Blue
   return call<BluetoothDevice>('request_device', { options })
 }
 
+/**
+ * Ask the user to select several Bluetooth devices at once, e.g. for multi-sensor setups.
+ *
+ * The built-in dialog shows checkboxes and a confirm button. Custom selection handlers that do not
+ * implement `select_many` return at most one device.
+ *
+ * @param options Selection rules; see {@link RequestDeviceOptions}.
+ * @returns The devices chosen by the user, in selection order.
+ */
+export async function requestDevices(options: RequestDeviceOptions): Promise<BluetoothDevice[]> {
+  return call<BluetoothDevice[]>('request_devices', { options })
+}
+
+/**
+ * Scan in the background without a picker, emitting `EVENTS.deviceDiscovered` for every newly
+ * matched device and `EVENTS.deviceUpdated` whenever one of them changes. `scanTimeoutMs` is ignored;
+ * the scan runs until {@link stopScan}. Calling it again replaces the running scan.
+ *
+ * @param options Matching rules; see {@link RequestDeviceOptions}.
+ */
+export async function startScan(options: RequestDeviceOptions): Promise<void> {
+  await call('start_scan', { options })
+}
+
+/**
+ * Stop the background scan started with {@link startScan}.
+ */
+export async function stopScan(): Promise<void> {
+  await call('stop_scan')
+}
+
+/**
+ * Close the picker of a pending {@link requestDevice} or {@link requestDevices} call, which then rejects
+ * with `SelectionCancelled`. Does nothing if the request already finished.
+ *
+ * @param requestId Request to dismiss; omit to dismiss whichever request is active.
+ */
+export async function dismissSelection(requestId?: string): Promise<void> {
+  await call('dismiss_selection', { request: { requestId } })
+}
+
+/**
+ * Describe how {@link requestDevice} picks a device on this host.
+ *
+ * Use it to decide whether to render your own picker or wait for the plugin's window.
+ *
+ * @returns The configured selection mode and whether it waits for a full scan.
+ */
+export async function getSelectionInfo(): Promise<SelectionHandlerInfo> {
+  return call<SelectionHandlerInfo>('get_selection_info')
+}
+
+/**
+ * Listen for picker requests when the plugin is configured with an `EventSelectionHandler`.
+ *
+ * Render the devices from the payload, follow {@link onSelectionUpdate} while `scanning` is set and
+ * answer with {@link answerDeviceRequest}.
+ *
+ * @param handler Callback receiving {@link DeviceRequestPayload}.
+ * @returns Unlisten function that removes the listener when called.
+ */
+export async function onDeviceRequest(
+  handler: (request: DeviceRequestPayload) => void,
+): Promise<UnlistenFn> {
+  return listen<DeviceRequestPayload>(EVENTS.requestDevice, (event) => {
+    handler(event.payload)
+  })
+}
+
+/**
+ * Listen for scan results of a pending picker request.
+ *
+ * @param request Request received through {@link onDeviceRequest}.
+ * @param handler Callback receiving the full device list so far.
+ * @returns Unlisten function that removes the listener when called.
+ */
+export async function onSelectionUpdate(
+  request: DeviceRequestPayload,
+  handler: (update: SelectionUpdatePayload) => void,
+): Promise<UnlistenFn> {
+  return listen<SelectionUpdatePayload>(request.updateEvent, (event) => {
+    handler(event.payload)
+  })
+}
+
+/**
+ * Resolve a picker request received through {@link onDeviceRequest}.
+ *
+ * @param request Request being answered.
+ * @param deviceIds Chosen devices, or `null` to cancel.
+ */
+export async function answerDeviceRequest(request: DeviceRequestPayload, deviceIds: string[] | null): Promise<void> {
+  await emit(request.selectionEvent, deviceIds && deviceIds.length > 0 ? { deviceIds } : { deviceId: null })
+}
+
+/**
+ * Select a device, connect to it and discover its services in one call.
+ *
+ * Equivalent to {@link requestDevice} followed by {@link connectGATT}; the first failing step's error is returned.
+ *
+ * @param options Selection rules; see {@link RequestDeviceOptions}.
+ * @returns Connection state plus discovered services of the chosen device.
+ */
+export async function connectMatching(options: RequestDeviceOptions): Promise<GattServerInfo> {
+  return call<GattServerInfo>('connect_matching', { options })
+}
+
+/**
+ * Describe how the device returned by the last {@link requestDevice} call was matched.
+ *
+ * @returns The matching filter index and advertisement snapshot, or `null` before any selection.
+ */
+export async function getLastSelection(): Promise<SelectionInfo | null> {
+  return call<SelectionInfo | null>('get_last_selection')
+}
+
+/**
+ * Get the device most recently picked through {@link requestDevice}, for a "reconnect last device" button.
+ *
+ * The device may be out of range; pass its id to {@link connectGATT} to reconnect. It survives restarts
+ * when the plugin is configured with `PluginConfig::last_device_store`.
+ *
+ * @returns The last picked device, or `null` if none was picked or it was forgotten.
+ */
+export async function getLastDevice(): Promise<LastDevice | null> {
+  return call<LastDevice | null>('get_last_device')
+}
+
+/**
+ * Subscribe to Service Changed (0x2A05) indications for a device.
+ *
+ * {@link connectGATT} does this automatically; use this to re-arm after {@link unwatchServiceChanged}.
+ * Indications invalidate the cached GATT tree and emit `EVENTS.serviceChanged`.
+ *
+ * @param deviceId Device identifier.
+ */
+export async function watchServiceChanged(deviceId: string): Promise<void> {
+  await call('watch_service_changed', { request: { deviceId } })
+}
+
+/**
+ * Stop observing Service Changed indications for a device.
+ *
+ * @param deviceId Device identifier.
+ */
+export async function unwatchServiceChanged(deviceId: string): Promise<void> {
+  await call('unwatch_service_changed', { request: { deviceId } })
+}
+
+/**
+ * Emit `EVENTS.rssiChanged` for a device with its first RSSI sample and whenever it changes by at
+ * least `threshold` dBm. Samples are taken every `intervalMs`.
+ *
+ * The value comes from the last advertisement the adapter received, since live connection RSSI is not
+ * available, so it only updates while the adapter is scanning and the device advertises. Watching
+ * stops on disconnect.
+ *
+ * @param deviceId Device identifier.
+ * @param threshold Minimum change in dBm between events (default: 2).
+ * @param intervalMs Sampling interval (default: 1000).
+ */
+export async function watchRssi(deviceId: string, threshold?: number, intervalMs?: number): Promise<void> {
+  await call('watch_rssi', { request: { deviceId, threshold, intervalMs } })
+}
+
+/**
+ * Stop RSSI events started with {@link watchRssi}.
+ *
+ * @param deviceId Device identifier.
+ */
+export async function unwatchRssi(deviceId: string): Promise<void> {
+  await call('unwatch_rssi', { request: { deviceId } })
+}
+
+/**
+ * Emit `EVENTS.advertisementReceived` whenever the adapter reports new advertisement data for a
+ * device, like Web Bluetooth's `watchAdvertisements()`. The adapter keeps scanning while any device
+ * is watched, and watching continues across disconnects.
+ *
+ * @param deviceId Device identifier.
+ */
+export async function watchAdvertisements(deviceId: string): Promise<void> {
+  await call('watch_advertisements', { request: { deviceId } })
+}
+
+/**
+ * Stop advertisement events started with {@link watchAdvertisements}.
+ *
+ * @param deviceId Device identifier.
+ */
+export async function unwatchAdvertisements(deviceId: string): Promise<void> {
+  await call('unwatch_advertisements', { request: { deviceId } })
+}
+
+/**
+ * Check whether a single device is connected, e.g. to disable buttons while a connect is in flight.
+ *
+ * @param deviceId Device identifier.
+ * @returns `connected`, `connecting` or `disconnected`.
+ */
+export async function getConnectionState(deviceId: string): Promise<ConnectionState> {
+  return call<ConnectionState>('get_connection_state', { request: { deviceId } })
+}
+
+/**
+ * Read the service UUIDs a device is currently advertising, without connecting.
+ *
+ * @param deviceId Device identifier.
+ * @returns Advertised service UUIDs; rejects with `DeviceNotFound` once the adapter forgets the device.
+ */
+export async function getAdvertisedServices(deviceId: string): Promise<string[]> {
+  return call<string[]>('get_advertised_services', { request: { deviceId } })
+}
+
 /**
  * Connect to a device and discover its GATT services.
  *
  * @param deviceId Internal device identifier from {@link getDevices} or {@link requestDevice}.
+ * @param services Only report these services; the result is then marked `partial`.
  * @returns Connection state plus discovered services.
  */
-export async function connectGATT(deviceId: string): Promise<GattServerInfo> {
-  return call<GattServerInfo>('connect_gatt', { request: { deviceId } })
+export async function connectGATT(deviceId: string, services?: string[]): Promise<GattServerInfo> {
+  return call<GattServerInfo>('connect_gatt', { request: { deviceId, services } })
 }
 
 /**
@@ -86,7 +490,38 @@ export async function disconnectGATT(deviceId: string): Promise<void> {
 }
 
 /**
- * Remove a device from the internal cache.
+ * Read the ATT MTU negotiated with a connected device. Only Linux (BlueZ) reports it; elsewhere
+ * the minimum of 23 is returned.
+ *
+ * @param deviceId Connected device identifier.
+ * @returns The MTU in bytes.
+ */
+export async function getMtu(deviceId: string): Promise<number> {
+  return call<number>('get_mtu', { request: { deviceId } })
+}
+
+/**
+ * Drop the cached GATT attribute table for a device and rediscover its services.
+ *
+ * Use this after a firmware update or when the peripheral reports that its services changed.
+ *
+ * @param deviceId Device identifier to refresh.
+ * @returns Connection state plus freshly discovered services.
+ */
+export async function refreshGATT(deviceId: string): Promise<GattServerInfo> {
+  return call<GattServerInfo>('refresh_gatt', { request: { deviceId } })
+}
+
+/**
+ * Stop every subscription, advertisement watcher and background scan and disconnect all cached devices,
+ * e.g. on app exit or logout. A device that fails to disconnect is logged and skipped.
+ */
+export async function disconnectAll(): Promise<void> {
+  await call('disconnect_all')
+}
+
+/**
+ * Stop the device's notifications and watchers, disconnect it and remove it from the internal cache.
  *
  * @param deviceId Device identifier to remove from cache.
  */
@@ -94,6 +529,55 @@ export async function forgetDevice(deviceId: string): Promise<void> {
   await call('forget_device', { request: { deviceId } })
 }
 
+/**
+ * Ask the platform to use the given PHYs for a connection.
+ *
+ * Rejects with `UnsupportedPlatform` where the backend cannot select PHYs (currently all desktop backends).
+ *
+ * @param deviceId Connected device identifier.
+ * @param txPhy Preferred transmit PHY.
+ * @param rxPhy Preferred receive PHY.
+ */
+export async function setPreferredPhy(deviceId: string, txPhy: BluetoothPhy, rxPhy: BluetoothPhy): Promise<void> {
+  await call('set_preferred_phy', { request: { deviceId, txPhy, rxPhy } })
+}
+
+/**
+ * Read the PHYs in use for a connection.
+ *
+ * Rejects with `UnsupportedPlatform` where the backend does not report PHYs.
+ *
+ * @param deviceId Connected device identifier.
+ * @returns Transmit and receive PHYs.
+ */
+export async function getPhy(deviceId: string): Promise<PhyInfo> {
+  return call<PhyInfo>('get_phy', { request: { deviceId } })
+}
+
+/**
+ * Disconnect a device, stop its notifications and drop the live handle, while keeping it known.
+ *
+ * Unlike {@link forgetDevice}, the id stays usable: a later {@link connectGATT} reloads the device
+ * from the adapter.
+ *
+ * @param deviceId Device identifier to release.
+ */
+export async function releaseDevice(deviceId: string): Promise<void> {
+  await call('release_device', { request: { deviceId } })
+}
+
+/**
+ * Get a single primary service, like Web Bluetooth's `getPrimaryService()`.
+ *
+ * @param deviceId Device identifier to query.
+ * @param serviceUuid UUID of the service.
+ * @returns The service with its characteristics; rejects with `ServiceNotFound` when the device has no
+ * such primary service.
+ */
+export async function getPrimaryService(deviceId: string, serviceUuid: string): Promise<BluetoothService> {
+  return call<BluetoothService>('get_primary_service', { request: { deviceId, serviceUuid } })
+}
+
 /**
  * List primary services for a device, optionally filtering by UUID.
  *
@@ -133,74 +617,576 @@ export async function getCharacteristics(
 }
 
 /**
- * Read the value of a characteristic.
- *
- * The returned value is base64 encoded.
+ * List descriptors of a characteristic, optionally filtering by descriptor UUID.
  *
  * @param deviceId Device identifier to query.
  * @param serviceUuid Service UUID containing the characteristic.
- * @param characteristicUuid Characteristic UUID to read.
- * @returns Base64-encoded value of the characteristic.
+ * @param characteristicUuid Characteristic UUID whose descriptors are listed.
+ * @param descriptorUuid Optional descriptor UUID filter.
+ * @returns Matching descriptors of the characteristic.
  */
-export async function readCharacteristicValue(
+export async function getDescriptors(
   deviceId: string,
   serviceUuid: string,
   characteristicUuid: string,
-): Promise<BluetoothValue> {
-  return call<BluetoothValue>('read_characteristic_value', {
-    request: { deviceId, serviceUuid, characteristicUuid },
+  descriptorUuid?: string,
+): Promise<BluetoothDescriptor[]> {
+  return call<BluetoothDescriptor[]>('get_descriptors', {
+    request: { deviceId, serviceUuid, characteristicUuid, descriptorUuid },
   })
 }
 
 /**
- * Write a base64-encoded value to a characteristic.
+ * List what every characteristic of a device supports, for enabling only valid actions in a UI.
  *
- * @param deviceId Device identifier to write to.
- * @param serviceUuid Service UUID containing the characteristic.
- * @param characteristicUuid Characteristic UUID to write.
- * @param value Base64-encoded payload to send.
- * @param withResponse Whether to request a write response (default: true).
+ * Built from the cached attribute table, so no values or descriptors are read.
+ *
+ * @param deviceId Device identifier to query.
+ * @returns One entry per characteristic of the device's allowed services.
  */
-export async function writeCharacteristicValue(
-  deviceId: string,
-  serviceUuid: string,
-  characteristicUuid: string,
-  value: string,
-  withResponse = true,
-): Promise<void> {
-  await call('write_characteristic_value', {
-    request: { deviceId, serviceUuid, characteristicUuid, value, withResponse },
-  })
+export async function getCharacteristicCapabilities(deviceId: string): Promise<CharacteristicCapabilities[]> {
+  return call<CharacteristicCapabilities[]>('get_characteristic_capabilities', { request: { deviceId } })
 }
 
 /**
- * Subscribe to notifications for a characteristic.
+ * Describe a characteristic together with its decoded standard descriptors.
  *
- * @param deviceId Device identifier to subscribe on.
- * @param serviceUuid Service UUID containing the characteristic.
- * @param characteristicUuid Characteristic UUID to subscribe to.
+ * Reads the user description, client configuration, presentation format and extended properties
+ * descriptors. Descriptors the characteristic lacks are omitted.
+ *
+ * @param deviceId Device identifier.
+ * @param serviceUuid Service UUID.
+ * @param characteristicUuid Characteristic UUID.
  */
-export async function startNotifications(deviceId: string, serviceUuid: string, characteristicUuid: string): Promise<void> {
-  await call('start_notifications', {
+export async function describeCharacteristic(
+  deviceId: string,
+  serviceUuid: string,
+  characteristicUuid: string,
+): Promise<CharacteristicDetails> {
+  return call<CharacteristicDetails>('describe_characteristic', {
     request: { deviceId, serviceUuid, characteristicUuid },
   })
 }
 
 /**
- * Stop notifications for a characteristic.
+ * Read the Battery Level of every Battery Service instance, e.g. both earbuds of a pair.
  *
- * @param deviceId Device identifier to unsubscribe from.
- * @param serviceUuid Service UUID containing the characteristic.
- * @param characteristicUuid Characteristic UUID to unsubscribe from.
+ * Only BlueZ can tell instances apart; other backends report at most one.
+ *
+ * @param deviceId Device identifier to query.
+ * @returns One level per instance; empty when the device has no Battery Service.
  */
-export async function stopNotifications(deviceId: string, serviceUuid: string, characteristicUuid: string): Promise<void> {
-  await call('stop_notifications', {
-    request: { deviceId, serviceUuid, characteristicUuid },
-  })
+export async function readAllBatteryLevels(deviceId: string): Promise<BatteryLevel[]> {
+  return call<BatteryLevel[]>('read_all_battery_levels', { request: { deviceId } })
 }
 
 /**
- * Listen for characteristic value changes emitted by the plugin.
+ * Read the firmware (0x2A26), hardware (0x2A27) and software (0x2A28) revision strings from the
+ * Device Information Service.
+ *
+ * @param deviceId Device identifier to query.
+ * @returns Revision strings; characteristics the device lacks are `null`.
+ */
+export async function readRevisions(deviceId: string): Promise<DeviceRevisions> {
+  return call<DeviceRevisions>('read_revisions', { request: { deviceId } })
+}
+
+/**
+ * Read the GAP Appearance (0x2A01) of a connected device and label it.
+ *
+ * Once read, the appearance is also reported on {@link BluetoothDevice.appearance}.
+ *
+ * @param deviceId Device identifier to query.
+ * @returns Raw appearance code with category and sub-category labels.
+ */
+export async function readAppearance(deviceId: string): Promise<Appearance> {
+  return call<Appearance>('read_appearance', { request: { deviceId } })
+}
+
+/**
+ * Read the value of a characteristic.
+ *
+ * The returned value is base64 encoded.
+ *
+ * @param deviceId Device identifier to query.
+ * @param serviceUuid Service UUID containing the characteristic.
+ * @param characteristicUuid Characteristic UUID to read.
+ * @returns Base64-encoded value of the characteristic.
+ */
+export async function readCharacteristicValue(
+  deviceId: string,
+  serviceUuid: string,
+  characteristicUuid: string,
+): Promise<BluetoothValue> {
+  return call<BluetoothValue>('read_characteristic_value', {
+    request: { deviceId, serviceUuid, characteristicUuid },
+  })
+}
+
+/**
+ * Measure read round-trip latency to a connected device.
+ *
+ * Each sample is one GATT read of the probe characteristic: the given one, or else the first readable
+ * characteristic (preferring the GAP Device Name). The result is an approximation.
+ *
+ * @param deviceId Device identifier.
+ * @param samples Number of reads to average over, 1 to 100 (default: 1).
+ * @param probe Characteristic to read instead of the default probe.
+ */
+export async function pingDevice(
+  deviceId: string,
+  samples?: number,
+  probe?: { serviceUuid: string; characteristicUuid: string },
+): Promise<PingResult> {
+  return call<PingResult>('ping_device', { request: { deviceId, samples, ...probe } })
+}
+
+/**
+ * Wait for a device to disconnect.
+ *
+ * Resolves immediately when the device is already disconnected.
+ *
+ * @param deviceId Device identifier.
+ * @param timeoutMs How long to wait before rejecting with `OperationTimeout`.
+ */
+export async function awaitDisconnect(deviceId: string, timeoutMs: number): Promise<void> {
+  await call('await_disconnect', { request: { deviceId, timeoutMs } })
+}
+
+/**
+ * Wait until a characteristic holds a value meeting `condition`, e.g. the reply to a command
+ * just written. The current value is checked first; notifying characteristics are subscribed to
+ * for the wait (an existing subscription is left alone), others are polled.
+ *
+ * @param deviceId Device identifier.
+ * @param serviceUuid Service UUID containing the characteristic.
+ * @param characteristicUuid Characteristic UUID to watch.
+ * @param condition Exact value or prefix to wait for, base64 encoded.
+ * @param timeoutMs How long to wait before rejecting with `OperationTimeout`.
+ * @param pollIntervalMs Read interval for characteristics that cannot notify (default: 100).
+ * @returns The first matching value, base64 encoded.
+ */
+export async function awaitValue(
+  deviceId: string,
+  serviceUuid: string,
+  characteristicUuid: string,
+  condition: ValueCondition,
+  timeoutMs: number,
+  pollIntervalMs?: number,
+): Promise<BluetoothValue> {
+  return call<BluetoothValue>('await_value', {
+    request: { deviceId, serviceUuid, characteristicUuid, condition, timeoutMs, pollIntervalMs },
+  })
+}
+
+/**
+ * Start the background listener for adapter events such as disconnects. It runs by default; this is a
+ * no-op while it is already running.
+ */
+export async function startEventMonitor(): Promise<void> {
+  await call('start_event_monitor')
+}
+
+/**
+ * Stop the background adapter event listener. Until restarted, `gattServerDisconnected` is not
+ * emitted and {@link awaitDisconnect} will not resolve on disconnect.
+ */
+export async function stopEventMonitor(): Promise<void> {
+  await call('stop_event_monitor')
+}
+
+/**
+ * Read a characteristic and receive the value in chunks instead of one large payload.
+ *
+ * The backend completes the (long) read before chunks are delivered, so progress reflects transfer to
+ * the frontend rather than the over-the-air read.
+ *
+ * @param deviceId Device identifier.
+ * @param serviceUuid Service UUID.
+ * @param characteristicUuid Characteristic UUID.
+ * @param onChunk Called for each chunk in order; the last one has `done: true`.
+ * @param chunkSize Bytes per chunk (default: 512).
+ * @returns The total number of bytes read.
+ */
+export async function readLongStreamed(
+  deviceId: string,
+  serviceUuid: string,
+  characteristicUuid: string,
+  onChunk: (chunk: ReadChunk) => void,
+  chunkSize?: number,
+): Promise<ReadLongSummary> {
+  const channel = new Channel<ReadChunk>()
+  channel.onmessage = onChunk
+  return call<ReadLongSummary>('read_long_streamed', {
+    request: { deviceId, serviceUuid, characteristicUuid, chunkSize },
+    onChunk: channel,
+  })
+}
+
+/**
+ * Read characteristics from several devices concurrently in one call.
+ *
+ * Each item succeeds or fails on its own; disconnected devices yield a per-item error instead of
+ * failing the whole batch. Results are returned in request order.
+ *
+ * @param reads Characteristics to read.
+ * @param maxConcurrency Maximum reads in flight at once (default: 4).
+ * @returns One result per read with either a base64 `value` or an `error`.
+ */
+export async function readAcrossDevices(reads: ReadValueRequest[], maxConcurrency?: number): Promise<BatchReadResult[]> {
+  return call<BatchReadResult[]>('read_across_devices', { request: { reads, maxConcurrency } })
+}
+
+/**
+ * Read a characteristic and also deliver the value through {@link onCharacteristicValueChanged}.
+ *
+ * The emitted payload has `fromRead` set so a single handler can process reads and notifications alike.
+ *
+ * @param deviceId Device identifier to query.
+ * @param serviceUuid Service UUID containing the characteristic.
+ * @param characteristicUuid Characteristic UUID to read.
+ * @returns Base64-encoded value of the characteristic.
+ */
+export async function readAndEmit(
+  deviceId: string,
+  serviceUuid: string,
+  characteristicUuid: string,
+): Promise<BluetoothValue> {
+  return call<BluetoothValue>('read_and_emit', {
+    request: { deviceId, serviceUuid, characteristicUuid },
+  })
+}
+
+/**
+ * Read the value of any descriptor, such as the Characteristic User Description (0x2901).
+ *
+ * @param deviceId Device identifier to query.
+ * @param serviceUuid Service UUID containing the characteristic.
+ * @param characteristicUuid Characteristic UUID owning the descriptor.
+ * @param descriptorUuid Descriptor UUID to read.
+ * @param interpret Also decode standard descriptors (0x2900, 0x2901, 0x2902, 0x2904).
+ * @returns Base64-encoded value and, with `interpret`, its typed form.
+ */
+export async function readDescriptorValue(
+  deviceId: string,
+  serviceUuid: string,
+  characteristicUuid: string,
+  descriptorUuid: string,
+  interpret = false,
+): Promise<DescriptorValue> {
+  return call<DescriptorValue>('read_descriptor_value', {
+    request: { deviceId, serviceUuid, characteristicUuid, descriptorUuid, interpret },
+  })
+}
+
+/**
+ * Read several descriptors of a characteristic one after another.
+ *
+ * @param deviceId Device identifier to query.
+ * @param serviceUuid Service UUID containing the characteristic.
+ * @param characteristicUuid Characteristic UUID owning the descriptors.
+ * @param descriptorUuids Descriptors to read; all of them when omitted.
+ * @returns Results keyed by descriptor UUID, each with a base64 value or an error.
+ */
+export async function readDescriptors(
+  deviceId: string,
+  serviceUuid: string,
+  characteristicUuid: string,
+  descriptorUuids?: string[],
+): Promise<Record<string, DescriptorReadResult>> {
+  return call<Record<string, DescriptorReadResult>>('read_descriptors', {
+    request: { deviceId, serviceUuid, characteristicUuid, descriptorUuids },
+  })
+}
+
+/**
+ * Write a descriptor from a base64-encoded payload or, for standard descriptors, a typed value.
+ *
+ * @param deviceId Device identifier to write to.
+ * @param serviceUuid Service UUID containing the characteristic.
+ * @param characteristicUuid Characteristic UUID owning the descriptor.
+ * @param descriptorUuid Descriptor UUID to write.
+ * @param value Base64-encoded payload, or a typed value whose kind matches `descriptorUuid`.
+ */
+export async function writeDescriptorValue(
+  deviceId: string,
+  serviceUuid: string,
+  characteristicUuid: string,
+  descriptorUuid: string,
+  value: string | StandardDescriptorValue,
+): Promise<void> {
+  const payload = typeof value === 'string' ? { value } : { interpreted: value }
+  await call('write_descriptor_value', {
+    request: { deviceId, serviceUuid, characteristicUuid, descriptorUuid, ...payload },
+  })
+}
+
+/**
+ * Write a base64-encoded value to a characteristic.
+ *
+ * @param deviceId Device identifier to write to.
+ * @param serviceUuid Service UUID containing the characteristic.
+ * @param characteristicUuid Characteristic UUID to write.
+ * @param value Base64-encoded payload to send.
+ * @param writeType Write procedure, or a boolean for with/without response (default: true).
+ * @param chunkSize Split values longer than this many bytes into sequential writes; defaults to
+ * the known MTU for writes without response, else the plugin's `write_chunk_size`, if any.
+ */
+export async function writeCharacteristicValue(
+  deviceId: string,
+  serviceUuid: string,
+  characteristicUuid: string,
+  value: string,
+  writeType: boolean | WriteTypeKind = true,
+  chunkSize?: number,
+): Promise<void> {
+  const mode = typeof writeType === 'boolean' ? { withResponse: writeType } : { writeType }
+  await call('write_characteristic_value', {
+    request: { deviceId, serviceUuid, characteristicUuid, value, ...mode, chunkSize },
+  })
+}
+
+/**
+ * Write several characteristics of one device in order, stopping at the first failure.
+ *
+ * GATT has no transactions, so the sequence is not atomic. With `rollbackOnFailure`, readable
+ * characteristics are read before being written and restored if a later write fails; writes to
+ * characteristics that cannot be read stay applied and `rolledBack` is reported as `false`.
+ *
+ * @param deviceId Device identifier to write to.
+ * @param writes Ordered writes with base64-encoded values.
+ * @param rollbackOnFailure Restore previous values when a write fails (default: false).
+ * @returns How many writes completed, and the failing index and error if the sequence stopped.
+ */
+export async function writeSequence(
+  deviceId: string,
+  writes: SequenceWrite[],
+  rollbackOnFailure = false,
+): Promise<WriteSequenceResult> {
+  return call<WriteSequenceResult>('write_sequence', {
+    request: { deviceId, writes, rollbackOnFailure },
+  })
+}
+
+/**
+ * Register how values of a characteristic are decoded into the `interpreted` field of value events.
+ *
+ * Registrations last for the session. Device-scoped ones take precedence over global ones and are
+ * cleared by {@link forgetDevice}.
+ *
+ * @param characteristicUuid Characteristic UUID.
+ * @param format Decode format.
+ * @param deviceId Limit the registration to one device.
+ */
+export async function registerCharacteristicFormat(
+  characteristicUuid: string,
+  format: ValueFormat,
+  deviceId?: string,
+): Promise<void> {
+  await call('register_characteristic_format', { request: { characteristicUuid, format, deviceId } })
+}
+
+/**
+ * Subscribe to notifications for a characteristic.
+ *
+ * @param deviceId Device identifier to subscribe on.
+ * @param serviceUuid Service UUID containing the characteristic.
+ * @param characteristicUuid Characteristic UUID to subscribe to.
+ * @param onDuplicate What to do if already subscribed (default: the plugin's configured policy).
+ */
+export async function startNotifications(
+  deviceId: string,
+  serviceUuid: string,
+  characteristicUuid: string,
+  onDuplicate?: DuplicateNotificationPolicy,
+): Promise<void> {
+  await call('start_notifications', {
+    request: { deviceId, serviceUuid, characteristicUuid, onDuplicate },
+  })
+}
+
+/**
+ * Stop notifications for a characteristic.
+ *
+ * @param deviceId Device identifier to unsubscribe from.
+ * @param serviceUuid Service UUID containing the characteristic.
+ * @param characteristicUuid Characteristic UUID to unsubscribe from.
+ */
+export async function stopNotifications(deviceId: string, serviceUuid: string, characteristicUuid: string): Promise<void> {
+  await call('stop_notifications', {
+    request: { deviceId, serviceUuid, characteristicUuid },
+  })
+}
+
+/**
+ * Stop emitting notification events while keeping the subscription open on the device.
+ *
+ * Lighter than stopping and restarting notifications for transient UI changes.
+ *
+ * @param deviceId Device identifier.
+ * @param serviceUuid Service UUID containing the characteristic.
+ * @param characteristicUuid Characteristic UUID to pause.
+ */
+export async function pauseNotifications(deviceId: string, serviceUuid: string, characteristicUuid: string): Promise<void> {
+  await call('pause_notifications', {
+    request: { deviceId, serviceUuid, characteristicUuid },
+  })
+}
+
+/**
+ * Resume notification events paused with {@link pauseNotifications}.
+ *
+ * @param deviceId Device identifier.
+ * @param serviceUuid Service UUID containing the characteristic.
+ * @param characteristicUuid Characteristic UUID to resume.
+ * @param emitLatest Emit the most recent value received while paused (default: `false`).
+ */
+export async function resumeNotifications(
+  deviceId: string,
+  serviceUuid: string,
+  characteristicUuid: string,
+  emitLatest = false,
+): Promise<void> {
+  await call('resume_notifications', {
+    request: { deviceId, serviceUuid, characteristicUuid, emitLatest },
+  })
+}
+
+/**
+ * Receive value updates for a characteristic however the device supports it.
+ *
+ * Subscribes when the characteristic can notify or indicate, reads the current value once when it is
+ * readable, and falls back to polling for read-only characteristics. Every update, including the initial
+ * read, is delivered through {@link onCharacteristicValueChanged}.
+ *
+ * Polling keeps the radio busy and drains noticeably more battery on both ends than notifications;
+ * prefer the longest interval your UI can tolerate.
+ *
+ * @param deviceId Device identifier to monitor.
+ * @param serviceUuid Service UUID containing the characteristic.
+ * @param characteristicUuid Characteristic UUID to monitor.
+ * @param pollIntervalMs Polling interval for read-only characteristics (default: 1000).
+ * @returns The chosen delivery mode and the initial value when one was read.
+ */
+export async function monitorCharacteristic(
+  deviceId: string,
+  serviceUuid: string,
+  characteristicUuid: string,
+  pollIntervalMs?: number,
+): Promise<MonitorInfo> {
+  return call<MonitorInfo>('monitor_characteristic', {
+    request: { deviceId, serviceUuid, characteristicUuid, pollIntervalMs },
+  })
+}
+
+/**
+ * Stop updates started with {@link monitorCharacteristic}, cancelling the subscription or polling task.
+ *
+ * @param deviceId Device identifier being monitored.
+ * @param serviceUuid Service UUID containing the characteristic.
+ * @param characteristicUuid Characteristic UUID being monitored.
+ */
+export async function stopMonitoring(deviceId: string, serviceUuid: string, characteristicUuid: string): Promise<void> {
+  await call('stop_monitoring', {
+    request: { deviceId, serviceUuid, characteristicUuid },
+  })
+}
+
+/**
+ * Read a characteristic on a timer and deliver the values through {@link onCharacteristicValueChanged}.
+ *
+ * With `aggregate`, one event is emitted per `window` successful samples: `interpreted` holds the
+ * min, max or average of the decoded samples and `value` the window's last raw read. Polling stops
+ * on {@link stopPolling} or when the device disconnects.
+ *
+ * @param deviceId Device identifier to poll.
+ * @param serviceUuid Service UUID containing the characteristic.
+ * @param characteristicUuid Readable characteristic UUID.
+ * @param intervalMs Delay between reads (default: 1000).
+ * @param aggregate Optional downsampling over a window of samples.
+ */
+export async function pollCharacteristic(
+  deviceId: string,
+  serviceUuid: string,
+  characteristicUuid: string,
+  intervalMs?: number,
+  aggregate?: PollAggregate,
+): Promise<void> {
+  await call('poll_characteristic', {
+    request: { deviceId, serviceUuid, characteristicUuid, intervalMs, aggregate },
+  })
+}
+
+/**
+ * Stop a polling task started with {@link pollCharacteristic}. Rejects when the characteristic is not
+ * being polled, e.g. while it is subscribed to with {@link startNotifications}.
+ *
+ * @param deviceId Device identifier being polled.
+ * @param serviceUuid Service UUID containing the characteristic.
+ * @param characteristicUuid Characteristic UUID being polled.
+ */
+export async function stopPolling(deviceId: string, serviceUuid: string, characteristicUuid: string): Promise<void> {
+  await call('stop_polling', {
+    request: { deviceId, serviceUuid, characteristicUuid },
+  })
+}
+
+/**
+ * Report how many notification events were emitted or dropped because the emit queue overflowed.
+ *
+ * @returns Totals since startup plus the configured queue capacity and overflow policy.
+ */
+export async function getNotificationStats(): Promise<NotificationStats> {
+  return call<NotificationStats>('get_notification_stats')
+}
+
+/**
+ * Report what the latest subscription to a characteristic has delivered, to check whether a sensor
+ * is actually streaming. Counters reset when notifications are started again.
+ */
+export async function getCharacteristicStats(
+  deviceId: string,
+  characteristicUuid: string
+): Promise<CharacteristicStats> {
+  return call<CharacteristicStats>('get_characteristic_stats', {
+    request: { deviceId, characteristicUuid },
+  })
+}
+
+/**
+ * Replay a scan recorded with `PluginConfig::record_scan` through the device picker, with its
+ * original timings. Requires the `scan-replay` Cargo feature and the `allow-replay-scan`
+ * permission. The returned device is not backed by real hardware.
+ *
+ * @param path Recording file on the host.
+ * @returns The device picked from the replayed list.
+ */
+export async function replayScan(path: string): Promise<BluetoothDevice> {
+  return call<BluetoothDevice>('replay_scan', { request: { path } })
+}
+
+/**
+ * Dump a redacted view of the plugin's internal state. Intended for debugging only; the command is
+ * disabled in release builds unless enabled with `PluginConfig::debug_snapshot`.
+ */
+export async function getDebugSnapshot(): Promise<DebugSnapshot> {
+  return call<DebugSnapshot>('get_debug_snapshot')
+}
+
+/**
+ * List the plugin's running background tasks, e.g. to verify cleanup after a teardown.
+ *
+ * @returns Subscriptions, polling loops, watchers, the event listener and any scan in flight.
+ */
+export async function listActiveTasks(): Promise<ActiveTasks> {
+  return call<ActiveTasks>('list_active_tasks')
+}
+
+/**
+ * Listen for characteristic value changes emitted by the plugin.
+ *
+ * While registered, the listener sends heartbeats so the plugin can stop subscriptions nobody
+ * listens to when `PluginConfig::auto_unsubscribe` is enabled.
  *
  * @param handler Callback receiving {@link NotificationEventPayload}.
  * @returns Unlisten function that removes the listener when called.
@@ -211,7 +1197,18 @@ export async function onCharacteristicValueChanged(
   const unlisten = await listen<NotificationEventPayload>(EVENTS.characteristicValueChanged, (event) => {
     handler(event.payload)
   })
-  return unlisten
+  const listenerId = `${Date.now().toString(36)}-${Math.random().toString(36).slice(2)}`
+  const heartbeat = (active: boolean) => {
+    // Heartbeats are advisory; a missing permission must not break the listener.
+    call('notification_listener_heartbeat', { request: { listenerId, active } }).catch(() => {})
+  }
+  heartbeat(true)
+  const timer = setInterval(() => heartbeat(true), LISTENER_HEARTBEAT_MS)
+  return () => {
+    clearInterval(timer)
+    heartbeat(false)
+    unlisten()
+  }
 }
 
 /**
@@ -229,16 +1226,229 @@ export async function onGattServerDisconnected(
   return unlisten
 }
 
+/**
+ * Listen for devices reconnected automatically after an unexpected disconnect.
+ *
+ * Only emitted when auto-reconnect is enabled in the desktop builder. Subscriptions active before
+ * the disconnect have been restored when this fires.
+ *
+ * @param handler Callback receiving {@link DeviceEventPayload}.
+ * @returns Unlisten function that removes the listener when called.
+ */
+export async function onGattServerConnected(
+  handler: (payload: DeviceEventPayload) => void,
+): Promise<UnlistenFn> {
+  const unlisten = await listen<DeviceEventPayload>(EVENTS.gattServerConnected, (event) => {
+    handler(event.payload)
+  })
+  return unlisten
+}
+
+/**
+ * Listen for the adapter appearing, disappearing or being powered on or off, like Web Bluetooth's
+ * `availabilitychanged` event.
+ *
+ * @param handler Callback receiving {@link AvailabilityChangedPayload}.
+ * @returns Unlisten function that removes the listener when called.
+ */
+export async function onAvailabilityChanged(
+  handler: (payload: AvailabilityChangedPayload) => void,
+): Promise<UnlistenFn> {
+  const unlisten = await listen<AvailabilityChangedPayload>(EVENTS.availabilityChanged, (event) => {
+    handler(event.payload)
+  })
+  return unlisten
+}
+
+/**
+ * Listen for devices that automatic reconnection gave up on.
+ *
+ * @param handler Callback receiving {@link ReconnectFailedPayload}.
+ * @returns Unlisten function that removes the listener when called.
+ */
+export async function onReconnectFailed(
+  handler: (payload: ReconnectFailedPayload) => void,
+): Promise<UnlistenFn> {
+  const unlisten = await listen<ReconnectFailedPayload>(EVENTS.reconnectFailed, (event) => {
+    handler(event.payload)
+  })
+  return unlisten
+}
+
+/**
+ * Listen for notification streams that ended without {@link stopNotifications} being called.
+ *
+ * Some backends drop the stream when a peripheral goes away without reporting a disconnect; the
+ * subscription is no longer active once this fires.
+ *
+ * @param handler Callback receiving {@link CharacteristicEventPayload}.
+ * @returns Unlisten function that removes the listener when called.
+ */
+export async function onNotificationsEnded(
+  handler: (payload: CharacteristicEventPayload) => void,
+): Promise<UnlistenFn> {
+  const unlisten = await listen<CharacteristicEventPayload>(EVENTS.notificationsEnded, (event) => {
+    handler(event.payload)
+  })
+  return unlisten
+}
+
+/**
+ * Listen for every device event through one handler, switching on `kind`.
+ *
+ * Requires unified events to be enabled in the desktop builder; the individual events keep firing
+ * either way.
+ *
+ * @param handler Callback receiving {@link EventEnvelope}.
+ * @returns Unlisten function that removes the listener when called.
+ */
+export async function onBluetoothEvent(handler: (envelope: EventEnvelope) => void): Promise<UnlistenFn> {
+  const unlisten = await listen<EventEnvelope>(EVENTS.event, (event) => {
+    handler(event.payload)
+  })
+  return unlisten
+}
+
+/**
+ * Listen for Service Changed indications. The device's cached GATT tree is invalidated before this
+ * fires, so the next GATT call rediscovers services.
+ *
+ * @param handler Callback receiving {@link ServiceChangedPayload}.
+ * @returns Unlisten function that removes the listener when called.
+ */
+export async function onServiceChanged(handler: (payload: ServiceChangedPayload) => void): Promise<UnlistenFn> {
+  const unlisten = await listen<ServiceChangedPayload>(EVENTS.serviceChanged, (event) => {
+    handler(event.payload)
+  })
+  return unlisten
+}
+
+/**
+ * Listen for RSSI changes reported by {@link watchRssi}.
+ *
+ * @param handler Callback receiving {@link RssiChangedPayload}.
+ * @returns Unlisten function that removes the listener when called.
+ */
+export async function onRssiChanged(handler: (payload: RssiChangedPayload) => void): Promise<UnlistenFn> {
+  const unlisten = await listen<RssiChangedPayload>(EVENTS.rssiChanged, (event) => {
+    handler(event.payload)
+  })
+  return unlisten
+}
+
+/**
+ * Listen for advertisements reported by {@link watchAdvertisements}.
+ *
+ * @param handler Callback receiving {@link AdvertisementReceivedPayload}.
+ * @returns Unlisten function that removes the listener when called.
+ */
+export async function onAdvertisementReceived(
+  handler: (payload: AdvertisementReceivedPayload) => void
+): Promise<UnlistenFn> {
+  const unlisten = await listen<AdvertisementReceivedPayload>(EVENTS.advertisementReceived, (event) => {
+    handler(event.payload)
+  })
+  return unlisten
+}
+
+/**
+ * Listen for devices found by {@link startScan}.
+ *
+ * @param handler Callback receiving the newly matched {@link BluetoothDevice}.
+ * @returns Unlisten function that removes the listener when called.
+ */
+export async function onDeviceDiscovered(handler: (device: BluetoothDevice) => void): Promise<UnlistenFn> {
+  const unlisten = await listen<BluetoothDevice>(EVENTS.deviceDiscovered, (event) => {
+    handler(event.payload)
+  })
+  return unlisten
+}
+
+/**
+ * Listen for changes to devices found by {@link startScan}, such as a new RSSI or advertisement data.
+ *
+ * @param handler Callback receiving the updated {@link BluetoothDevice}.
+ * @returns Unlisten function that removes the listener when called.
+ */
+export async function onDeviceUpdated(handler: (device: BluetoothDevice) => void): Promise<UnlistenFn> {
+  const unlisten = await listen<BluetoothDevice>(EVENTS.deviceUpdated, (event) => {
+    handler(event.payload)
+  })
+  return unlisten
+}
+
 export type {
+  AdapterCapabilities,
+  LocalAdapterAddress,
+  AdapterInfo,
+  PermissionStatus,
+  ConnectionState,
   RequestDeviceOptions,
+  KnownDeviceExclusion,
   DeviceFilter,
+  ManufacturerDataFilter,
+  ServiceDataFilter,
+  ServicesMatch,
   BluetoothDevice,
+  Appearance,
+  AdvertisementSnapshot,
+  SelectionInfo,
+  SelectionMode,
+  SelectionUpdatePayload,
+  SelectionHandlerInfo,
   GattServerInfo,
+  LastDevice,
+  BluetoothError,
+  BluetoothErrorName,
+  DiscoveryError,
   BluetoothService,
   BluetoothCharacteristic,
   CharacteristicProperties,
   BluetoothDescriptor,
+  CharacteristicCapabilities,
+  CharacteristicDetails,
+  ClientConfiguration,
+  PresentationFormat,
+  ExtendedProperties,
+  StandardDescriptorValue,
+  DescriptorValue,
+  DescriptorReadResult,
   BluetoothValue,
+  ValueCondition,
+  ReadValueRequest,
+  BatchReadResult,
+  BatteryLevel,
+  DeviceRevisions,
+  PingResult,
+  ReadChunk,
+  ReadLongSummary,
+  SequenceWrite,
+  WriteSequenceResult,
+  WriteTypeKind,
+  BluetoothPhy,
+  PhyInfo,
+  MonitorMode,
+  MonitorInfo,
+  AggregateFunction,
+  PollAggregate,
+  NotificationOverflowPolicy,
+  DuplicateNotificationPolicy,
+  NotificationStats,
+  CharacteristicStats,
+  DebugSnapshot,
+  ActiveTasks,
+  CharacteristicTask,
   NotificationEventPayload,
+  ValueFormat,
+  CharacteristicEventPayload,
+  ServiceChangedPayload,
+  RssiChangedPayload,
+  AdvertisementReceivedPayload,
   DeviceEventPayload,
+  DeviceRequestPayload,
+  ReconnectFailedPayload,
+  AvailabilityChangedPayload,
+  EventEnvelope,
+  EventKind,
+  EventPayloads,
 } from './types'
@@ -4,17 +4,119 @@
 export interface RequestDeviceOptions {
   acceptAllDevices?: boolean
   filters?: DeviceFilter[]
+  /**
+   * Services the page may access besides those named in `filters`. As in Web Bluetooth, other services
+   * of the selected device are rejected with a security error.
+   */
   optionalServices?: string[]
+  /** Defaults to the app-wide value set with `setDefaultScanTimeout` (initially 10 000 ms). */
   scanTimeoutMs?: number
+  /** Hide devices the app already knows about (default `'none'`). */
+  excludeKnownDevices?: KnownDeviceExclusion
 }
 
+/**
+ * Devices left out of the picker: those connected to this host, or every device the plugin has already
+ * returned or looked up. Taken when the request starts.
+ */
+export type KnownDeviceExclusion = 'none' | 'connected' | 'cached'
+
 /**
  * Filter for narrowing device discovery.
  */
 export interface DeviceFilter {
   services?: string[]
+  /** Require all listed services (default, as in Web Bluetooth) or any one of them. */
+  servicesMatch?: ServicesMatch
   name?: string
   namePrefix?: string
+  /** Every entry must match the device's manufacturer specific data. */
+  manufacturerData?: ManufacturerDataFilter[]
+  /** Every entry must match the device's service data. */
+  serviceData?: ServiceDataFilter[]
+}
+
+/**
+ * Matches manufacturer data for one company: the data must start with `dataPrefix` after both are
+ * masked with `mask`. Without `dataPrefix`, any data from the company matches.
+ */
+export interface ManufacturerDataFilter {
+  companyIdentifier: number
+  /** Base64-encoded prefix. */
+  dataPrefix?: string
+  /** Base64-encoded mask, as long as `dataPrefix`; defaults to all ones. */
+  mask?: string
+}
+
+/**
+ * Matches the service data advertised for one service UUID, with the same prefix and mask rules
+ * as {@link ManufacturerDataFilter}.
+ */
+export interface ServiceDataFilter {
+  service: string
+  /** Base64-encoded prefix. */
+  dataPrefix?: string
+  /** Base64-encoded mask, as long as `dataPrefix`; defaults to all ones. */
+  mask?: string
+}
+
+/**
+ * Quantifier applied to {@link DeviceFilter.services}.
+ */
+export type ServicesMatch = 'all' | 'any'
+
+/**
+ * Outcome of `requestPermission`.
+ */
+export type PermissionStatus = 'granted' | 'denied'
+
+/**
+ * Outcome of `getConnectionState`. `connecting` covers `connectGatt`, connecting before a subscription
+ * and automatic reconnects.
+ */
+export type ConnectionState = 'disconnected' | 'connecting' | 'connected'
+
+/**
+ * Identity of the local controller; `null` when the platform does not expose it.
+ */
+export interface LocalAdapterAddress {
+  /** Controller BD_ADDR. */
+  address?: string | null
+  /** A random private address may change and should not identify the machine. */
+  randomAddress?: boolean | null
+  name?: string | null
+}
+
+/**
+ * A Bluetooth adapter of the host, as listed by `getAdapters`.
+ */
+export interface AdapterInfo {
+  /** Position in the platform's adapter list. */
+  index: number
+  /** Platform identifier, e.g. `hci1` on Linux. */
+  id: string
+  /** Raw adapter description reported by the platform. */
+  info: string
+  /** Controller BD_ADDR; only reported on Linux. */
+  address?: string | null
+  /** Friendly name; only reported on Linux. */
+  name?: string | null
+  /** `null` when the platform cannot tell. */
+  powered?: boolean | null
+  /** Whether the adapter can be used, i.e. it is not powered off. */
+  available: boolean
+  /** Whether the plugin uses this adapter. */
+  selected: boolean
+}
+
+/**
+ * Controller features reported by the host; `undefined` when unknown.
+ */
+export interface AdapterCapabilities {
+  extendedAdvertising?: boolean
+  codedPhy?: boolean
+  le2mPhy?: boolean
+  maxConnections?: number
 }
 
 /**
@@ -26,6 +128,113 @@ export interface BluetoothDevice {
   uuids: string[]
   watchingAdvertisements: boolean
   connected: boolean
+  /** Set once the GAP Appearance has been read for this device. */
+  appearance?: Appearance
+  /** Vendor from the manufacturer data company id; requires `PluginConfig::manufacturer_names`. */
+  manufacturerName?: string | null
+  /** Signal strength in dBm from the latest advertisement seen while scanning. */
+  rssi?: number | null
+  /** Advertised transmit power in dBm. */
+  txPower?: number | null
+  /** Manufacturer specific data keyed by company identifier, base64 encoded. */
+  manufacturerData: Record<string, string>
+  /** Set when a data blob was cut to `PluginConfig::max_advertisement_data_len`. */
+  truncated: boolean
+  /** Bonded with the host OS. Only reported by `getBondedDevices`. */
+  paired: boolean
+}
+
+/**
+ * Battery Level of one Battery Service instance.
+ */
+export interface BatteryLevel {
+  /** Opaque id telling instances on the same device apart. */
+  serviceInstanceId: string
+  /** Percent, 0-100. */
+  level: number
+}
+
+/**
+ * GAP Appearance code with its assigned-numbers labels.
+ */
+export interface Appearance {
+  value: number
+  category: string
+  subcategory?: string
+}
+
+/**
+ * Advertisement data seen for a device. Binary values are base64 encoded.
+ */
+export interface AdvertisementSnapshot {
+  name?: string
+  rssi?: number
+  txPower?: number
+  uuids: string[]
+  /** Keyed by company identifier. */
+  manufacturerData: Record<string, string>
+  /** Keyed by service UUID. */
+  serviceData: Record<string, string>
+  /** Set when a data blob was cut to `PluginConfig::max_advertisement_data_len`. */
+  truncated: boolean
+}
+
+/**
+ * Device most recently picked through `requestDevice`.
+ */
+export interface LastDevice {
+  deviceId: string
+  name?: string | null
+  /** Milliseconds since the Unix epoch. */
+  selectedAtMs: number
+}
+
+/**
+ * Details about the device picked by the last `requestDevice` call.
+ */
+export interface SelectionInfo {
+  device: BluetoothDevice
+  /** Index of the first filter that matched; absent when only `acceptAllDevices` matched. */
+  matchedFilterIndex?: number
+  advertisement: AdvertisementSnapshot
+}
+
+/**
+ * Kind of device picker the plugin was configured with.
+ */
+export type SelectionMode = 'nativeDialog' | 'firstMatch' | 'index' | 'event' | 'custom'
+
+/**
+ * Picker request emitted to the app's window by an `EventSelectionHandler`.
+ */
+export interface DeviceRequestPayload {
+  requestId: string
+  /** Event the answer is emitted on; see `answerDeviceRequest`. */
+  selectionEvent: string
+  /** Event carrying {@link SelectionUpdatePayload} while the scan runs. */
+  updateEvent: string
+  options: RequestDeviceOptions
+  devices: BluetoothDevice[]
+  /** Whether the scan is still running and updates will follow. */
+  scanning: boolean
+  /** Set for `requestDevices`, where several devices may be picked. */
+  multiple: boolean
+}
+
+/**
+ * Scan progress sent to the picker; `devices` is the full list so far.
+ */
+export interface SelectionUpdatePayload {
+  devices: BluetoothDevice[]
+  completed: boolean
+}
+
+/**
+ * Description of the configured selection handler.
+ */
+export interface SelectionHandlerInfo {
+  mode: SelectionMode
+  wantsFullScan: boolean
 }
 
 /**
@@ -35,6 +244,19 @@ export interface GattServerInfo {
   deviceId: string
   connected: boolean
   services: BluetoothService[]
+  /** `true` when `services` only lists the subset requested on connect. */
+  partial: boolean
+  /** Tolerated discovery failures; empty when discovery completed. */
+  discoveryErrors: DiscoveryError[]
+}
+
+/**
+ * A service or characteristic that failed to enumerate during discovery.
+ */
+export interface DiscoveryError {
+  /** `null` when the backend does not say which attribute failed. */
+  uuid: string | null
+  message: string
 }
 
 /**
@@ -42,6 +264,8 @@ export interface GattServerInfo {
  */
 export interface BluetoothService {
   uuid: string
+  /** Name registered with `registerUuidAliases`, if any. */
+  alias?: string | null
   isPrimary: boolean
   characteristics: BluetoothCharacteristic[]
 }
@@ -51,10 +275,89 @@ export interface BluetoothService {
  */
 export interface BluetoothCharacteristic {
   uuid: string
+  /** Name registered with `registerUuidAliases`, if any. */
+  alias?: string | null
   properties: CharacteristicProperties
   descriptors: BluetoothDescriptor[]
 }
 
+/**
+ * What a characteristic supports, returned by `getCharacteristicCapabilities`.
+ */
+export interface CharacteristicCapabilities {
+  serviceUuid: string
+  uuid: string
+  properties: CharacteristicProperties
+  /** Whether a Client Characteristic Configuration descriptor is present, so notifications can be toggled. */
+  hasClientConfiguration: boolean
+  hasDescriptors: boolean
+}
+
+/**
+ * A characteristic with its decoded standard descriptors; absent or unreadable ones are omitted.
+ */
+export interface CharacteristicDetails {
+  serviceUuid: string
+  characteristic: BluetoothCharacteristic
+  /** Characteristic User Description (0x2901). */
+  userDescription?: string | null
+  /** Client Characteristic Configuration (0x2902). */
+  clientConfiguration?: ClientConfiguration | null
+  /** Characteristic Presentation Format (0x2904). */
+  presentationFormat?: PresentationFormat | null
+  /** Characteristic Extended Properties (0x2900). */
+  extendedProperties?: ExtendedProperties | null
+}
+
+export interface ClientConfiguration {
+  notifications: boolean
+  indications: boolean
+}
+
+/**
+ * Raw presentation format fields; `format` and `unit` are Bluetooth Assigned Numbers and the value
+ * scales by `10^exponent`.
+ */
+export interface PresentationFormat {
+  format: number
+  exponent: number
+  unit: number
+  namespace: number
+  description: number
+}
+
+export interface ExtendedProperties {
+  reliableWrite: boolean
+  writableAuxiliaries: boolean
+}
+
+/**
+ * Typed value of a standard descriptor.
+ */
+export type StandardDescriptorValue =
+  | { kind: 'extendedProperties'; value: ExtendedProperties }
+  | { kind: 'userDescription'; value: string }
+  | { kind: 'clientConfiguration'; value: ClientConfiguration }
+  | { kind: 'presentationFormat'; value: PresentationFormat }
+
+/**
+ * Outcome of one read in `readDescriptors`; exactly one of `value` and `error` is set.
+ */
+export interface DescriptorReadResult {
+  /** Base64-encoded value. */
+  value?: string | null
+  error?: string | null
+}
+
+/**
+ * Raw descriptor value, plus its decoded form when requested and the descriptor is a standard one.
+ */
+export interface DescriptorValue {
+  /** Base64-encoded value. */
+  value: string
+  interpreted?: StandardDescriptorValue | null
+}
+
 /**
  * Characteristic property flags.
  */
@@ -84,6 +387,213 @@ export interface BluetoothValue {
   value: string
 }
 
+/**
+ * Condition awaited by `awaitValue`. Values are base64 encoded.
+ */
+export type ValueCondition = { kind: 'equals'; value: string } | { kind: 'hasPrefix'; prefix: string }
+
+/**
+ * Identifies a characteristic to read.
+ */
+export interface ReadValueRequest {
+  deviceId: string
+  serviceUuid: string
+  characteristicUuid: string
+}
+
+/**
+ * Probe read round-trip times in milliseconds. An approximation that includes OS stack and
+ * connection-interval delays.
+ */
+export interface PingResult {
+  averageMs: number
+  minMs: number
+  maxMs: number
+  samples: number
+  probeCharacteristicUuid: string
+}
+
+/**
+ * Revision strings from the Device Information Service; missing characteristics are `null`.
+ */
+export interface DeviceRevisions {
+  firmware?: string | null
+  hardware?: string | null
+  software?: string | null
+}
+
+/**
+ * One slice of a streamed read. The last chunk has `done` set.
+ */
+export interface ReadChunk {
+  offset: number
+  /** Base64-encoded slice. */
+  chunkBase64: string
+  done: boolean
+}
+
+export interface ReadLongSummary {
+  totalBytes: number
+}
+
+/**
+ * Outcome of one read in a batch; exactly one of `value` and `error` is set.
+ */
+export interface BatchReadResult {
+  deviceId: string
+  serviceUuid: string
+  characteristicUuid: string
+  /** Base64-encoded value. */
+  value?: string
+  error?: string
+}
+
+/**
+ * GATT write procedure. Signed writes require the `authenticatedSignedWrites` property.
+ */
+export type WriteTypeKind = 'withResponse' | 'withoutResponse' | 'signedWithoutResponse'
+
+/**
+ * One step of a write sequence.
+ */
+export interface SequenceWrite {
+  serviceUuid: string
+  characteristicUuid: string
+  /** Base64-encoded payload. */
+  value: string
+  withResponse?: boolean
+}
+
+/**
+ * Outcome of a write sequence.
+ */
+export interface WriteSequenceResult {
+  completed: number
+  failedIndex?: number
+  error?: string
+  rolledBack: boolean
+}
+
+/**
+ * LE physical layer.
+ */
+export type BluetoothPhy = 'le1m' | 'le2m' | 'leCoded'
+
+/**
+ * PHYs in use for a connection.
+ */
+export interface PhyInfo {
+  txPhy: BluetoothPhy
+  rxPhy: BluetoothPhy
+}
+
+/**
+ * How {@link MonitorInfo} updates are delivered.
+ */
+export type MonitorMode = 'notify' | 'poll'
+
+/**
+ * Result of starting to monitor a characteristic.
+ */
+export interface MonitorInfo {
+  mode: MonitorMode
+  initialValue?: BluetoothValue
+}
+
+export type AggregateFunction = 'min' | 'max' | 'avg'
+
+/**
+ * Downsampling for `pollCharacteristic`. `format` must be numeric; samples that fail to decode are skipped.
+ */
+export interface PollAggregate {
+  function: AggregateFunction
+  /** Successful samples per emitted value. */
+  window: number
+  format: ValueFormat
+}
+
+/**
+ * What happens when notifications arrive faster than they can be emitted.
+ */
+export type NotificationOverflowPolicy = 'dropOldest' | 'dropNewest' | 'block'
+
+/**
+ * What `startNotifications` does when the characteristic is already subscribed: fail with
+ * `NotificationsAlreadyActive`, succeed without changes, or tear down and resubscribe.
+ */
+export type DuplicateNotificationPolicy = 'error' | 'ignore' | 'restart'
+
+/**
+ * Redacted view of the plugin's internal state, for debugging only. Contains no payload values.
+ */
+export interface DebugSnapshot {
+  adapterIndex: number
+  adapterInfo?: string | null
+  cachedDeviceIds: string[]
+  connectedDeviceIds: string[]
+  discoveredServiceDeviceIds: string[]
+  /** Keys (`deviceId:characteristicUuid`) of active subscriptions and polling monitors. */
+  notificationKeys: string[]
+  pausedNotificationKeys: string[]
+  /** Keys of Service Changed and RSSI watchers. */
+  watcherKeys: string[]
+  activeRequestId?: string | null
+  scanning: boolean
+}
+
+/**
+ * Background tasks currently running, grouped by kind. Lists are sorted.
+ */
+export interface ActiveTasks {
+  /** Subscriptions from `startNotifications` or a notifying `monitorCharacteristic`. */
+  notifications: CharacteristicTask[]
+  /** Reads driven by `pollCharacteristic` or a polling `monitorCharacteristic`. */
+  polling: CharacteristicTask[]
+  /** Device ids with an RSSI watch. */
+  rssiWatchers: string[]
+  /** Device ids with a Service Changed watch. */
+  serviceChangedWatchers: string[]
+  /** Device ids with an advertisement watch. */
+  advertisementWatchers: string[]
+  /** Device ids being reconnected after an unexpected disconnect. */
+  reconnects: string[]
+  /** Whether the adapter event listener is running. */
+  eventListener: boolean
+  /** Whether a `startScan` background scan is running. */
+  backgroundScan: boolean
+  activeRequestId?: string | null
+}
+
+/**
+ * A background task bound to one characteristic.
+ */
+export interface CharacteristicTask {
+  deviceId: string
+  characteristicUuid: string
+  /** Only ever `true` for paused notifications. */
+  paused: boolean
+}
+
+/**
+ * Notification delivery counters.
+ */
+export interface NotificationStats {
+  emitted: number
+  dropped: number
+  queueCapacity: number
+  overflowPolicy: NotificationOverflowPolicy
+}
+
+/**
+ * Values delivered by the latest subscription to a characteristic.
+ */
+export interface CharacteristicStats {
+  notificationCount: number
+  /** Unix time in milliseconds of the last value, absent until one arrives. */
+  lastValueMs?: number | null
+  bytesTotal: number
+}
+
 /**
  * Payload emitted when a characteristic value changes.
  */
@@ -92,6 +602,61 @@ export interface NotificationEventPayload {
   serviceUuid: string
   characteristicUuid: string
   value: string
+  /** `true` when the value came from a read (e.g. {@link readAndEmit} or polling) rather than a notification. */
+  fromRead: boolean
+  /** `value` decoded with the format registered via `registerCharacteristicFormat`, if any. */
+  interpreted?: number | string | null
+}
+
+/**
+ * How to decode a characteristic value. Numbers are little endian, as in GATT.
+ */
+export type ValueFormat =
+  | 'uint8'
+  | 'int8'
+  | 'uint16'
+  | 'int16'
+  | 'uint32'
+  | 'int32'
+  | 'uint64'
+  | 'int64'
+  | 'float32'
+  | 'float64'
+  | 'utf8'
+  | 'hex'
+
+/**
+ * Attribute handle range from a Service Changed indication; handles are `null` if malformed.
+ */
+export interface ServiceChangedPayload {
+  deviceId: string
+  startHandle?: number | null
+  endHandle?: number | null
+}
+
+/**
+ * Payload emitted by `watchRssi` when a device's signal strength changes.
+ */
+export interface RssiChangedPayload {
+  deviceId: string
+  /** Signal strength in dBm. */
+  rssi: number
+}
+
+/**
+ * Payload emitted by `watchAdvertisements` with the device's latest advertisement data.
+ */
+export interface AdvertisementReceivedPayload extends AdvertisementSnapshot {
+  deviceId: string
+}
+
+/**
+ * Payload identifying a characteristic, emitted when its notification stream ends.
+ */
+export interface CharacteristicEventPayload {
+  deviceId: string
+  serviceUuid: string
+  characteristicUuid: string
 }
 
 /**
@@ -100,3 +665,75 @@ export interface NotificationEventPayload {
 export interface DeviceEventPayload {
   deviceId: string
 }
+
+/**
+ * Payload emitted when the adapter appears, disappears or is powered on or off.
+ */
+export interface AvailabilityChangedPayload {
+  /** Same as {@link getAvailability} would now return. */
+  value: boolean
+}
+
+/**
+ * Payload emitted when automatic reconnection gives up on a device.
+ */
+export interface ReconnectFailedPayload {
+  deviceId: string
+  /** Connection attempts made before giving up. */
+  attempts: number
+}
+
+/**
+ * Payload of each device event, keyed by its kind.
+ */
+export interface EventPayloads {
+  characteristicValueChanged: NotificationEventPayload
+  notificationsEnded: CharacteristicEventPayload
+  gattServerDisconnected: DeviceEventPayload
+  gattServerConnected: DeviceEventPayload
+  reconnectFailed: ReconnectFailedPayload
+  serviceChanged: ServiceChangedPayload
+  rssiChanged: RssiChangedPayload
+  advertisementReceived: AdvertisementReceivedPayload
+  deviceDiscovered: BluetoothDevice
+  deviceUpdated: BluetoothDevice
+}
+
+export type EventKind = keyof EventPayloads
+
+/**
+ * Envelope emitted on `web-bluetooth://event` when unified events are enabled. Narrow `data` by
+ * switching on `kind`.
+ */
+export type EventEnvelope = {
+  [K in EventKind]: {
+    kind: K
+    deviceId: string
+    /** Milliseconds since the Unix epoch. */
+    timestampMs: number
+    data: EventPayloads[K]
+  }
+}[EventKind]
+
+/**
+ * DOMException name a command rejects with, matching what Web Bluetooth would throw.
+ */
+export type BluetoothErrorName =
+  | 'NotFoundError'
+  | 'SecurityError'
+  | 'NetworkError'
+  | 'NotSupportedError'
+  | 'NotAllowedError'
+  | 'InvalidStateError'
+  | 'TimeoutError'
+  | 'TypeError'
+  | 'DataError'
+  | 'UnknownError'
+
+/**
+ * Value every command rejects with.
+ */
+export interface BluetoothError {
+  name: BluetoothErrorName
+  message: string
+}
@@ -0,0 +1,124 @@
+use crate::models::Appearance;
+
+/// Splits a GAP Appearance value into its category (upper 10 bits) and
+/// sub-category (lower 6 bits) and labels both from the Bluetooth Assigned
+/// Numbers table.
+pub(crate) fn describe_appearance(value: u16) -> Appearance {
+  let category = value >> 6;
+  let subcategory = value & 0x3f;
+  Appearance {
+    value,
+    category: category_name(category).to_string(),
+    subcategory: if subcategory == 0 {
+      None
+    } else {
+      subcategory_name(category, subcategory).map(str::to_string)
+    },
+  }
+}
+
+fn category_name(category: u16) -> &'static str {
+  match category {
+    0x000 => "Unknown",
+    0x001 => "Phone",
+    0x002 => "Computer",
+    0x003 => "Watch",
+    0x004 => "Clock",
+    0x005 => "Display",
+    0x006 => "Remote Control",
+    0x007 => "Eye-glasses",
+    0x008 => "Tag",
+    0x009 => "Keyring",
+    0x00A => "Media Player",
+    0x00B => "Barcode Scanner",
+    0x00C => "Thermometer",
+    0x00D => "Heart Rate Sensor",
+    0x00E => "Blood Pressure",
+    0x00F => "Human Interface Device",
+    0x010 => "Glucose Meter",
+    0x011 => "Running Walking Sensor",
+    0x012 => "Cycling",
+    0x013 => "Control Device",
+    0x014 => "Network Device",
+    0x015 => "Sensor",
+    0x016 => "Light Fixtures",
+    0x017 => "Fan",
+    0x018 => "HVAC",
+    0x019 => "Air Conditioning",
+    0x01A => "Humidifier",
+    0x01B => "Heating",
+    0x01C => "Access Control",
+    0x01D => "Motorized Device",
+    0x01E => "Power Device",
+    0x01F => "Light Source",
+    0x020 => "Window Covering",
+    0x021 => "Audio Sink",
+    0x022 => "Audio Source",
+    0x023 => "Motorized Vehicle",
+    0x024 => "Domestic Appliance",
+    0x025 => "Wearable Audio Device",
+    0x026 => "Aircraft",
+    0x027 => "AV Equipment",
+    0x028 => "Display Equipment",
+    0x029 => "Hearing aid",
+    0x02A => "Gaming",
+    0x02B => "Signage",
+    0x031 => "Pulse Oximeter",
+    0x032 => "Weight Scale",
+    0x033 => "Personal Mobility Device",
+    0x034 => "Continuous Glucose Monitor",
+    0x035 => "Insulin Pump",
+    0x036 => "Medication Delivery",
+    0x037 => "Spirometer",
+    0x051 => "Outdoor Sports Activity",
+    _ => "Reserved",
+  }
+}
+
+fn subcategory_name(category: u16, subcategory: u16) -> Option<&'static str> {
+  let name = match (category, subcategory) {
+    (0x002, 0x01) => "Desktop Workstation",
+    (0x002, 0x02) => "Server-class Computer",
+    (0x002, 0x03) => "Laptop",
+    (0x002, 0x04) => "Handheld PC/PDA",
+    (0x002, 0x05) => "Palm-size PC/PDA",
+    (0x002, 0x06) => "Wearable Computer",
+    (0x002, 0x07) => "Tablet",
+    (0x003, 0x01) => "Sports Watch",
+    (0x003, 0x02) => "Smartwatch",
+    (0x00C, 0x01) => "Ear Thermometer",
+    (0x00D, 0x01) => "Heart Rate Belt",
+    (0x00E, 0x01) => "Arm Blood Pressure",
+    (0x00E, 0x02) => "Wrist Blood Pressure",
+    (0x00F, 0x01) => "Keyboard",
+    (0x00F, 0x02) => "Mouse",
+    (0x00F, 0x03) => "Joystick",
+    (0x00F, 0x04) => "Gamepad",
+    (0x00F, 0x05) => "Digitizer Tablet",
+    (0x00F, 0x06) => "Card Reader",
+    (0x00F, 0x07) => "Digital Pen",
+    (0x00F, 0x08) => "Barcode Scanner",
+    (0x00F, 0x09) => "Touchpad",
+    (0x00F, 0x0A) => "Presentation Remote",
+    (0x011, 0x01) => "In-Shoe Running Walking Sensor",
+    (0x011, 0x02) => "On-Shoe Running Walking Sensor",
+    (0x011, 0x03) => "On-Hip Running Walking Sensor",
+    (0x012, 0x01) => "Cycling Computer",
+    (0x012, 0x02) => "Speed Sensor",
+    (0x012, 0x03) => "Cadence Sensor",
+    (0x012, 0x04) => "Power Sensor",
+    (0x012, 0x05) => "Speed and Cadence Sensor",
+    (0x025, 0x01) => "Earbud",
+    (0x025, 0x02) => "Headset",
+    (0x025, 0x03) => "Headphones",
+    (0x025, 0x04) => "Neck Band",
+    (0x031, 0x01) => "Fingertip Pulse Oximeter",
+    (0x031, 0x02) => "Wrist Worn Pulse Oximeter",
+    (0x051, 0x01) => "Location Display",
+    (0x051, 0x02) => "Location and Navigation Display",
+    (0x051, 0x03) => "Location Pod",
+    (0x051, 0x04) => "Location and Navigation Pod",
+    _ => return None,
+  };
+  Some(name)
+}
@@ -1,4 +1,6 @@
-use tauri::{command, AppHandle, Runtime};
+use std::collections::HashMap;
+
+use tauri::{command, ipc::Channel, AppHandle, Runtime};
 
 use crate::models::*;
 use crate::{Result, WebBluetoothExt};
@@ -22,7 +24,7 @@ pub(crate) async fn request_device<R: Runtime>(
 }
 
 #[command]
-pub(crate) async fn connect_gatt<R: Runtime>(app: AppHandle<R>, request: DeviceRequest) -> Result<GattServerInfo> {
+pub(crate) async fn connect_gatt<R: Runtime>(app: AppHandle<R>, request: ConnectRequest) -> Result<GattServerInfo> {
     app.web_bluetooth().connect_gatt(request).await
 }
 
@@ -84,6 +86,396 @@ pub(crate) async fn stop_notifications<R: Runtime>(
     app.web_bluetooth().stop_notifications(request).await
 }
 
+#[command]
+pub(crate) async fn refresh_gatt<R: Runtime>(app: AppHandle<R>, request: DeviceRequest) -> Result<GattServerInfo> {
+    app.web_bluetooth().refresh_gatt(request).await
+}
+
+#[command]
+pub(crate) async fn monitor_characteristic<R: Runtime>(
+    app: AppHandle<R>,
+    request: MonitorRequest,
+) -> Result<MonitorInfo> {
+    app.web_bluetooth().monitor_characteristic(request).await
+}
+
+#[command]
+pub(crate) async fn stop_monitoring<R: Runtime>(app: AppHandle<R>, request: NotificationRequest) -> Result<()> {
+    app.web_bluetooth().stop_monitoring(request).await
+}
+
+#[command]
+pub(crate) async fn get_adapter_capabilities<R: Runtime>(app: AppHandle<R>) -> Result<AdapterCapabilities> {
+    app.web_bluetooth().get_adapter_capabilities().await
+}
+
+#[command]
+pub(crate) async fn get_bonded_devices<R: Runtime>(app: AppHandle<R>) -> Result<Vec<BluetoothDevice>> {
+    app.web_bluetooth().get_bonded_devices().await
+}
+
+#[command]
+pub(crate) async fn set_preferred_phy<R: Runtime>(app: AppHandle<R>, request: SetPhyRequest) -> Result<()> {
+    app.web_bluetooth().set_preferred_phy(request).await
+}
+
+#[command]
+pub(crate) async fn get_phy<R: Runtime>(app: AppHandle<R>, request: DeviceRequest) -> Result<PhyInfo> {
+    app.web_bluetooth().get_phy(request).await
+}
+
+#[command]
+pub(crate) async fn write_sequence<R: Runtime>(
+    app: AppHandle<R>,
+    request: WriteSequenceRequest,
+) -> Result<WriteSequenceResult> {
+    app.web_bluetooth().write_sequence(request).await
+}
+
+#[command]
+pub(crate) async fn get_last_selection<R: Runtime>(app: AppHandle<R>) -> Result<Option<SelectionInfo>> {
+    app.web_bluetooth().get_last_selection().await
+}
+
+#[command]
+pub(crate) async fn get_notification_stats<R: Runtime>(app: AppHandle<R>) -> Result<NotificationStats> {
+    app.web_bluetooth().get_notification_stats().await
+}
+
+#[command]
+pub(crate) async fn read_appearance<R: Runtime>(app: AppHandle<R>, request: DeviceRequest) -> Result<Appearance> {
+    app.web_bluetooth().read_appearance(request).await
+}
+
+#[command]
+pub(crate) async fn release_device<R: Runtime>(app: AppHandle<R>, request: DeviceRequest) -> Result<()> {
+    app.web_bluetooth().release_device(request).await
+}
+
+#[command]
+pub(crate) async fn read_and_emit<R: Runtime>(app: AppHandle<R>, request: ReadValueRequest) -> Result<BluetoothValue> {
+    app.web_bluetooth().read_and_emit(request).await
+}
+
+#[command]
+pub(crate) async fn connect_matching<R: Runtime>(
+    app: AppHandle<R>,
+    options: RequestDeviceOptions,
+) -> Result<GattServerInfo> {
+    app.web_bluetooth().connect_matching(options).await
+}
+
+#[command]
+pub(crate) async fn get_selection_info<R: Runtime>(app: AppHandle<R>) -> Result<SelectionHandlerInfo> {
+    app.web_bluetooth().get_selection_info().await
+}
+
+#[command]
+pub(crate) async fn read_across_devices<R: Runtime>(
+    app: AppHandle<R>,
+    request: BatchReadRequest,
+) -> Result<Vec<BatchReadResult>> {
+    app.web_bluetooth().read_across_devices(request).await
+}
+
+#[command]
+pub(crate) async fn register_uuid_aliases<R: Runtime>(
+    app: AppHandle<R>,
+    request: RegisterUuidAliasesRequest,
+) -> Result<()> {
+    app.web_bluetooth().register_uuid_aliases(request).await
+}
+
+#[command]
+pub(crate) async fn read_long_streamed<R: Runtime>(
+    app: AppHandle<R>,
+    request: ReadLongStreamedRequest,
+    on_chunk: Channel<ReadChunk>,
+) -> Result<ReadLongSummary> {
+    app.web_bluetooth().read_long_streamed(request, on_chunk).await
+}
+
+#[command]
+pub(crate) async fn await_disconnect<R: Runtime>(app: AppHandle<R>, request: AwaitDisconnectRequest) -> Result<()> {
+    app.web_bluetooth().await_disconnect(request).await
+}
+
+#[command]
+pub(crate) async fn describe_characteristic<R: Runtime>(
+    app: AppHandle<R>,
+    request: ReadValueRequest,
+) -> Result<CharacteristicDetails> {
+    app.web_bluetooth().describe_characteristic(request).await
+}
+
+#[command]
+pub(crate) async fn pause_notifications<R: Runtime>(app: AppHandle<R>, request: NotificationRequest) -> Result<()> {
+    app.web_bluetooth().pause_notifications(request).await
+}
+
+#[command]
+pub(crate) async fn resume_notifications<R: Runtime>(
+    app: AppHandle<R>,
+    request: ResumeNotificationsRequest,
+) -> Result<()> {
+    app.web_bluetooth().resume_notifications(request).await
+}
+
+#[command]
+pub(crate) async fn read_revisions<R: Runtime>(app: AppHandle<R>, request: DeviceRequest) -> Result<DeviceRevisions> {
+    app.web_bluetooth().read_revisions(request).await
+}
+
+#[command]
+pub(crate) async fn get_debug_snapshot<R: Runtime>(app: AppHandle<R>) -> Result<DebugSnapshot> {
+    app.web_bluetooth().get_debug_snapshot().await
+}
+
+#[command]
+pub(crate) async fn watch_service_changed<R: Runtime>(app: AppHandle<R>, request: DeviceRequest) -> Result<()> {
+    app.web_bluetooth().watch_service_changed(request).await
+}
+
+#[command]
+pub(crate) async fn unwatch_service_changed<R: Runtime>(app: AppHandle<R>, request: DeviceRequest) -> Result<()> {
+    app.web_bluetooth().unwatch_service_changed(request).await
+}
+
+#[command]
+pub(crate) async fn get_advertised_services<R: Runtime>(
+    app: AppHandle<R>,
+    request: DeviceRequest,
+) -> Result<Vec<String>> {
+    app.web_bluetooth().get_advertised_services(request).await
+}
+
+#[command]
+pub(crate) async fn start_event_monitor<R: Runtime>(app: AppHandle<R>) -> Result<()> {
+    app.web_bluetooth().start_event_monitor().await
+}
+
+#[command]
+pub(crate) async fn stop_event_monitor<R: Runtime>(app: AppHandle<R>) -> Result<()> {
+    app.web_bluetooth().stop_event_monitor().await
+}
+
+#[command]
+pub(crate) async fn register_characteristic_format<R: Runtime>(
+    app: AppHandle<R>,
+    request: RegisterFormatRequest,
+) -> Result<()> {
+    app.web_bluetooth().register_characteristic_format(request).await
+}
+
+#[command]
+pub(crate) async fn ping_device<R: Runtime>(app: AppHandle<R>, request: PingDeviceRequest) -> Result<PingResult> {
+    app.web_bluetooth().ping_device(request).await
+}
+
+#[command]
+pub(crate) async fn poll_characteristic<R: Runtime>(app: AppHandle<R>, request: PollRequest) -> Result<()> {
+    app.web_bluetooth().poll_characteristic(request).await
+}
+
+#[command]
+pub(crate) async fn stop_polling<R: Runtime>(app: AppHandle<R>, request: NotificationRequest) -> Result<()> {
+    app.web_bluetooth().stop_polling(request).await
+}
+
+#[command]
+pub(crate) async fn request_permission<R: Runtime>(app: AppHandle<R>) -> Result<PermissionStatus> {
+    app.web_bluetooth().request_permission().await
+}
+
+#[command]
+pub(crate) async fn read_descriptor_value<R: Runtime>(
+    app: AppHandle<R>,
+    request: DescriptorRequest,
+) -> Result<DescriptorValue> {
+    app.web_bluetooth().read_descriptor_value(request).await
+}
+
+#[command]
+pub(crate) async fn write_descriptor_value<R: Runtime>(
+    app: AppHandle<R>,
+    request: WriteDescriptorRequest,
+) -> Result<()> {
+    app.web_bluetooth().write_descriptor_value(request).await
+}
+
+#[command]
+pub(crate) async fn request_devices<R: Runtime>(
+    app: AppHandle<R>,
+    options: RequestDeviceOptions,
+) -> Result<Vec<BluetoothDevice>> {
+    app.web_bluetooth().request_devices(options).await
+}
+
+#[command]
+pub(crate) async fn get_descriptors<R: Runtime>(
+    app: AppHandle<R>,
+    request: DescriptorsRequest,
+) -> Result<Vec<BluetoothDescriptor>> {
+    app.web_bluetooth().get_descriptors(request).await
+}
+
+#[command]
+pub(crate) async fn watch_rssi<R: Runtime>(app: AppHandle<R>, request: WatchRssiRequest) -> Result<()> {
+    app.web_bluetooth().watch_rssi(request).await
+}
+
+#[command]
+pub(crate) async fn unwatch_rssi<R: Runtime>(app: AppHandle<R>, request: DeviceRequest) -> Result<()> {
+    app.web_bluetooth().unwatch_rssi(request).await
+}
+
+#[command]
+pub(crate) async fn dismiss_selection<R: Runtime>(app: AppHandle<R>, request: DismissSelectionRequest) -> Result<()> {
+    app.web_bluetooth().dismiss_selection(request).await
+}
+
+#[command]
+pub(crate) async fn get_characteristic_stats<R: Runtime>(
+    app: AppHandle<R>,
+    request: CharacteristicStatsRequest,
+) -> Result<CharacteristicStats> {
+    app.web_bluetooth().get_characteristic_stats(request).await
+}
+
+#[command]
+pub(crate) async fn read_descriptors<R: Runtime>(
+    app: AppHandle<R>,
+    request: ReadDescriptorsRequest,
+) -> Result<HashMap<String, DescriptorReadResult>> {
+    app.web_bluetooth().read_descriptors(request).await
+}
+
+#[command]
+pub(crate) async fn watch_advertisements<R: Runtime>(app: AppHandle<R>, request: DeviceRequest) -> Result<()> {
+    app.web_bluetooth().watch_advertisements(request).await
+}
+
+#[command]
+pub(crate) async fn unwatch_advertisements<R: Runtime>(app: AppHandle<R>, request: DeviceRequest) -> Result<()> {
+    app.web_bluetooth().unwatch_advertisements(request).await
+}
+
+#[cfg(feature = "scan-replay")]
+#[command]
+pub(crate) async fn replay_scan<R: Runtime>(app: AppHandle<R>, request: ReplayScanRequest) -> Result<BluetoothDevice> {
+    app.web_bluetooth().replay_scan(request).await
+}
+
+#[command]
+pub(crate) async fn get_local_adapter_address<R: Runtime>(app: AppHandle<R>) -> Result<LocalAdapterAddress> {
+    app.web_bluetooth().get_local_adapter_address().await
+}
+
+#[command]
+pub(crate) async fn notification_listener_heartbeat<R: Runtime>(
+    app: AppHandle<R>,
+    request: ListenerHeartbeatRequest,
+) -> Result<()> {
+    app.web_bluetooth().notification_listener_heartbeat(request).await
+}
+
+#[command]
+pub(crate) async fn set_default_scan_timeout<R: Runtime>(app: AppHandle<R>, request: ScanTimeoutRequest) -> Result<()> {
+    app.web_bluetooth().set_default_scan_timeout(request).await
+}
+
+#[command]
+pub(crate) async fn get_default_scan_timeout<R: Runtime>(app: AppHandle<R>) -> Result<u64> {
+    app.web_bluetooth().get_default_scan_timeout().await
+}
+
+#[command]
+pub(crate) async fn list_active_tasks<R: Runtime>(app: AppHandle<R>) -> Result<ActiveTasks> {
+    app.web_bluetooth().list_active_tasks().await
+}
+
+#[command]
+pub(crate) async fn get_mtu<R: Runtime>(app: AppHandle<R>, request: DeviceRequest) -> Result<u16> {
+    app.web_bluetooth().get_mtu(request).await
+}
+
+#[command]
+pub(crate) async fn await_value<R: Runtime>(app: AppHandle<R>, request: AwaitValueRequest) -> Result<BluetoothValue> {
+    app.web_bluetooth().await_value(request).await
+}
+
+#[command]
+pub(crate) async fn get_characteristic_capabilities<R: Runtime>(
+    app: AppHandle<R>,
+    request: DeviceRequest,
+) -> Result<Vec<CharacteristicCapabilities>> {
+    app.web_bluetooth().get_characteristic_capabilities(request).await
+}
+
+#[command]
+pub(crate) async fn read_all_battery_levels<R: Runtime>(
+    app: AppHandle<R>,
+    request: DeviceRequest,
+) -> Result<Vec<BatteryLevel>> {
+    app.web_bluetooth().read_all_battery_levels(request).await
+}
+
+#[command]
+pub(crate) async fn get_last_device<R: Runtime>(app: AppHandle<R>) -> Result<Option<LastDevice>> {
+    app.web_bluetooth().get_last_device().await
+}
+
+#[command]
+pub(crate) async fn start_scan<R: Runtime>(app: AppHandle<R>, options: RequestDeviceOptions) -> Result<()> {
+    app.web_bluetooth().start_scan(options).await
+}
+
+#[command]
+pub(crate) async fn stop_scan<R: Runtime>(app: AppHandle<R>) -> Result<()> {
+    app.web_bluetooth().stop_scan().await
+}
+
+#[command]
+pub(crate) async fn get_connected_devices<R: Runtime>(app: AppHandle<R>) -> Result<Vec<BluetoothDevice>> {
+    app.web_bluetooth().get_connected_devices().await
+}
+
+#[command]
+pub(crate) async fn get_connection_state<R: Runtime>(
+    app: AppHandle<R>,
+    request: DeviceRequest,
+) -> Result<ConnectionState> {
+    app.web_bluetooth().get_connection_state(request).await
+}
+
+#[command]
+pub(crate) async fn disconnect_all<R: Runtime>(app: AppHandle<R>) -> Result<()> {
+    app.web_bluetooth().disconnect_all().await
+}
+
+#[command]
+pub(crate) async fn get_adapters<R: Runtime>(app: AppHandle<R>) -> Result<Vec<AdapterInfo>> {
+    app.web_bluetooth().get_adapters().await
+}
+
+#[command]
+pub(crate) async fn set_adapter<R: Runtime>(app: AppHandle<R>, request: SetAdapterRequest) -> Result<AdapterInfo> {
+    app.web_bluetooth().set_adapter(request).await
+}
+
+#[command]
+pub(crate) async fn get_adapter_info<R: Runtime>(app: AppHandle<R>) -> Result<AdapterInfo> {
+    app.web_bluetooth().get_adapter_info().await
+}
+
+#[command]
+pub(crate) async fn get_primary_service<R: Runtime>(
+    app: AppHandle<R>,
+    request: ServiceRequest,
+) -> Result<BluetoothService> {
+    app.web_bluetooth().get_primary_service(request).await
+}
+
 pub(crate) fn handlers<R: Runtime>() -> impl Fn(tauri::ipc::Invoke<R>) -> bool {
     tauri::generate_handler![
         get_availability,
@@ -97,6 +489,72 @@ pub(crate) fn handlers<R: Runtime>() -> impl Fn(tauri::ipc::Invoke<R>) -> bool {
         read_characteristic_value,
         write_characteristic_value,
         start_notifications,
-        stop_notifications
+        stop_notifications,
+        refresh_gatt,
+        monitor_characteristic,
+        stop_monitoring,
+        get_adapter_capabilities,
+        get_bonded_devices,
+        set_preferred_phy,
+        get_phy,
+        write_sequence,
+        get_last_selection,
+        get_notification_stats,
+        read_appearance,
+        release_device,
+        read_and_emit,
+        connect_matching,
+        get_selection_info,
+        read_across_devices,
+        register_uuid_aliases,
+        read_long_streamed,
+        await_disconnect,
+        describe_characteristic,
+        pause_notifications,
+        resume_notifications,
+        read_revisions,
+        get_debug_snapshot,
+        watch_service_changed,
+        unwatch_service_changed,
+        get_advertised_services,
+        start_event_monitor,
+        stop_event_monitor,
+        register_characteristic_format,
+        ping_device,
+        poll_characteristic,
+        stop_polling,
+        request_permission,
+        read_descriptor_value,
+        write_descriptor_value,
+        request_devices,
+        get_descriptors,
+        watch_rssi,
+        unwatch_rssi,
+        dismiss_selection,
+        get_characteristic_stats,
+        read_descriptors,
+        watch_advertisements,
+        unwatch_advertisements,
+        get_local_adapter_address,
+        notification_listener_heartbeat,
+        set_default_scan_timeout,
+        get_default_scan_timeout,
+        list_active_tasks,
+        get_mtu,
+        await_value,
+        get_characteristic_capabilities,
+        read_all_battery_levels,
+        get_last_device,
+        start_scan,
+        stop_scan,
+        get_connected_devices,
+        get_connection_state,
+        disconnect_all,
+        get_adapters,
+        set_adapter,
+        get_adapter_info,
+        get_primary_service,
+        #[cfg(feature = "scan-replay")]
+        replay_scan
     ]
 }
@@ -0,0 +1,73 @@
+/// Resolves a Bluetooth SIG company identifier, as found at the start of
+/// manufacturer specific advertising data. Only widely seen chip vendors and
+/// consumer brands are bundled; other ids resolve to `None`.
+pub(crate) fn company_name(id: u16) -> Option<&'static str> {
+  let name = match id {
+    0x0000 => "Ericsson AB",
+    0x0001 => "Nokia Mobile Phones",
+    0x0002 => "Intel Corp.",
+    0x0003 => "IBM Corp.",
+    0x0004 => "Toshiba Corp.",
+    0x0006 => "Microsoft",
+    0x0008 => "Motorola",
+    0x0009 => "Infineon Technologies AG",
+    0x000A => "Qualcomm Technologies International, Ltd. (QTIL)",
+    0x000D => "Texas Instruments Inc.",
+    0x000F => "Broadcom Corporation",
+    0x0013 => "Atmel Corporation",
+    0x001D => "Qualcomm",
+    0x0025 => "NXP Semiconductors",
+    0x0030 => "ST Microelectronics",
+    0x0036 => "Renesas Electronics Corporation",
+    0x003F => "Bluetooth SIG, Inc",
+    0x0046 => "MediaTek, Inc.",
+    0x0047 => "Bluegiga",
+    0x0048 => "Marvell Technology Group Ltd.",
+    0x004C => "Apple, Inc.",
+    0x0055 => "Plantronics, Inc.",
+    0x0056 => "Sony Ericsson Mobile Communications",
+    0x0057 => "Harman International Industries, Inc.",
+    0x0059 => "Nordic Semiconductor ASA",
+    0x005D => "Realtek Semiconductor Corporation",
+    0x0065 => "HP, Inc.",
+    0x006B => "Polar Electro OY",
+    0x0075 => "Samsung Electronics Co. Ltd.",
+    0x0078 => "Nike, Inc.",
+    0x0087 => "Garmin International, Inc.",
+    0x009E => "Bose Corporation",
+    0x009F => "Suunto Oy",
+    0x00C4 => "LG Electronics",
+    0x00CD => "Microchip Technology Inc.",
+    0x00D2 => "Dialog Semiconductor B.V.",
+    0x00D7 => "Qualcomm Technologies, Inc.",
+    0x00E0 => "Google",
+    0x012D => "Sony Corporation",
+    0x0131 => "Cypress Semiconductor",
+    0x0157 => "Anhui Huami Information Technology Co., Ltd.",
+    0x0171 => "Amazon.com Services, Inc.",
+    0x027D => "HUAWEI Technologies Co., Ltd.",
+    0x02E5 => "Espressif Systems (Shanghai) Co., Ltd.",
+    0x02FF => "Silicon Laboratories",
+    0x038F => "Xiaomi Inc.",
+    0x0499 => "Ruuvi Innovations Ltd.",
+    _ => return None,
+  };
+  Some(name)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn bundled_company_ids_resolve_to_their_names() {
+    assert_eq!(company_name(0x004C), Some("Apple, Inc."));
+    assert_eq!(company_name(0x0006), Some("Microsoft"));
+    assert_eq!(company_name(0x0075), Some("Samsung Electronics Co. Ltd."));
+  }
+
+  #[test]
+  fn unbundled_company_ids_resolve_to_none() {
+    assert_eq!(company_name(0xFFFF), None);
+  }
+}
@@ -0,0 +1,68 @@
+use crate::models::{ClientConfiguration, ExtendedProperties, PresentationFormat, StandardDescriptorValue};
+
+/// Characteristic Extended Properties (0x2900).
+pub(crate) fn decode_extended_properties(bytes: &[u8]) -> Option<ExtendedProperties> {
+  let bits = read_u16(bytes, 0)?;
+  Some(ExtendedProperties {
+    reliable_write: bits & 0x0001 != 0,
+    writable_auxiliaries: bits & 0x0002 != 0,
+  })
+}
+
+/// Characteristic User Description (0x2901). Invalid UTF-8 is replaced
+/// rather than rejected, since devices commonly pad with garbage.
+pub(crate) fn decode_user_description(bytes: &[u8]) -> String {
+  String::from_utf8_lossy(bytes).trim_end_matches('\0').to_string()
+}
+
+/// Client Characteristic Configuration (0x2902).
+pub(crate) fn decode_client_configuration(bytes: &[u8]) -> Option<ClientConfiguration> {
+  let bits = read_u16(bytes, 0)?;
+  Some(ClientConfiguration {
+    notifications: bits & 0x0001 != 0,
+    indications: bits & 0x0002 != 0,
+  })
+}
+
+/// Characteristic Presentation Format (0x2904), a fixed seven byte layout.
+pub(crate) fn decode_presentation_format(bytes: &[u8]) -> Option<PresentationFormat> {
+  if bytes.len() < 7 {
+    return None;
+  }
+  Some(PresentationFormat {
+    format: bytes[0],
+    exponent: bytes[1] as i8,
+    unit: read_u16(bytes, 2)?,
+    namespace: bytes[4],
+    description: read_u16(bytes, 5)?,
+  })
+}
+
+/// Encodes a typed descriptor value in its on-air layout.
+pub(crate) fn encode_standard_descriptor(value: &StandardDescriptorValue) -> Vec<u8> {
+  match value {
+    StandardDescriptorValue::ExtendedProperties(properties) => {
+      let bits = u16::from(properties.reliable_write) | u16::from(properties.writable_auxiliaries) << 1;
+      bits.to_le_bytes().to_vec()
+    }
+    StandardDescriptorValue::UserDescription(text) => text.as_bytes().to_vec(),
+    StandardDescriptorValue::ClientConfiguration(configuration) => {
+      let bits = u16::from(configuration.notifications) | u16::from(configuration.indications) << 1;
+      bits.to_le_bytes().to_vec()
+    }
+    StandardDescriptorValue::PresentationFormat(format) => {
+      let mut bytes = Vec::with_capacity(7);
+      bytes.push(format.format);
+      bytes.push(format.exponent as u8);
+      bytes.extend_from_slice(&format.unit.to_le_bytes());
+      bytes.push(format.namespace);
+      bytes.extend_from_slice(&format.description.to_le_bytes());
+      bytes
+    }
+  }
+}
+
+fn read_u16(bytes: &[u8], offset: usize) -> Option<u16> {
+  let slice = bytes.get(offset..offset + 2)?;
+  Some(u16::from_le_bytes([slice[0], slice[1]]))
+}
@@ -1,40 +1,62 @@
 use std::{
-  collections::{HashMap, HashSet},
+  collections::{HashMap, HashSet, VecDeque},
   future::Future,
   pin::Pin,
-  sync::{Arc, Mutex as StdMutex, OnceLock},
-  time::{Duration, Instant},
+  sync::{
+    atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+    Arc, Mutex as StdMutex, OnceLock, RwLock as StdRwLock, Weak,
+  },
+  time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
 
 use base64::prelude::BASE64_STANDARD;
 use base64::Engine;
 use btleplug::{
   api::{
-    Central, CentralEvent, CharPropFlags, Characteristic, Manager as _, Peripheral as _,
+    bleuuid::uuid_from_u16,
+    Central, CentralEvent, CentralState, CharPropFlags, Characteristic, Descriptor, Manager as _, Peripheral as _,
     PeripheralProperties, ScanFilter, Service, ValueNotification, WriteType,
   },
-  platform::{Adapter, Manager as BtleManager, Peripheral},
+  platform::{Adapter, Manager as BtleManager, Peripheral, PeripheralId},
 };
-use futures::{FutureExt, StreamExt};
+use futures::{FutureExt, Stream, StreamExt};
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use tauri::{
   async_runtime::{self, JoinHandle, Mutex, RwLock},
   http::{header::CONTENT_TYPE, Response, StatusCode},
+  ipc::Channel,
   plugin::{Builder as PluginBuilder, PluginApi},
-  AppHandle, Emitter, Listener, Runtime, Url, WebviewUrl, WebviewWindowBuilder, WindowEvent,
+  AppHandle, Emitter, Listener, Manager, Runtime, Url, WebviewUrl, WebviewWindowBuilder, WindowEvent,
 };
 use tokio::{
-  sync::oneshot,
+  sync::{oneshot, Notify, OwnedMutexGuard},
   time::{sleep, timeout},
 };
 use uuid::Uuid;
 
 use crate::{
+  appearance::describe_appearance,
+  company_ids::company_name,
+  gatt_names::standard_uuid,
+  descriptors::{
+    decode_client_configuration, decode_extended_properties, decode_presentation_format, decode_user_description,
+    encode_standard_descriptor,
+  },
+  value_format::interpret,
   models::*,
-  Error, Result,
+  Error, InitConfig, Result,
 };
+#[cfg(feature = "scan-replay")]
+use crate::scan_replay::{replay_path, RecordedScan, ScanRecorder};
 
 const SCAN_POLL_INTERVAL: Duration = Duration::from_millis(300);
+const MIN_SCAN_TIMEOUT_MS: u64 = 1_000;
+const MIN_MONITOR_POLL_INTERVAL: Duration = Duration::from_millis(50);
+/// Minimum ATT MTU, reported when the platform does not expose the negotiated one.
+const DEFAULT_ATT_MTU: u16 = 23;
+/// Opcode and attribute handle preceding the value in an ATT write.
+const ATT_WRITE_HEADER_LEN: usize = 3;
+const MAX_PING_SAMPLES: u32 = 100;
 const SELECTION_EVENT_PREFIX: &str = "web-bluetooth://select-bluetooth-device/";
 const SELECTION_UPDATE_EVENT_SUFFIX: &str = "devices";
 const SELECTION_WINDOW_PREFIX: &str = "web-bluetooth-selector-";
@@ -42,16 +64,61 @@ const SELECTION_WINDOW_TITLE: &str = "Select Bluetooth Device";
 const SELECTION_WINDOW_SCHEME: &str = "web-bluetooth-selector";
 const SELECTION_WINDOW_HOST: &str = "dialog";
 const SELECTION_RESPONSE_TIMEOUT: Duration = Duration::from_secs(30);
+const GENERIC_ACCESS_SERVICE_UUID: Uuid = uuid_from_u16(0x1800);
+const DEVICE_NAME_CHARACTERISTIC_UUID: Uuid = uuid_from_u16(0x2A00);
+const APPEARANCE_CHARACTERISTIC_UUID: Uuid = uuid_from_u16(0x2A01);
+const GENERIC_ATTRIBUTE_SERVICE_UUID: Uuid = uuid_from_u16(0x1801);
+const SERVICE_CHANGED_CHARACTERISTIC_UUID: Uuid = uuid_from_u16(0x2A05);
+const DEVICE_INFORMATION_SERVICE_UUID: Uuid = uuid_from_u16(0x180A);
+const FIRMWARE_REVISION_CHARACTERISTIC_UUID: Uuid = uuid_from_u16(0x2A26);
+const HARDWARE_REVISION_CHARACTERISTIC_UUID: Uuid = uuid_from_u16(0x2A27);
+const SOFTWARE_REVISION_CHARACTERISTIC_UUID: Uuid = uuid_from_u16(0x2A28);
+const BATTERY_SERVICE_UUID: Uuid = uuid_from_u16(0x180F);
+const BATTERY_LEVEL_CHARACTERISTIC_UUID: Uuid = uuid_from_u16(0x2A19);
+const EXTENDED_PROPERTIES_DESCRIPTOR_UUID: Uuid = uuid_from_u16(0x2900);
+const USER_DESCRIPTION_DESCRIPTOR_UUID: Uuid = uuid_from_u16(0x2901);
+const CLIENT_CONFIGURATION_DESCRIPTOR_UUID: Uuid = uuid_from_u16(0x2902);
+const PRESENTATION_FORMAT_DESCRIPTOR_UUID: Uuid = uuid_from_u16(0x2904);
+const SERVICE_CHANGED_TASK_SUFFIX: &str = "service-changed";
+const RSSI_TASK_SUFFIX: &str = "rssi";
+const GRANTED_DEVICES_FILE: &str = "web-bluetooth-devices.json";
+const DEVICE_ID_SALT_FILE: &str = "web-bluetooth-device-id-salt";
+const AVAILABILITY_POLL_INTERVAL: Duration = Duration::from_secs(2);
+/// A listener that misses heartbeats for this long is considered gone. The
+/// guest bindings send one every 5 seconds.
+const LISTENER_HEARTBEAT_TIMEOUT: Duration = Duration::from_secs(15);
 
 static SELECTION_PAGE_STORE: OnceLock<Arc<StdMutex<HashMap<String, String>>>> = OnceLock::new();
 
 type SelectionFuture = Pin<Box<dyn Future<Output = Result<Option<String>>> + Send>>;
+type MultiSelectionFuture = Pin<Box<dyn Future<Output = Result<Option<Vec<String>>>> + Send>>;
+pub(crate) type RequestIdGenerator = Arc<dyn Fn() -> String + Send + Sync>;
+pub type AdapterSelectorFn = Arc<dyn Fn(&[AdapterInfo]) -> Option<usize> + Send + Sync>;
+
+pub(crate) fn random_request_id_generator() -> RequestIdGenerator {
+  Arc::new(|| Uuid::new_v4().to_string())
+}
 
 pub trait DeviceSelectionHandler<R: Runtime>: Send + Sync + 'static {
   fn select(&self, ctx: DeviceSelectionContext<R>) -> SelectionFuture;
+  /// Picks any number of devices for `request_devices`. `None` or an empty
+  /// list cancels. By default this asks [`Self::select`] for a single device.
+  fn select_many(&self, ctx: DeviceSelectionContext<R>) -> MultiSelectionFuture {
+    single_selection(self.select(ctx))
+  }
   fn wants_full_scan(&self) -> bool {
     false
   }
+  /// Reported to the frontend so it can tell whether the plugin shows a picker.
+  fn mode(&self) -> SelectionMode {
+    SelectionMode::Custom
+  }
+  /// Label of an existing window hosting the picker, for handlers that do not
+  /// open their own. Scan updates on `update_event` are sent there instead of
+  /// to the plugin's picker window.
+  fn picker_window(&self) -> Option<String> {
+    None
+  }
 }
 
 impl<R: Runtime, F, Fut> DeviceSelectionHandler<R> for F
@@ -82,9 +149,21 @@ impl<R: Runtime> SelectionHandler<R> {
     self.inner.select(ctx)
   }
 
+  pub fn select_many(&self, ctx: DeviceSelectionContext<R>) -> MultiSelectionFuture {
+    self.inner.select_many(ctx)
+  }
+
   pub fn wants_full_scan(&self) -> bool {
     self.inner.wants_full_scan()
   }
+
+  pub fn mode(&self) -> SelectionMode {
+    self.inner.mode()
+  }
+
+  pub fn picker_window(&self) -> Option<String> {
+    self.inner.picker_window()
+  }
 }
 
 impl<R: Runtime> Clone for SelectionHandler<R> {
@@ -101,6 +180,39 @@ impl<R: Runtime> Default for SelectionHandler<R> {
   }
 }
 
+fn single_selection(selection: SelectionFuture) -> MultiSelectionFuture {
+  Box::pin(async move { Ok(selection.await?.map(|device_id| vec![device_id])) })
+}
+
+/// Settles a selection whose scan matched nothing. A handler that already
+/// answered is left alone; one still waiting is polled once more, since a
+/// cancel that raced the end of the scan takes precedence, and otherwise
+/// closed through `close` and awaited.
+async fn finish_empty_selection(
+  selection_result: Option<Option<Vec<String>>>,
+  mut selection_future: MultiSelectionFuture,
+  close: impl FnOnce(),
+) -> Error {
+  let answer = match selection_result {
+    Some(answer) => answer,
+    None => match selection_future.as_mut().now_or_never() {
+      Some(Ok(answer)) => answer,
+      Some(Err(err)) => return err,
+      None => {
+        close();
+        if let Err(err) = selection_future.await {
+          return err;
+        }
+        return Error::NoDevicesFound;
+      }
+    },
+  };
+  match answer {
+    Some(_) => Error::NoDevicesFound,
+    None => Error::SelectionCancelled,
+  }
+}
+
 #[derive(Clone)]
 pub struct DeviceSelectionContext<R: Runtime> {
   pub app: AppHandle<R>,
@@ -109,20 +221,234 @@ pub struct DeviceSelectionContext<R: Runtime> {
   pub selection_event: String,
   pub update_event: String,
   pub window_label: String,
+  /// Where the built-in picker page is served from.
+  pub selection_page: SelectionPageConfig,
   pub initial_scanning: bool,
 }
 
+/// Names used by the built-in picker window: the custom URI scheme serving
+/// its page, the URL host and the prefix of each window label. Change them
+/// when the defaults clash with the app's CSP or its own schemes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SelectionPageConfig {
+  scheme: String,
+  host: String,
+  window_label_prefix: String,
+}
+
+impl SelectionPageConfig {
+  pub fn new() -> Self {
+    Self {
+      scheme: SELECTION_WINDOW_SCHEME.to_string(),
+      host: SELECTION_WINDOW_HOST.to_string(),
+      window_label_prefix: SELECTION_WINDOW_PREFIX.to_string(),
+    }
+  }
+
+  /// URI scheme registered for the page. Defaults to `web-bluetooth-selector`.
+  pub fn scheme(mut self, scheme: impl Into<String>) -> Self {
+    self.scheme = scheme.into();
+    self
+  }
+
+  /// Host of the page URL. Defaults to `dialog`.
+  pub fn host(mut self, host: impl Into<String>) -> Self {
+    self.host = host.into();
+    self
+  }
+
+  /// Prefix of the picker window labels, followed by the request id.
+  /// Defaults to `web-bluetooth-selector-`.
+  pub fn window_label_prefix(mut self, prefix: impl Into<String>) -> Self {
+    self.window_label_prefix = prefix.into();
+    self
+  }
+
+  fn window_label(&self, request_id: &str) -> String {
+    format!("{}{request_id}", self.window_label_prefix)
+  }
+
+  fn page_url(&self, request_id: &str) -> Result<Url> {
+    Url::parse(&format!("{}://{}/{request_id}", self.scheme, self.host))
+      .map_err(|err| Error::InvalidRequest(format!("Invalid selection page URL: {err}")))
+  }
+}
+
+impl Default for SelectionPageConfig {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+/// Picks the adapter used by the plugin, see [`crate::PluginConfig::adapter`].
+/// An adapter that cannot be found is an error, except with
+/// [`Self::preferring`], which falls back to the first adapter.
+#[derive(Clone)]
+pub enum AdapterSelector {
+  /// Position in the platform's adapter list, as reported by `get_adapters`.
+  Index(usize),
+  /// Controller address, compared case-insensitively. Only Linux reports
+  /// adapter addresses.
+  Address(String),
+  /// Friendly name of the controller or its platform id, e.g. `hci1`.
+  Name(String),
+  /// Returns the index of the adapter to use, given every adapter.
+  Custom(AdapterSelectorFn),
+}
+
+impl AdapterSelector {
+  /// Wraps a closure over the adapter list in [`Self::Custom`].
+  pub fn custom<F>(select: F) -> Self
+  where
+    F: Fn(&[AdapterInfo]) -> Option<usize> + Send + Sync + 'static,
+  {
+    Self::Custom(Arc::new(select))
+  }
+
+  /// Picks the first adapter whose `adapter_info` string satisfies
+  /// `predicate`, e.g. `|info| info.contains("USB")`, or the first adapter
+  /// when none does. Used by [`crate::PluginConfig::preferred_adapter`].
+  pub fn preferring<F>(predicate: F) -> Self
+  where
+    F: Fn(&str) -> bool + Send + Sync + 'static,
+  {
+    Self::custom(move |adapters| Some(preferred_adapter_index(adapters, &predicate)))
+  }
+
+  fn select(&self, adapters: &[AdapterInfo]) -> Option<usize> {
+    match self {
+      Self::Index(index) => adapters.iter().position(|adapter| adapter.index == *index),
+      Self::Address(address) => adapters.iter().position(|adapter| {
+        adapter
+          .address
+          .as_deref()
+          .is_some_and(|candidate| candidate.eq_ignore_ascii_case(address))
+      }),
+      Self::Name(name) => adapters
+        .iter()
+        .position(|adapter| adapter.name.as_deref() == Some(name.as_str()) || adapter.id == *name),
+      Self::Custom(select) => select(adapters).filter(|index| *index < adapters.len()),
+    }
+  }
+}
+
+/// How [`crate::PluginConfig::auto_reconnect`] retries after a device drops:
+/// up to `max_attempts` connects, waiting between them with a delay that
+/// starts at `initial_delay` and doubles up to `max_delay`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReconnectPolicy {
+  max_attempts: u32,
+  initial_delay: Duration,
+  max_delay: Duration,
+}
+
+impl ReconnectPolicy {
+  /// At least one attempt is always made. Delays default to 500 ms, growing
+  /// to at most 30 s.
+  pub fn new(max_attempts: u32) -> Self {
+    Self {
+      max_attempts: max_attempts.max(1),
+      initial_delay: Duration::from_millis(500),
+      max_delay: Duration::from_secs(30),
+    }
+  }
+
+  /// Wait before the first attempt.
+  pub fn initial_delay(mut self, delay: Duration) -> Self {
+    self.initial_delay = delay;
+    self
+  }
+
+  /// Upper bound for the doubled delay.
+  pub fn max_delay(mut self, delay: Duration) -> Self {
+    self.max_delay = delay;
+    self
+  }
+}
+
 struct FirstMatchSelectionHandler;
 
 impl<R: Runtime> DeviceSelectionHandler<R> for FirstMatchSelectionHandler {
   fn select(&self, ctx: DeviceSelectionContext<R>) -> SelectionFuture {
     Box::pin(async move { Ok(ctx.devices.first().map(|device| device.id.clone())) })
   }
+
+  fn mode(&self) -> SelectionMode {
+    SelectionMode::FirstMatch
+  }
+}
+
+/// Headless handler that picks the device at a fixed position, for test rigs
+/// that need to target a known device deterministically. It waits for the
+/// full scan and indexes into the matches sorted by device id, so the order
+/// follows the configured [`DeviceIdStrategy`]. Fewer matches than `index + 1`
+/// cancels the selection.
+#[derive(Debug, Clone, Copy)]
+pub struct IndexSelectionHandler {
+  index: usize,
+}
+
+impl IndexSelectionHandler {
+  pub fn new(index: usize) -> Self {
+    Self { index }
+  }
+}
+
+impl<R: Runtime> DeviceSelectionHandler<R> for IndexSelectionHandler {
+  fn select(&self, ctx: DeviceSelectionContext<R>) -> SelectionFuture {
+    let index = self.index;
+    Box::pin(async move {
+      let mut ids: Vec<String> = ctx.devices.into_iter().map(|device| device.id).collect();
+      ids.sort();
+      Ok(ids.into_iter().nth(index))
+    })
+  }
+
+  fn wants_full_scan(&self) -> bool {
+    true
+  }
+
+  fn mode(&self) -> SelectionMode {
+    SelectionMode::Index
+  }
+}
+
+/// Scheme used to derive the device identifiers handed to the frontend.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DeviceIdStrategy {
+  /// The Bluetooth address. Human readable, but some platforms (macOS) do not
+  /// report real addresses.
+  Address,
+  /// The platform `PeripheralId` as formatted by btleplug.
+  PeripheralId,
+  /// An opaque hash of the platform `PeripheralId` that stays stable across
+  /// sessions. Salted per install, see `PluginConfig::device_id_salt_store`.
+  #[default]
+  Stable,
+}
+
+/// What `request_device` does when a streaming scan window ends without any
+/// matching device.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EmptyScanBehavior {
+  /// Close the selection handler and fail with [`Error::NoDevicesFound`].
+  #[default]
+  Close,
+  /// Keep scanning with the picker open until a device shows up or the
+  /// handler resolves (e.g. the user cancels or the dialog times out).
+  KeepScanning,
 }
 
 pub struct NativeDialogSelectionHandler {
   response_timeout: Duration,
   full_scan_before_dialog: bool,
+  title: String,
+  window_size: (f64, f64),
+  resizable: bool,
+  strings: SelectionDialogStrings,
+  theme: SelectionDialogTheme,
+  sort_by_signal: bool,
+  search: bool,
 }
 
 impl NativeDialogSelectionHandler {
@@ -130,9 +456,59 @@ impl NativeDialogSelectionHandler {
     Self {
       response_timeout: SELECTION_RESPONSE_TIMEOUT,
       full_scan_before_dialog: false,
+      title: SELECTION_WINDOW_TITLE.to_string(),
+      window_size: (420.0, 520.0),
+      resizable: false,
+      strings: SelectionDialogStrings::default(),
+      theme: SelectionDialogTheme::default(),
+      sort_by_signal: true,
+      search: true,
     }
   }
 
+  /// Shows a text box above the list that narrows the displayed devices by
+  /// name or id. It does not affect the scan. On by default.
+  pub fn with_search(mut self, enabled: bool) -> Self {
+    self.search = enabled;
+    self
+  }
+
+  /// Lists the strongest signal first, keeping that order as devices stream
+  /// in. Turn it off to list devices in discovery order. On by default.
+  pub fn with_signal_sort(mut self, enabled: bool) -> Self {
+    self.sort_by_signal = enabled;
+    self
+  }
+
+  /// Text shown by the picker, for localization. The heading follows
+  /// [`Self::with_title`].
+  pub fn with_strings(mut self, strings: SelectionDialogStrings) -> Self {
+    self.strings = strings;
+    self
+  }
+
+  pub fn with_theme(mut self, theme: SelectionDialogTheme) -> Self {
+    self.theme = theme;
+    self
+  }
+
+  /// Logical size of the picker window. Defaults to 420 × 520.
+  pub fn with_window_size(mut self, width: f64, height: f64) -> Self {
+    self.window_size = (width, height);
+    self
+  }
+
+  /// Whether the user can resize the picker window. Defaults to `false`.
+  pub fn with_resizable(mut self, resizable: bool) -> Self {
+    self.resizable = resizable;
+    self
+  }
+
+  pub fn with_title(mut self, title: impl Into<String>) -> Self {
+    self.title = title.into();
+    self
+  }
+
   pub fn with_response_timeout(mut self, timeout: Duration) -> Self {
     self.response_timeout = timeout;
     self
@@ -144,19 +520,151 @@ impl NativeDialogSelectionHandler {
   }
 }
 
+/// Text of the built-in picker. Defaults to English; `select_count` may
+/// contain `{count}`, replaced with the number of checked devices.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SelectionDialogStrings {
+  /// Value of the page's `lang` attribute.
+  pub lang: String,
+  pub prompt: String,
+  /// Prompt of the multi-select picker used by `request_devices`.
+  pub prompt_multiple: String,
+  pub scanning: String,
+  /// Shown in place of the list while scanning without results.
+  pub looking_for_devices: String,
+  pub no_devices: String,
+  pub search_placeholder: String,
+  /// Shown when devices were found but none matches the search text.
+  pub no_matches: String,
+  pub unnamed_device: String,
+  pub select: String,
+  pub select_count: String,
+  pub cancel: String,
+  pub connecting: String,
+  pub tauri_unavailable: String,
+  pub updates_unavailable: String,
+}
+
+impl Default for SelectionDialogStrings {
+  fn default() -> Self {
+    Self {
+      lang: "en".to_string(),
+      prompt: "Select a nearby Bluetooth device.".to_string(),
+      prompt_multiple: "Select one or more nearby Bluetooth devices.".to_string(),
+      scanning: "Scanning for devices...".to_string(),
+      looking_for_devices: "Looking for devices...".to_string(),
+      no_devices: "No devices were found.".to_string(),
+      search_placeholder: "Search by name or id".to_string(),
+      no_matches: "No devices match your search.".to_string(),
+      unnamed_device: "Unnamed Device".to_string(),
+      select: "Select".to_string(),
+      select_count: "Select {count} devices".to_string(),
+      cancel: "Cancel".to_string(),
+      connecting: "Connecting to Tauri bridge...".to_string(),
+      tauri_unavailable: "Unable to access Tauri APIs. Please enable withGlobalTauri for this window.".to_string(),
+      updates_unavailable: "Unable to subscribe to device updates.".to_string(),
+    }
+  }
+}
+
+/// Colors of the built-in picker. Values are CSS colors. Background and text
+/// default to the light or dark palette picked by [`Self::dark_mode`]; the
+/// remaining shades always follow that palette. Values containing `<`, `;`,
+/// `{` or `}` are ignored in favor of the default.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SelectionDialogTheme {
+  accent: String,
+  background: Option<String>,
+  text: Option<String>,
+  dark: bool,
+}
+
+impl SelectionDialogTheme {
+  pub fn new() -> Self {
+    Self {
+      accent: "#0082f6".to_string(),
+      background: None,
+      text: None,
+      dark: false,
+    }
+  }
+
+  /// Buttons, focus rings and the scan spinner. Defaults to `#0082f6`.
+  pub fn accent(mut self, color: impl Into<String>) -> Self {
+    self.accent = color.into();
+    self
+  }
+
+  pub fn background(mut self, color: impl Into<String>) -> Self {
+    self.background = Some(color.into());
+    self
+  }
+
+  pub fn text(mut self, color: impl Into<String>) -> Self {
+    self.text = Some(color.into());
+    self
+  }
+
+  pub fn dark_mode(mut self, enabled: bool) -> Self {
+    self.dark = enabled;
+    self
+  }
+
+  /// CSS custom properties for the page's `:root`.
+  fn css_variables(&self) -> String {
+    // background, text, muted, subtle, surface, border, disabled, error
+    let palette = if self.dark {
+      ["#1d2026", "#f2f4f7", "#b0b7c3", "#98a2b3", "#272b33", "#3a404a", "#4a515c", "#fda29b"]
+    } else {
+      ["#f4f5f7", "#101828", "#475467", "#667085", "#fff", "#d0d5dd", "#98a2b3", "#b42318"]
+    };
+    format!(
+      "color-scheme: {scheme}; --accent: {accent}; --background: {background}; --text: {text}; --muted: {muted}; \
+       --subtle: {subtle}; --surface: {surface}; --border: {border}; --disabled: {disabled}; --error: {error};",
+      scheme = if self.dark { "dark" } else { "light" },
+      accent = css_color(Some(&self.accent), "#0082f6"),
+      background = css_color(self.background.as_deref(), palette[0]),
+      text = css_color(self.text.as_deref(), palette[1]),
+      muted = palette[2],
+      subtle = palette[3],
+      surface = palette[4],
+      border = palette[5],
+      disabled = palette[6],
+      error = palette[7],
+    )
+  }
+}
+
+impl Default for SelectionDialogTheme {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
 impl Default for NativeDialogSelectionHandler {
   fn default() -> Self {
     Self::new()
   }
 }
 
-impl<R: Runtime> DeviceSelectionHandler<R> for NativeDialogSelectionHandler {
-  fn select(&self, ctx: DeviceSelectionContext<R>) -> SelectionFuture {
+impl NativeDialogSelectionHandler {
+  /// Shows the picker; with `multiple` the devices get checkboxes and a
+  /// confirm button.
+  fn show_dialog<R: Runtime>(&self, ctx: DeviceSelectionContext<R>, multiple: bool) -> MultiSelectionFuture {
     let timeout_duration = self.response_timeout;
+    let title = self.title.clone();
+    let (width, height) = self.window_size;
+    let resizable = self.resizable;
+    let strings = self.strings.clone();
+    let theme = self.theme.clone();
+    let sort_by_signal = self.sort_by_signal;
+    let search = self.search;
     Box::pin(async move {
       let event_name = ctx.selection_event.clone();
       let update_event = ctx.update_event.clone();
       let window_label = ctx.window_label.clone();
+      let selection_page = ctx.selection_page.clone();
       let devices = ctx.devices.clone();
       let initial_scanning = ctx.initial_scanning;
       let app = ctx.app.clone();
@@ -168,7 +676,7 @@ impl<R: Runtime> DeviceSelectionHandler<R> for NativeDialogSelectionHandler {
         if let Ok(message) = serde_json::from_str::<SelectionEventPayload>(event.payload()) {
           if let Ok(mut guard) = sender_handle.lock() {
             if let Some(sender) = guard.take() {
-              let _ = sender.send(message.device_id);
+              let _ = sender.send(message.into_device_ids());
             }
           }
         }
@@ -178,7 +686,19 @@ impl<R: Runtime> DeviceSelectionHandler<R> for NativeDialogSelectionHandler {
         .strip_prefix(SELECTION_EVENT_PREFIX)
         .unwrap_or(&event_name)
         .to_string();
-      let page_url = match build_selection_window_url(&app, &request_id, &devices, &event_name, &update_event, initial_scanning) {
+      let page = SelectionPage {
+        title: &title,
+        strings: &strings,
+        theme: &theme,
+        devices: &devices,
+        selection_event: &event_name,
+        update_event: &update_event,
+        initial_scanning,
+        multiple,
+        sort_by_signal,
+        search,
+      };
+      let page_url = match build_selection_window_url(&selection_page, &request_id, &page) {
         Ok(url) => url,
         Err(err) => {
           app.unlisten(event_id);
@@ -186,11 +706,11 @@ impl<R: Runtime> DeviceSelectionHandler<R> for NativeDialogSelectionHandler {
         }
       };
       let window = match WebviewWindowBuilder::new(&app, window_label.clone(), page_url)
-        .title(SELECTION_WINDOW_TITLE)
-        .inner_size(420.0, 520.0)
+        .title(title)
+        .inner_size(width, height)
         .decorations(false)
         .always_on_top(true)
-        .resizable(false)
+        .resizable(resizable)
         .visible(true)
         .build()
       {
@@ -206,7 +726,7 @@ impl<R: Runtime> DeviceSelectionHandler<R> for NativeDialogSelectionHandler {
       let app_on_close = app.clone();
       window.on_window_event(move |event| {
         if let WindowEvent::Destroyed = event {
-          let _ = app_on_close.emit(&selection_event_on_close, SelectionEventPayload { device_id: None });
+          let _ = app_on_close.emit(&selection_event_on_close, SelectionEventPayload::cancelled());
         }
       });
 
@@ -221,16 +741,149 @@ impl<R: Runtime> DeviceSelectionHandler<R> for NativeDialogSelectionHandler {
       Ok(selection)
     })
   }
+}
+
+impl<R: Runtime> DeviceSelectionHandler<R> for NativeDialogSelectionHandler {
+  fn select(&self, ctx: DeviceSelectionContext<R>) -> SelectionFuture {
+    let selection = self.show_dialog(ctx, false);
+    Box::pin(async move { Ok(selection.await?.and_then(|device_ids| device_ids.into_iter().next())) })
+  }
+
+  fn select_many(&self, ctx: DeviceSelectionContext<R>) -> MultiSelectionFuture {
+    self.show_dialog(ctx, true)
+  }
 
   fn wants_full_scan(&self) -> bool {
     self.full_scan_before_dialog
   }
+
+  fn mode(&self) -> SelectionMode {
+    SelectionMode::NativeDialog
+  }
+}
+
+/// Lets the app render the picker itself. Emits [`EVENT_REQUEST_DEVICE`] to
+/// the app's window, streams scan results to it on the context's
+/// `update_event` and waits for the app to emit its choice on
+/// `selection_event`, the same way the built-in page does.
+pub struct EventSelectionHandler {
+  window_label: String,
+  response_timeout: Duration,
+}
+
+impl EventSelectionHandler {
+  /// Targets the `main` window.
+  pub fn new() -> Self {
+    Self {
+      window_label: "main".to_string(),
+      response_timeout: SELECTION_RESPONSE_TIMEOUT,
+    }
+  }
+
+  /// Window that renders the picker.
+  pub fn with_window(mut self, label: impl Into<String>) -> Self {
+    self.window_label = label.into();
+    self
+  }
+
+  /// How long to wait for the app's answer before cancelling.
+  pub fn with_response_timeout(mut self, timeout: Duration) -> Self {
+    self.response_timeout = timeout;
+    self
+  }
+
+  fn request_selection<R: Runtime>(&self, ctx: DeviceSelectionContext<R>, multiple: bool) -> MultiSelectionFuture {
+    let timeout_duration = self.response_timeout;
+    let target = self.window_label.clone();
+    Box::pin(async move {
+      let app = ctx.app.clone();
+      let (tx, rx) = oneshot::channel();
+      let sender = StdMutex::new(Some(tx));
+      let event_id = app.listen_any(ctx.selection_event.clone(), move |event| {
+        if let Ok(message) = serde_json::from_str::<SelectionEventPayload>(event.payload()) {
+          if let Some(sender) = sender.lock().unwrap_or_else(|err| err.into_inner()).take() {
+            let _ = sender.send(message.into_device_ids());
+          }
+        }
+      });
+
+      let request_id = ctx
+        .selection_event
+        .strip_prefix(SELECTION_EVENT_PREFIX)
+        .unwrap_or(&ctx.selection_event)
+        .to_string();
+      let payload = DeviceRequestPayload {
+        request_id,
+        selection_event: ctx.selection_event,
+        update_event: ctx.update_event,
+        options: ctx.options,
+        devices: ctx.devices,
+        scanning: ctx.initial_scanning,
+        multiple,
+      };
+      if let Err(err) = app.emit_to(target.as_str(), EVENT_REQUEST_DEVICE, payload) {
+        app.unlisten(event_id);
+        return Err(err.into());
+      }
+
+      let selection = match timeout(timeout_duration, rx).await {
+        Ok(Ok(value)) => value,
+        _ => None,
+      };
+      app.unlisten(event_id);
+      Ok(selection)
+    })
+  }
+}
+
+impl Default for EventSelectionHandler {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<R: Runtime> DeviceSelectionHandler<R> for EventSelectionHandler {
+  fn select(&self, ctx: DeviceSelectionContext<R>) -> SelectionFuture {
+    let selection = self.request_selection(ctx, false);
+    Box::pin(async move { Ok(selection.await?.and_then(|device_ids| device_ids.into_iter().next())) })
+  }
+
+  fn select_many(&self, ctx: DeviceSelectionContext<R>) -> MultiSelectionFuture {
+    self.request_selection(ctx, true)
+  }
+
+  fn mode(&self) -> SelectionMode {
+    SelectionMode::Event
+  }
+
+  fn picker_window(&self) -> Option<String> {
+    Some(self.window_label.clone())
+  }
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 struct SelectionEventPayload {
   device_id: Option<String>,
+  /// Set by the picker in multi-select mode.
+  #[serde(default)]
+  device_ids: Option<Vec<String>>,
+}
+
+impl SelectionEventPayload {
+  fn cancelled() -> Self {
+    Self {
+      device_id: None,
+      device_ids: None,
+    }
+  }
+
+  fn into_device_ids(self) -> Option<Vec<String>> {
+    self
+      .device_ids
+      .or_else(|| self.device_id.map(|device_id| vec![device_id]))
+      .filter(|device_ids| !device_ids.is_empty())
+  }
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -244,11 +897,14 @@ fn selection_page_store() -> &'static Arc<StdMutex<HashMap<String, String>>> {
   SELECTION_PAGE_STORE.get_or_init(|| Arc::new(StdMutex::new(HashMap::new())))
 }
 
+/// Serves the built-in picker pages. Called by `init_with_config` while the
+/// plugin is built, since URI schemes cannot be registered after startup.
 pub(crate) fn register_selection_scheme_protocol<R: Runtime, C: DeserializeOwned>(
   builder: PluginBuilder<R, C>,
+  selection_page: &SelectionPageConfig,
 ) -> PluginBuilder<R, C> {
   let store = selection_page_store().clone();
-  builder.register_uri_scheme_protocol(SELECTION_WINDOW_SCHEME, move |_ctx, request| {
+  builder.register_uri_scheme_protocol(selection_page.scheme.clone(), move |_ctx, request| {
     handle_selection_scheme_request(store.clone(), request)
   })
 }
@@ -288,29 +944,93 @@ fn store_selection_page(request_id: &str, html: String) {
   }
 }
 
-fn build_selection_window_url<R: Runtime>(
-  _app: &AppHandle<R>,
-  request_id: &str,
-  devices: &[BluetoothDevice],
-  selection_event: &str,
-  update_event: &str,
+/// Names of the events and window one `request_device` call uses, derived
+/// from a fresh request id.
+struct SelectionNames {
+  request_id: String,
+  selection_event: String,
+  update_event: String,
+  window_label: String,
+}
+
+impl SelectionNames {
+  fn new(
+    generator: &RequestIdGenerator,
+    selection_page: &SelectionPageConfig,
+    picker_window: Option<String>,
+  ) -> Self {
+    let request_id = generator();
+    let selection_event = format!("{SELECTION_EVENT_PREFIX}{request_id}");
+    let update_event = format!("{selection_event}{SELECTION_UPDATE_EVENT_SUFFIX}");
+    let window_label = picker_window.unwrap_or_else(|| selection_page.window_label(&request_id));
+    Self {
+      request_id,
+      selection_event,
+      update_event,
+      window_label,
+    }
+  }
+}
+
+/// Everything the built-in picker page is rendered from.
+struct SelectionPage<'a> {
+  title: &'a str,
+  strings: &'a SelectionDialogStrings,
+  theme: &'a SelectionDialogTheme,
+  devices: &'a [BluetoothDevice],
+  selection_event: &'a str,
+  update_event: &'a str,
   initial_scanning: bool,
-) -> Result<WebviewUrl> {
-  let devices_json = serde_json::to_string(devices)?;
-  let selection_event_json = serde_json::to_string(selection_event)?;
-  let update_event_json = serde_json::to_string(update_event)?;
-  let initial_scanning_flag = if initial_scanning { "true" } else { "false" };
-  let html = format!(
-    r#"<!DOCTYPE html>
-<html lang="en">
-  <head>
-    <meta charset="utf-8" />
+  multiple: bool,
+  sort_by_signal: bool,
+  search: bool,
+}
+
+/// Keeps a themed color from ending its declaration, rule or `<style>` block.
+fn css_color<'a>(value: Option<&'a str>, fallback: &'a str) -> &'a str {
+  match value {
+    Some(value) if value.contains(['<', ';', '{', '}']) => {
+      log::warn!("Ignoring picker theme color {value:?}; it may not contain `<`, `;`, `{{` or `}}`");
+      fallback
+    }
+    Some(value) => value,
+    None => fallback,
+  }
+}
+
+fn build_selection_window_url(
+  selection_page: &SelectionPageConfig,
+  request_id: &str,
+  page: &SelectionPage<'_>,
+) -> Result<WebviewUrl> {
+  // Device names come from advertisements, so they get the same treatment.
+  let devices_json = serde_json::to_string(page.devices)?.replace("</", "<\\/");
+  let selection_event_json = serde_json::to_string(page.selection_event)?;
+  let update_event_json = serde_json::to_string(page.update_event)?;
+  // Keep `</script>` in translated text from closing the script early.
+  let strings_json = serde_json::to_string(page.strings)?.replace("</", "<\\/");
+  let initial_scanning_flag = if page.initial_scanning { "true" } else { "false" };
+  let multiple_flag = if page.multiple { "true" } else { "false" };
+  let sort_flag = if page.sort_by_signal { "true" } else { "false" };
+  let search_flag = if page.search { "true" } else { "false" };
+  let strings = page.strings;
+  let prompt = if page.multiple {
+    &strings.prompt_multiple
+  } else {
+    &strings.prompt
+  };
+  let html = format!(
+    r#"<!DOCTYPE html>
+<html lang="{lang}">
+  <head>
+    <meta charset="utf-8" />
     <title>{title}</title>
     <style>
       :root {{
+        {theme}
         font-family: 'Segoe UI', system-ui, -apple-system, BlinkMacSystemFont, sans-serif;
-        color: #101828;
-        background-color: #f4f5f7;
+        color: var(--text);
+        background-color: var(--background);
       }}
       body {{
         margin: 0;
@@ -330,7 +1050,7 @@ fn build_selection_window_url<R: Runtime>(
       }}
       p {{
         margin: 0;
-        color: #475467;
+        color: var(--muted);
       }}
       .status {{
         display: flex;
@@ -342,7 +1062,7 @@ fn build_selection_window_url<R: Runtime>(
         align-items: center;
         gap: 8px;
         font-size: 13px;
-        color: #475467;
+        color: var(--muted);
       }}
       .scan-status[aria-hidden="true"] {{
         display: none;
@@ -351,8 +1071,8 @@ fn build_selection_window_url<R: Runtime>(
         width: 14px;
         height: 14px;
         border-radius: 50%;
-        border: 2px solid #d0d5dd;
-        border-top-color: #0082f6;
+        border: 2px solid var(--border);
+        border-top-color: var(--accent);
         animation: spin 0.9s linear infinite;
       }}
       @keyframes spin {{
@@ -360,6 +1080,18 @@ fn build_selection_window_url<R: Runtime>(
           transform: rotate(360deg);
         }}
       }}
+      #search {{
+        border: 1px solid var(--border);
+        border-radius: 8px;
+        padding: 8px 10px;
+        background: var(--surface);
+        color: inherit;
+        font: inherit;
+      }}
+      #search:focus {{
+        outline: none;
+        border-color: var(--accent);
+      }}
       .device-list {{
         display: flex;
         flex-direction: column;
@@ -369,39 +1101,96 @@ fn build_selection_window_url<R: Runtime>(
         overflow: auto;
       }}
       .device {{
-        border: 1px solid #d0d5dd;
+        border: 1px solid var(--border);
         border-radius: 8px;
         padding: 12px;
         display: flex;
         flex-direction: column;
         gap: 4px;
-        background-color: #fff;
+        background-color: var(--surface);
+        color: inherit;
         cursor: pointer;
         text-align: left;
       }}
       .device:hover {{
-        border-color: #0082f6;
-        box-shadow: 0 0 0 2px rgba(0,130,246,0.15);
+        border-color: var(--accent);
+        box-shadow: 0 0 0 1px var(--accent);
+      }}
+      .device-option {{
+        flex-direction: row;
+        align-items: center;
+        gap: 10px;
+      }}
+      .device-option input {{
+        margin: 0;
+      }}
+      .device-text {{
+        display: flex;
+        flex-direction: column;
+        gap: 4px;
+        flex: 1;
+      }}
+      .device-header {{
+        display: flex;
+        align-items: center;
+        justify-content: space-between;
+        gap: 8px;
       }}
       .device-name {{
         font-weight: 600;
       }}
+      .signal {{
+        display: inline-flex;
+        align-items: flex-end;
+        gap: 2px;
+        height: 12px;
+        flex-shrink: 0;
+      }}
+      .signal i {{
+        width: 3px;
+        border-radius: 1px;
+        background: var(--border);
+      }}
+      .signal i:nth-child(1) {{ height: 25%; }}
+      .signal i:nth-child(2) {{ height: 50%; }}
+      .signal i:nth-child(3) {{ height: 75%; }}
+      .signal i:nth-child(4) {{ height: 100%; }}
+      .signal i.on {{
+        background: var(--accent);
+      }}
       .device-meta {{
         font-size: 12px;
-        color: #667085;
+        color: var(--subtle);
       }}
       .actions {{
         position: sticky;
         bottom: 0;
-        background: linear-gradient(180deg, rgba(244,245,247,0) 0%, #f4f5f7 30%);
+        background: linear-gradient(180deg, transparent 0%, var(--background) 30%);
         padding-top: 8px;
         padding-bottom: 4px;
+        display: flex;
+        flex-direction: column;
+        gap: 8px;
+      }}
+      #confirm-btn {{
+        border: none;
+        border-radius: 8px;
+        background: var(--accent);
+        color: #fff;
+        font-weight: 600;
+        cursor: pointer;
+        padding: 10px 12px;
+        width: 100%;
+      }}
+      #confirm-btn:disabled {{
+        background: var(--disabled);
+        cursor: default;
       }}
       #cancel-btn {{
-        border: 1px solid #d0d5dd;
+        border: 1px solid var(--border);
         border-radius: 8px;
-        background: #fff;
-        color: #0082f6;
+        background: var(--surface);
+        color: var(--accent);
         font-weight: 600;
         cursor: pointer;
         padding: 10px 12px;
@@ -410,17 +1199,17 @@ fn build_selection_window_url<R: Runtime>(
       }}
       .empty {{
         padding: 16px;
-        border: 1px dashed #d0d5dd;
+        border: 1px dashed var(--border);
         border-radius: 8px;
         text-align: center;
-        color: #667085;
+        color: var(--subtle);
       }}
       .error-banner {{
         padding: 12px 14px;
         border-radius: 8px;
         border: 1px solid rgba(255, 99, 71, 0.4);
         background-color: rgba(255, 99, 71, 0.12);
-        color: #b42318;
+        color: var(--error);
         font-size: 13px;
       }}
     </style>
@@ -429,20 +1218,22 @@ fn build_selection_window_url<R: Runtime>(
     <div class="container">
       <div>
         <h1>{title}</h1>
-        <p>Select a nearby Bluetooth device.</p>
+        <p>{prompt}</p>
       </div>
       <div id="tauri-error" class="error-banner" aria-live="polite" hidden>
-        Connecting to Tauri bridge...
+        {connecting}
       </div>
       <div class="status">
         <div id="scan-status" class="scan-status" aria-hidden="true">
           <span class="spinner-icon" aria-hidden="true"></span>
-          <span>Scanning for devices...</span>
+          <span>{scanning}</span>
         </div>
       </div>
+      <input id="search" type="search" placeholder="{search_placeholder}" autocomplete="off" hidden />
       <div id="device-list" class="device-list"></div>
       <div class="actions">
-        <button id="cancel-btn" type="button">Cancel</button>
+        <button id="confirm-btn" type="button" hidden disabled>{select}</button>
+        <button id="cancel-btn" type="button">{cancel}</button>
       </div>
     </div>
     <script>
@@ -450,13 +1241,21 @@ fn build_selection_window_url<R: Runtime>(
       const EVENT_NAME = {selection_event};
       const UPDATE_EVENT_NAME = {update_event};
       const INITIAL_SCANNING = {initial_scanning};
+      const MULTIPLE = {multiple};
+      const SORT_BY_SIGNAL = {sort_by_signal};
+      const SEARCH = {search};
+      const STRINGS = {strings_json};
       const list = document.getElementById('device-list');
       const scanStatus = document.getElementById('scan-status');
       const tauriError = document.getElementById('tauri-error');
       const cancelBtn = document.getElementById('cancel-btn');
+      const confirmBtn = document.getElementById('confirm-btn');
+      const searchInput = document.getElementById('search');
       const state = {{
         devices: [...DEVICES],
         scanning: INITIAL_SCANNING,
+        selected: new Set(),
+        query: '',
       }};
       let selectHandler = () => {{}};
 
@@ -471,29 +1270,99 @@ fn build_selection_window_url<R: Runtime>(
         scanStatus.setAttribute('aria-hidden', active ? 'false' : 'true');
       }};
 
+      // Devices without an RSSI reading go last; ties keep discovery order.
+      const orderedDevices = () =>
+        SORT_BY_SIGNAL
+          ? [...state.devices].sort((a, b) => (b.rssi ?? -Infinity) - (a.rssi ?? -Infinity))
+          : state.devices;
+
+      const matchesQuery = (device) => {{
+        const query = state.query.trim().toLowerCase();
+        if (!query) return true;
+        return [device.name, device.id].some((text) => text?.toLowerCase().includes(query));
+      }};
+
+      const signalBars = (rssi) => {{
+        if (rssi == null) return '';
+        const level = rssi >= -60 ? 4 : rssi >= -70 ? 3 : rssi >= -80 ? 2 : 1;
+        const bars = [1, 2, 3, 4].map((bar) => `<i class="${{bar <= level ? 'on' : ''}}"></i>`).join('');
+        return `<span class="signal" title="${{rssi}} dBm" aria-hidden="true">${{bars}}</span>`;
+      }};
+
       const renderDevices = () => {{
         list.innerHTML = '';
         if (!state.devices.length) {{
           const empty = document.createElement('div');
           empty.className = 'empty';
-          empty.textContent = state.scanning ? 'Looking for devices...' : 'No devices were found.';
+          empty.textContent = state.scanning ? STRINGS.lookingForDevices : STRINGS.noDevices;
+          list.appendChild(empty);
+          return;
+        }}
+
+        const devices = orderedDevices().filter(matchesQuery);
+        if (!devices.length) {{
+          const empty = document.createElement('div');
+          empty.className = 'empty';
+          empty.textContent = STRINGS.noMatches;
           list.appendChild(empty);
           return;
         }}
+        if (MULTIPLE) {{
+          devices.forEach((device) => {{
+            const option = document.createElement('label');
+            option.className = 'device device-option';
+            option.innerHTML = `
+              <input type="checkbox" ${{state.selected.has(device.id) ? 'checked' : ''}} />
+              <span class="device-text">
+                <span class="device-header">
+                  <span class="device-name">${{escapeHtml(device.name ?? STRINGS.unnamedDevice)}}</span>
+                  ${{signalBars(device.rssi)}}
+                </span>
+                <span class="device-meta">${{deviceMeta(device)}}</span>
+              </span>
+            `;
+            option.querySelector('input').addEventListener('change', (evt) => {{
+              if (evt.target.checked) {{
+                state.selected.add(device.id);
+              }} else {{
+                state.selected.delete(device.id);
+              }}
+              updateConfirm();
+            }});
+            list.appendChild(option);
+          }});
+          return;
+        }}
 
-        state.devices.forEach((device) => {{
+        devices.forEach((device) => {{
           const button = document.createElement('button');
           button.type = 'button';
           button.className = 'device';
           button.innerHTML = `
-            <span class="device-name">${{device.name ?? 'Unnamed Device'}}</span>
-            <span class="device-meta">${{device.id}}</span>
+            <span class="device-header">
+              <span class="device-name">${{escapeHtml(device.name ?? STRINGS.unnamedDevice)}}</span>
+              ${{signalBars(device.rssi)}}
+            </span>
+            <span class="device-meta">${{deviceMeta(device)}}</span>
           `;
           button.addEventListener('click', () => selectHandler(device.id));
           list.appendChild(button);
         }});
       }};
 
+      const escapeHtml = (text) =>
+        String(text).replace(/[&<>"']/g, (ch) => `&#${{ch.charCodeAt(0)}};`);
+
+      const deviceMeta = (device) =>
+        device.rssi == null ? device.id : `${{device.id}} · ${{device.rssi}} dBm`;
+
+      const updateConfirm = () => {{
+        if (!confirmBtn) return;
+        const count = state.selected.size;
+        confirmBtn.disabled = count === 0;
+        confirmBtn.textContent = count > 1 ? STRINGS.selectCount.replace('{{count}}', count) : STRINGS.select;
+      }};
+
       const applyUpdate = (payload) => {{
         if (!payload) return;
         state.devices = Array.isArray(payload.devices) ? payload.devices : [];
@@ -534,13 +1403,21 @@ fn build_selection_window_url<R: Runtime>(
         }});
       }};
 
+      if (SEARCH && searchInput) {{
+        searchInput.hidden = false;
+        searchInput.addEventListener('input', () => {{
+          state.query = searchInput.value;
+          renderDevices();
+        }});
+      }}
+
       setScanning(state.scanning);
       renderDevices();
 
       const bootstrap = async () => {{
         const api = await waitForTauri();
         if (!api?.event) {{
-          showError('Unable to access Tauri APIs. Please enable withGlobalTauri for this window.');
+          showError(STRINGS.tauriUnavailable);
           state.scanning = false;
           setScanning(false);
           return;
@@ -560,20 +1437,26 @@ fn build_selection_window_url<R: Runtime>(
           }}
         }}
 
-        const emitSelection = async (deviceId) => {{
+        const emitSelection = async (selection) => {{
+          const payload = Array.isArray(selection) ? {{ deviceIds: selection }} : {{ deviceId: selection }};
           try {{
-            await event.emit(EVENT_NAME, {{ deviceId }});
+            await event.emit(EVENT_NAME, payload);
           }} catch (err) {{
             console.warn('Failed to emit selection', err);
           }}
         }};
 
-        const handleSelection = async (deviceId) => {{
-          await emitSelection(deviceId);
+        const handleSelection = async (selection) => {{
+          await emitSelection(selection);
           currentWindow?.close?.();
         }};
         selectHandler = handleSelection;
 
+        if (MULTIPLE && confirmBtn) {{
+          confirmBtn.hidden = false;
+          confirmBtn.addEventListener('click', () => handleSelection([...state.selected]));
+        }}
+
         cancelBtn?.addEventListener('click', () => handleSelection(null));
         window.addEventListener('keydown', (evt) => {{
           if (evt.key === 'Escape') {{
@@ -602,7 +1485,7 @@ fn build_selection_window_url<R: Runtime>(
               console.warn('Failed to subscribe via global listener', err);
             }}
           }}
-          showError('Unable to subscribe to device updates.');
+          showError(STRINGS.updatesUnavailable);
           return null;
         }};
 
@@ -621,19 +1504,96 @@ fn build_selection_window_url<R: Runtime>(
   </body>
 </html>
 "#,
-    title = SELECTION_WINDOW_TITLE,
+    lang = escape_html(&strings.lang),
+    theme = page.theme.css_variables(),
+    title = escape_html(page.title),
+    connecting = escape_html(&strings.connecting),
+    scanning = escape_html(&strings.scanning),
+    select = escape_html(&strings.select),
+    cancel = escape_html(&strings.cancel),
+    strings_json = strings_json,
     devices = devices_json,
     selection_event = selection_event_json,
     update_event = update_event_json,
     initial_scanning = initial_scanning_flag,
+    multiple = multiple_flag,
+    sort_by_signal = sort_flag,
+    search = search_flag,
+    search_placeholder = escape_html(&strings.search_placeholder),
+    prompt = escape_html(prompt),
   );
 
+  let url = selection_page.page_url(request_id)?;
   store_selection_page(request_id, html);
-  let raw_url = format!("{SELECTION_WINDOW_SCHEME}://{SELECTION_WINDOW_HOST}/{request_id}");
-  let url = Url::parse(&raw_url).map_err(|err| Error::InvalidRequest(err.to_string()))?;
   Ok(WebviewUrl::External(url))
 }
 
+fn escape_html(text: &str) -> String {
+  let mut escaped = String::with_capacity(text.len());
+  for ch in text.chars() {
+    match ch {
+      '&' => escaped.push_str("&amp;"),
+      '<' => escaped.push_str("&lt;"),
+      '>' => escaped.push_str("&gt;"),
+      '"' => escaped.push_str("&quot;"),
+      '\'' => escaped.push_str("&#39;"),
+      _ => escaped.push(ch),
+    }
+  }
+  escaped
+}
+
+/// Index of the first adapter whose info satisfies the preference, falling
+/// back to the first adapter when none does.
+fn preferred_adapter_index(adapters: &[AdapterInfo], preference: impl Fn(&str) -> bool) -> usize {
+  adapters
+    .iter()
+    .position(|adapter| preference(&adapter.info))
+    .unwrap_or_else(|| {
+      log::info!("No adapter matched the configured preference, using the first one");
+      0
+    })
+}
+
+/// Lists the adapters with what each platform reports about them. Adapters
+/// whose details cannot be read are kept with empty fields, so indexes stay
+/// aligned with the platform's list.
+async fn describe_adapters(adapters: &[Adapter]) -> Vec<AdapterInfo> {
+  let mut infos = Vec::with_capacity(adapters.len());
+  for (index, adapter) in adapters.iter().enumerate() {
+    let info = adapter.adapter_info().await.unwrap_or_else(|err| {
+      log::warn!("Failed to read adapter info | index={} | err={:?}", index, err);
+      String::new()
+    });
+    // BlueZ reports "<id> (<modalias>)"; other backends only a name.
+    let id = info.split(' ').next().unwrap_or_default().to_string();
+    #[cfg(target_os = "linux")]
+    let local = bluez_adapter_address(&id).await.unwrap_or_else(|err| {
+      log::warn!("Failed to query BlueZ adapter | adapter={} | err={:?}", id, err);
+      LocalAdapterAddress::default()
+    });
+    #[cfg(not(target_os = "linux"))]
+    let local = LocalAdapterAddress::default();
+    let state = adapter.adapter_state().await;
+    let powered = match state {
+      Ok(CentralState::PoweredOn) => Some(true),
+      Ok(CentralState::PoweredOff) => Some(false),
+      _ => None,
+    };
+    infos.push(AdapterInfo {
+      index,
+      id,
+      info,
+      address: local.address,
+      name: local.name,
+      powered,
+      available: ensure_powered(state).is_ok(),
+      selected: false,
+    });
+  }
+  infos
+}
+
 fn emit_selection_update<R: Runtime>(
   app: &AppHandle<R>,
   window_label: &str,
@@ -661,17 +1621,26 @@ fn emit_selection_update<R: Runtime>(
 pub fn init<R: Runtime, C: DeserializeOwned>(
   app: &AppHandle<R>,
   _api: PluginApi<R, C>,
-  selection_handler: SelectionHandler<R>,
+  config: InitConfig<R>,
 ) -> Result<WebBluetooth<R>> {
+  register_uuid_aliases(config.uuid_aliases.clone())?;
   let app_handle = app.clone();
+  let selector = config.adapter_selector.clone();
   let (manager, adapter, adapter_index) = async_runtime::block_on(async move {
     let manager = BtleManager::new().await?;
     let mut adapters = manager.adapters().await?;
     if adapters.is_empty() {
       return Err(Error::NoAdapter);
     }
-    let adapter = adapters.remove(0);
-    Ok::<_, Error>((manager, adapter, 0usize))
+    let adapter_index = match selector {
+      Some(selector) => {
+        let infos = describe_adapters(&adapters).await;
+        selector.select(&infos).ok_or(Error::NoAdapter)?
+      }
+      None => 0,
+    };
+    let adapter = adapters.remove(adapter_index);
+    Ok::<_, Error>((manager, adapter, adapter_index))
   })?;
 
   Ok(WebBluetooth::new(
@@ -679,7 +1648,7 @@ pub fn init<R: Runtime, C: DeserializeOwned>(
     manager,
     adapter,
     adapter_index,
-    selection_handler,
+    config,
   ))
 }
 
@@ -691,11 +1660,106 @@ pub struct WebBluetooth<R: Runtime> {
 struct WebBluetoothState<R: Runtime> {
   app: AppHandle<R>,
   manager: BtleManager,
-  adapter: Adapter,
-  adapter_index: usize,
+  /// Replaced by `set_adapter`; clone it out with [`Self::adapter`] rather
+  /// than holding the lock.
+  adapter: StdRwLock<Adapter>,
+  adapter_index: AtomicUsize,
   peripherals: RwLock<HashMap<String, Peripheral>>,
   notification_tasks: Arc<Mutex<HashMap<String, JoinHandle<()>>>>,
+  /// Pause switches for subscription tasks, keyed like `notification_tasks`.
+  notification_gates: Arc<Mutex<HashMap<String, Arc<NotificationGate>>>>,
+  /// Per-subscription counters, keyed like `notification_tasks`. Kept after
+  /// the subscription ends and replaced when it is started again.
+  characteristic_stats: StdMutex<HashMap<String, Arc<CharacteristicCounters>>>,
+  device_ids: Arc<DeviceIds>,
+  last_selection: Mutex<Option<SelectionInfo>>,
+  /// Most recent pick, mirrored to `last_device_store` when set.
+  last_device: StdMutex<Option<LastDevice>>,
+  last_device_store: Option<std::path::PathBuf>,
+  /// Devices granted through `request_device`, keyed by device id.
+  granted_devices: StdMutex<HashMap<String, GrantedDevice>>,
+  /// Where `granted_devices` is persisted, when enabled with
+  /// `PluginConfig::persist_granted_devices`.
+  granted_devices_store: Option<std::path::PathBuf>,
+  /// Set by `PluginConfig::unified_events`.
+  unified_events: bool,
+  /// GAP Appearance values read per device, used to enrich `BluetoothDevice`.
+  appearances: Mutex<HashMap<String, u16>>,
+  /// MTUs reported by the platform per device, until it disconnects.
+  mtus: Arc<Mutex<HashMap<String, u16>>>,
+  /// Services granted per device by `request_device`: the filters' services
+  /// plus `optionalServices`, accumulated across requests. Devices without an
+  /// entry were never granted through a request and are not restricted.
+  allowed_services: Mutex<HashMap<String, HashSet<Uuid>>>,
+  device_query_concurrency: usize,
+  prewarm_connection: bool,
+  connect_on_subscribe: bool,
+  notification_queue_capacity: usize,
+  notification_overflow_policy: NotificationOverflowPolicy,
+  duplicate_notification_policy: DuplicateNotificationPolicy,
+  notification_counters: Arc<NotificationCounters>,
+  request_id_generator: RequestIdGenerator,
+  empty_scan_behavior: EmptyScanBehavior,
+  /// Used by requests that omit `scan_timeout_ms`.
+  default_scan_timeout_ms: AtomicU64,
+  /// Devices whose services have been discovered and are still considered current.
+  discovered_services: Arc<Mutex<HashSet<String>>>,
+  /// Upper bound on cached peripherals; `None` keeps every device.
+  max_cached_devices: Option<usize>,
+  /// Used by writes that omit `chunk_size`; `None` writes values whole.
+  write_chunk_size: Option<usize>,
+  /// Byte cap per advertisement data blob in payloads; `None` keeps them whole.
+  max_advertisement_data_len: Option<usize>,
+  /// Cached device ids ordered from least to most recently used. Only
+  /// maintained when `max_cached_devices` is set.
+  peripheral_recency: Mutex<VecDeque<String>>,
+  debug_snapshot: bool,
+  manufacturer_names: bool,
+  /// Request id of the `request_device` call in flight, if any.
+  active_request: Arc<StdMutex<Option<String>>>,
+  /// Tasks started by `watch_advertisements`, keyed by device id. Kept apart
+  /// from `notification_tasks` because they outlive disconnects.
+  advertisement_watchers: Mutex<HashMap<String, JoinHandle<()>>>,
+  /// Task started by `start_scan`, reporting matches until `stop_scan`.
+  /// Always locked before `advertisement_watchers` when both are needed.
+  background_scan: Mutex<Option<JoinHandle<()>>>,
+  /// Grace period for `PluginConfig::auto_unsubscribe`; `None` when disabled.
+  auto_unsubscribe_grace: Option<Duration>,
+  listener_leases: StdMutex<ListenerLeases>,
+  /// File each `request_device` scan is recorded to.
+  #[cfg(feature = "scan-replay")]
+  scan_recording: Option<std::path::PathBuf>,
+  /// Directory `replay_scan` may also load recordings from.
+  #[cfg(feature = "scan-replay")]
+  scan_replay_dir: Option<std::path::PathBuf>,
+  /// Set by `dismiss_selection` for the active request.
+  selection_dismissed: AtomicBool,
+  /// Decode formats keyed by device (`None` for every device) and characteristic.
+  value_formats: StdMutex<HashMap<(Option<String>, Uuid), ValueFormat>>,
+  /// Background task watching adapter events, while enabled.
+  event_listener: StdMutex<Option<JoinHandle<()>>>,
+  /// Callers of `await_disconnect`, woken by the adapter event listener.
+  disconnect_waiters: Arc<Mutex<HashMap<String, Vec<oneshot::Sender<()>>>>>,
+  /// One lock per device so GATT operations on it run one at a time, as
+  /// in Web Bluetooth; concurrent btleplug calls can interleave badly.
+  gatt_queues: KeyedLocks,
+  /// One lock per notification key, held by `await_value` for the life of
+  /// its temporary subscription.
+  value_waits: KeyedLocks,
+  /// Set by `PluginConfig::auto_reconnect`; `None` leaves dropped devices alone.
+  reconnect_policy: Option<ReconnectPolicy>,
+  /// Devices connected through `connect_gatt` and not disconnected on
+  /// purpose since, i.e. the ones worth reconnecting.
+  reconnect_devices: StdMutex<HashSet<String>>,
+  /// Requests of the active subscriptions, keyed like `notification_tasks`,
+  /// so they can be restored after a reconnect.
+  subscriptions: StdMutex<HashMap<String, NotificationRequest>>,
+  /// Reconnect loops in progress, keyed by device id.
+  reconnect_tasks: Mutex<HashMap<String, JoinHandle<()>>>,
+  /// Devices with a `connect` call in flight, for `get_connection_state`.
+  connecting: StdMutex<HashSet<String>>,
   selection_handler: SelectionHandler<R>,
+  selection_page: SelectionPageConfig,
 }
 
 impl<R: Runtime> WebBluetooth<R> {
@@ -704,70 +1768,523 @@ impl<R: Runtime> WebBluetooth<R> {
     manager: BtleManager,
     adapter: Adapter,
     adapter_index: usize,
-    selection_handler: SelectionHandler<R>,
+    config: InitConfig<R>,
   ) -> Self {
+    let granted_devices_store = if config.persist_granted_devices {
+      match app.path().app_data_dir() {
+        Ok(dir) => Some(dir.join(GRANTED_DEVICES_FILE)),
+        Err(err) => {
+          log::warn!("Granted devices not persisted, no app data dir | err={:?}", err);
+          None
+        }
+      }
+    } else {
+      None
+    };
+    let granted_devices: HashMap<String, GrantedDevice> = granted_devices_store
+      .as_deref()
+      .map(load_granted_devices)
+      .unwrap_or_default()
+      .into_iter()
+      .map(|device| (device.device_id.clone(), device))
+      .collect();
+    let salt_store = config.device_id_salt_store.or_else(|| match app.path().app_data_dir() {
+      Ok(dir) => Some(dir.join(DEVICE_ID_SALT_FILE)),
+      Err(err) => {
+        log::warn!("Device ids left unsalted, no app data dir | err={:?}", err);
+        None
+      }
+    });
+    let device_ids = DeviceIds {
+      strategy: config.device_id_strategy,
+      salt: salt_store.as_deref().map(load_or_create_salt).unwrap_or_default(),
+      peripherals: StdMutex::new(
+        granted_devices
+          .values()
+          .map(|device| (device.device_id.clone(), device.peripheral_id.clone()))
+          .collect(),
+      ),
+    };
+    let allowed_services = granted_devices
+      .values()
+      .map(|device| (device.device_id.clone(), device.allowed_services.clone()))
+      .collect();
     let state = Arc::new(WebBluetoothState {
       app,
       manager,
-      adapter,
-      adapter_index,
+      adapter: StdRwLock::new(adapter),
+      adapter_index: AtomicUsize::new(adapter_index),
       peripherals: RwLock::new(HashMap::new()),
       notification_tasks: Arc::new(Mutex::new(HashMap::new())),
-      selection_handler,
+      notification_gates: Arc::new(Mutex::new(HashMap::new())),
+      characteristic_stats: StdMutex::new(HashMap::new()),
+      discovered_services: Arc::new(Mutex::new(HashSet::new())),
+      max_cached_devices: config.max_cached_devices,
+      write_chunk_size: config.write_chunk_size,
+      max_advertisement_data_len: config.max_advertisement_data_len,
+      peripheral_recency: Mutex::new(VecDeque::new()),
+      debug_snapshot: config.debug_snapshot,
+      manufacturer_names: config.manufacturer_names,
+      active_request: Arc::new(StdMutex::new(None)),
+      advertisement_watchers: Mutex::new(HashMap::new()),
+      background_scan: Mutex::new(None),
+      auto_unsubscribe_grace: config.auto_unsubscribe_grace,
+      listener_leases: StdMutex::new(ListenerLeases::new()),
+      #[cfg(feature = "scan-replay")]
+      scan_recording: config.scan_recording,
+      #[cfg(feature = "scan-replay")]
+      scan_replay_dir: config.scan_replay_dir,
+      selection_dismissed: AtomicBool::new(false),
+      value_formats: StdMutex::new(HashMap::new()),
+      event_listener: StdMutex::new(None),
+      disconnect_waiters: Arc::new(Mutex::new(HashMap::new())),
+      device_ids: Arc::new(device_ids),
+      last_selection: Mutex::new(None),
+      last_device: StdMutex::new(config.last_device_store.as_deref().and_then(load_last_device)),
+      last_device_store: config.last_device_store,
+      granted_devices: StdMutex::new(granted_devices),
+      granted_devices_store,
+      unified_events: config.unified_events,
+      appearances: Mutex::new(HashMap::new()),
+      mtus: Arc::new(Mutex::new(HashMap::new())),
+      allowed_services: Mutex::new(allowed_services),
+      device_query_concurrency: config.device_query_concurrency,
+      prewarm_connection: config.prewarm_connection,
+      connect_on_subscribe: config.connect_on_subscribe,
+      notification_queue_capacity: config.notification_queue_capacity,
+      notification_overflow_policy: config.notification_overflow_policy,
+      duplicate_notification_policy: config.duplicate_notification_policy,
+      notification_counters: Arc::new(NotificationCounters::default()),
+      request_id_generator: config.request_id_generator,
+      empty_scan_behavior: config.empty_scan_behavior,
+      default_scan_timeout_ms: AtomicU64::new(DEFAULT_SCAN_TIMEOUT_MS),
+      gatt_queues: KeyedLocks::default(),
+      value_waits: KeyedLocks::default(),
+      reconnect_policy: config.reconnect_policy,
+      reconnect_devices: StdMutex::new(HashSet::new()),
+      subscriptions: StdMutex::new(HashMap::new()),
+      reconnect_tasks: Mutex::new(HashMap::new()),
+      connecting: StdMutex::new(HashSet::new()),
+      selection_handler: config.selection_handler,
+      selection_page: config.selection_page,
     });
-    state.spawn_event_listener();
+    if config.event_monitor {
+      WebBluetoothState::ensure_event_listener(&state);
+    }
+    if let Some(grace) = config.auto_unsubscribe_grace {
+      WebBluetoothState::spawn_listener_reaper(&state, grace);
+    }
+    WebBluetoothState::spawn_availability_watcher(&state);
     Self { inner: state }
   }
 
+  /// Adds application-defined UUID names. They are accepted anywhere a UUID
+  /// is, and label matching services and characteristics in results. Standard
+  /// GATT names such as `heart_rate` cannot be reused.
+  pub async fn register_uuid_aliases(&self, request: RegisterUuidAliasesRequest) -> Result<()> {
+    register_uuid_aliases(request.aliases)
+  }
+
+  /// Whether the adapter is present and not powered off. Changes are
+  /// emitted as [`EVENT_AVAILABILITY_CHANGED`].
   pub async fn get_availability(&self) -> Result<bool> {
-    Ok(!self
+    self.inner.availability().await
+  }
+
+  /// Lists the host's adapters so the UI can offer a choice for
+  /// `PluginConfig::adapter`. The one in use is marked `selected`.
+  pub async fn get_adapters(&self) -> Result<Vec<AdapterInfo>> {
+    let adapters = self.inner.manager.adapters().await?;
+    let mut infos = describe_adapters(&adapters).await;
+    for info in &mut infos {
+      info.selected = info.index == self.inner.adapter_index.load(Ordering::SeqCst);
+    }
+    Ok(infos)
+  }
+
+  /// Describes the adapter in use, for settings and troubleshooting screens.
+  pub async fn get_adapter_info(&self) -> Result<AdapterInfo> {
+    let index = self.inner.adapter_index.load(Ordering::SeqCst);
+    let mut info = describe_adapters(std::slice::from_ref(&self.inner.adapter()))
+      .await
+      .pop()
+      .unwrap_or_default();
+    info.index = index;
+    // The handle outlives an unplugged adapter, so check it is still listed.
+    info.available = self.inner.availability().await?;
+    info.selected = true;
+    Ok(info)
+  }
+
+  /// Switches to another adapter without restarting, e.g. after a dongle was
+  /// plugged in. Waits for queued GATT operations in flight, such as connects,
+  /// subscriptions, reads and writes, then stops every scan, watcher and
+  /// subscription, disconnects all devices and empties the device cache,
+  /// since peripheral handles belong to the old adapter. `await_value` only
+  /// queues its setup and reads; a wait in progress is not waited for and
+  /// ends with `NotConnected` when its device is disconnected. Devices are
+  /// reloaded from the new adapter on next use; ids derived from platform
+  /// ids may differ on it. Fails with `AdapterBusy` while a `request_device`
+  /// call is running.
+  pub async fn set_adapter(&self, request: SetAdapterRequest) -> Result<AdapterInfo> {
+    if self.active_request_id().is_some() {
+      return Err(Error::AdapterBusy);
+    }
+    let mut adapters = self.inner.manager.adapters().await?;
+    let mut infos = describe_adapters(&adapters).await;
+    let index = match request.adapter {
+      AdapterRef::Index(index) => AdapterSelector::Index(index).select(&infos),
+      AdapterRef::Id(id) => AdapterSelector::Name(id.clone())
+        .select(&infos)
+        .or_else(|| AdapterSelector::Address(id).select(&infos)),
+    }
+    .ok_or(Error::NoAdapter)?;
+    let mut info = infos.swap_remove(index);
+    info.selected = true;
+    if index == self.inner.adapter_index.load(Ordering::SeqCst) {
+      return Ok(info);
+    }
+
+    let queues = self.inner.gatt_queues.all();
+    // Held until the switch is done, so queued operations run on the new adapter.
+    let mut gatt_guards = Vec::with_capacity(queues.len());
+    for queue in queues {
+      gatt_guards.push(queue.lock_owned().await);
+    }
+    self.disconnect_all().await?;
+    let old_adapter = self.inner.adapter();
+    old_adapter.stop_scan().await.ok();
+    let had_listener = self
       .inner
-      .manager
-      .adapters()
-      .await?
-      .into_iter()
-      .nth(self.inner.adapter_index)
-      .is_none())
+      .event_listener
+      .lock()
+      .unwrap_or_else(|err| err.into_inner())
+      .is_some();
+    self.inner.stop_event_listener();
+
+    *self.inner.adapter.write().unwrap_or_else(|err| err.into_inner()) = adapters.swap_remove(index);
+    self.inner.adapter_index.store(index, Ordering::SeqCst);
+    self.inner.peripherals.write().await.clear();
+    self.inner.peripheral_recency.lock().await.clear();
+    self.inner.discovered_services.lock().await.clear();
+    self.inner.mtus.lock().await.clear();
+    self
+      .inner
+      .device_ids
+      .peripherals
+      .lock()
+      .unwrap_or_else(|err| err.into_inner())
+      .clear();
+    if had_listener {
+      WebBluetoothState::ensure_event_listener(&self.inner);
+    }
+    drop(gatt_guards);
+    log::info!("Switched adapter | index={} | info={}", index, info.info);
+    Ok(info)
+  }
+
+  /// Queries the adapter so the OS shows its Bluetooth permission prompt if it
+  /// has not yet, and reports the outcome. On macOS the prompt appears on first
+  /// CoreBluetooth use; elsewhere there is no prompt and access is reported as
+  /// granted. Never starts a scan.
+  pub async fn request_permission(&self) -> Result<PermissionStatus> {
+    match self.inner.adapter().adapter_state().await {
+      Ok(_) => Ok(PermissionStatus::Granted),
+      Err(btleplug::Error::PermissionDenied) => Ok(PermissionStatus::Denied),
+      Err(err) => Err(err.into()),
+    }
+  }
+
+  /// Best-effort view of the controller's feature set. Only BlueZ reports
+  /// anything: the secondary advertising channels tell whether extended
+  /// advertising, LE 2M and LE Coded are supported. Unknown values, and every
+  /// value on other platforms, stay `None`.
+  pub async fn get_adapter_capabilities(&self) -> Result<AdapterCapabilities> {
+    #[cfg(target_os = "linux")]
+    {
+      let adapter_info = self.inner.adapter().adapter_info().await?;
+      let adapter_id = adapter_info.split(' ').next().unwrap_or_default();
+      match bluez_secondary_channels(adapter_id).await {
+        Ok(channels) => return Ok(capabilities_from_secondary_channels(&channels)),
+        Err(err) => log::debug!("BlueZ advertising channels unknown | adapter={} | err={:?}", adapter_id, err),
+      }
+    }
+    Ok(AdapterCapabilities::default())
+  }
+
+  /// Address and friendly name of the active adapter. Only BlueZ exposes
+  /// them; other backends return empty fields.
+  pub async fn get_local_adapter_address(&self) -> Result<LocalAdapterAddress> {
+    #[cfg(target_os = "linux")]
+    {
+      let adapter_info = self.inner.adapter().adapter_info().await?;
+      // BlueZ reports "<id> (<modalias>)", e.g. "hci0 (usb:v1D6Bp0246d0540)".
+      let adapter_id = adapter_info.split(' ').next().unwrap_or_default();
+      match bluez_adapter_address(adapter_id).await {
+        Ok(address) => return Ok(address),
+        Err(err) => log::warn!("Failed to query BlueZ adapter | adapter={} | err={:?}", adapter_id, err),
+      }
+    }
+    Ok(LocalAdapterAddress::default())
+  }
+
+  /// The ATT MTU negotiated with a connected device. Only BlueZ reports it;
+  /// elsewhere, or before BlueZ has exchanged it, the minimum of 23 is
+  /// returned. A reported MTU also bounds later writes without response;
+  /// `connect_gatt` queries it too.
+  pub async fn get_mtu(&self, request: DeviceRequest) -> Result<u16> {
+    let peripheral = self.get_or_try_load_peripheral(&request.device_id).await?;
+    if !peripheral.is_connected().await.unwrap_or(false) {
+      return Err(Error::NotConnected(request.device_id));
+    }
+    Ok(self.refresh_mtu(&request.device_id, &peripheral).await?.unwrap_or(DEFAULT_ATT_MTU))
+  }
+
+  /// Stores the ATT MTU BlueZ reports for a connected device, so it bounds
+  /// later writes without response. `None` when it is not known.
+  #[cfg(target_os = "linux")]
+  async fn refresh_mtu(&self, device_id: &str, peripheral: &Peripheral) -> Result<Option<u16>> {
+    let adapter_info = self.inner.adapter().adapter_info().await?;
+    let adapter_id = adapter_info.split(' ').next().unwrap_or_default();
+    match bluez_mtu(adapter_id, &peripheral.address().to_string()).await {
+      Ok(Some(mtu)) => {
+        self.inner.mtus.lock().await.insert(device_id.to_string(), mtu);
+        Ok(Some(mtu))
+      }
+      Ok(None) => Ok(None),
+      Err(err) => {
+        log::warn!("Failed to query BlueZ MTU | device_id={} | err={:?}", device_id, err);
+        Ok(None)
+      }
+    }
+  }
+
+  #[cfg(not(target_os = "linux"))]
+  async fn refresh_mtu(&self, _device_id: &str, _peripheral: &Peripheral) -> Result<Option<u16>> {
+    Ok(None)
+  }
+
+  /// Changes the scan timeout of requests that do not set `scan_timeout_ms`,
+  /// for the rest of the session.
+  pub async fn set_default_scan_timeout(&self, request: ScanTimeoutRequest) -> Result<()> {
+    if request.timeout_ms < MIN_SCAN_TIMEOUT_MS {
+      return Err(Error::InvalidRequest(format!(
+        "Scan timeout must be at least {MIN_SCAN_TIMEOUT_MS} ms"
+      )));
+    }
+    self
+      .inner
+      .default_scan_timeout_ms
+      .store(request.timeout_ms, Ordering::Relaxed);
+    Ok(())
   }
 
+  pub async fn get_default_scan_timeout(&self) -> Result<u64> {
+    Ok(self.inner.default_scan_timeout_ms.load(Ordering::Relaxed))
+  }
+
+  /// Describes cached devices concurrently, at most `device_query_concurrency`
+  /// at a time. The order of the result is unspecified. Granted devices the
+  /// adapter does not know yet, e.g. after a restart, are listed as
+  /// disconnected with their last known name.
   pub async fn get_devices(&self) -> Result<Vec<BluetoothDevice>> {
-    let peripherals = self.inner.peripherals.read().await;
-    let mut devices = Vec::with_capacity(peripherals.len());
-    for peripheral in peripherals.values() {
-      devices.push(self.describe_device(peripheral).await?);
+    let missing = self.restore_granted_devices().await;
+    let peripherals: Vec<(String, Peripheral)> = self
+      .inner
+      .peripherals
+      .read()
+      .await
+      .iter()
+      .map(|(device_id, peripheral)| (device_id.clone(), peripheral.clone()))
+      .collect();
+    let devices = describe_skipping_failures(peripherals, self.inner.device_query_concurrency, |peripheral| async move {
+      self.describe_device(&peripheral).await
+    })
+    .await;
+    Ok(devices.into_iter().chain(missing.iter().map(GrantedDevice::describe)).collect())
+  }
+
+  /// Caches the granted devices the adapter can resolve and returns the
+  /// ones it cannot.
+  async fn restore_granted_devices(&self) -> Vec<GrantedDevice> {
+    let granted: Vec<GrantedDevice> = self
+      .inner
+      .granted_devices
+      .lock()
+      .unwrap_or_else(|err| err.into_inner())
+      .values()
+      .cloned()
+      .collect();
+    let mut missing = Vec::new();
+    for device in granted {
+      if self.inner.peripherals.read().await.contains_key(&device.device_id) {
+        continue;
+      }
+      match self.inner.adapter().peripheral(&device.peripheral_id).await {
+        Ok(peripheral) => self.cache_peripheral(&device.device_id, peripheral).await,
+        Err(_) => missing.push(device),
+      }
     }
+    missing
+  }
+
+  fn save_granted_devices(&self) {
+    let Some(path) = &self.inner.granted_devices_store else {
+      return;
+    };
+    let mut devices: Vec<GrantedDevice> = self
+      .inner
+      .granted_devices
+      .lock()
+      .unwrap_or_else(|err| err.into_inner())
+      .values()
+      .cloned()
+      .collect();
+    devices.sort_by(|a, b| a.device_id.cmp(&b.device_id));
+    save_granted_devices(path, &devices);
+  }
+
+  /// Describes the cached devices that are currently connected, so the
+  /// frontend can rebuild its state after a reload or reconnects. The order
+  /// of the result is unspecified.
+  pub async fn get_connected_devices(&self) -> Result<Vec<BluetoothDevice>> {
+    let peripherals: Vec<Peripheral> = self.inner.peripherals.read().await.values().cloned().collect();
+    let devices = futures::stream::iter(peripherals)
+      .map(|peripheral| async move {
+        if !peripheral.is_connected().await.unwrap_or(false) {
+          return None;
+        }
+        match self.describe_device(&peripheral).await {
+          Ok(device) => Some(device),
+          Err(err) => {
+            log::warn!(
+              "Skipping device in get_connected_devices | device_id={} | err={:?}",
+              self.device_key(&peripheral),
+              err
+            );
+            None
+          }
+        }
+      })
+      .buffer_unordered(self.inner.device_query_concurrency)
+      .filter_map(|device| async move { device })
+      .collect()
+      .await;
     Ok(devices)
   }
 
+  /// Lists devices bonded with the host at the OS level, marked `paired`,
+  /// including ones out of range. Only BlueZ exposes the bond database, so
+  /// other backends report `UnsupportedPlatform`.
+  pub async fn get_bonded_devices(&self) -> Result<Vec<BluetoothDevice>> {
+    #[cfg(target_os = "linux")]
+    {
+      let adapter = self.inner.adapter();
+      let adapter_info = adapter.adapter_info().await?;
+      let adapter_id = adapter_info.split(' ').next().unwrap_or_default();
+      let bonded = bluez_paired_addresses(adapter_id)
+        .await
+        .map_err(|err| Error::Btleplug(btleplug::Error::Other(Box::new(err))))?;
+      // BlueZ keeps bonded devices on its object tree, so btleplug lists them
+      // even when they are not advertising.
+      let mut devices = Vec::with_capacity(bonded.len());
+      for peripheral in adapter.peripherals().await? {
+        if !bonded.contains(&peripheral.address().to_string().to_ascii_lowercase()) {
+          continue;
+        }
+        match self.describe_device(&peripheral).await {
+          Ok(device) => devices.push(BluetoothDevice { paired: true, ..device }),
+          Err(err) => log::warn!("Failed to describe bonded device | address={} | err={:?}", peripheral.address(), err),
+        }
+      }
+      Ok(devices)
+    }
+    #[cfg(not(target_os = "linux"))]
+    Err(Error::UnsupportedPlatform)
+  }
+
   pub async fn request_device(&self, options: RequestDeviceOptions) -> Result<BluetoothDevice> {
+    self
+      .select_devices(options, false)
+      .await?
+      .into_iter()
+      .next()
+      .ok_or(Error::SelectionCancelled)
+  }
+
+  /// Multi-select variant of [`Self::request_device`]: the selection handler's
+  /// `select_many` picks the devices and every one of them is cached.
+  pub async fn request_devices(&self, options: RequestDeviceOptions) -> Result<Vec<BluetoothDevice>> {
+    self.select_devices(options, true).await
+  }
+
+  /// Window receiving scan updates: the handler's own, or the plugin's picker.
+  fn selection_names(&self) -> SelectionNames {
+    SelectionNames::new(
+      &self.inner.request_id_generator,
+      &self.inner.selection_page,
+      self.inner.selection_handler.picker_window(),
+    )
+  }
+
+  fn start_selection(&self, ctx: DeviceSelectionContext<R>, multiple: bool) -> MultiSelectionFuture {
+    let handler = &self.inner.selection_handler;
+    let selection = if multiple {
+      handler.select_many(ctx)
+    } else {
+      single_selection(handler.select(ctx))
+    };
+    Box::pin(async move { Ok(selection.await?.filter(|device_ids| !device_ids.is_empty())) })
+  }
+
+  async fn select_devices(&self, options: RequestDeviceOptions, multiple: bool) -> Result<Vec<BluetoothDevice>> {
+    let options = with_default_scan_timeout(options, self.inner.default_scan_timeout_ms.load(Ordering::Relaxed));
     let request_options = options.clone();
     let normalized = NormalizedRequestDeviceOptions::try_from(options)?;
-    let adapter = self.inner.adapter.clone();
-    adapter.start_scan(ScanFilter::default()).await?;
+    let adapter = self.inner.adapter();
+    // Bail out before any selection window or listener exists. Backends that
+    // cannot report power state fall through and let `start_scan` decide.
+    ensure_powered(adapter.adapter_state().await)?;
+    let excluded = self.known_device_ids(normalized.exclude_known_devices).await?;
+    // A filtered scan would starve running advertisement watchers and
+    // background scans.
+    let scan_filter = if !self.scan_shared().await {
+      normalized.scan_filter()
+    } else {
+      ScanFilter::default()
+    };
+    adapter.start_scan(scan_filter).await?;
     let deadline = Instant::now() + normalized.scan_timeout;
     let require_full_scan = self.inner.selection_handler.wants_full_scan();
-    let request_id = Uuid::new_v4().to_string();
-    let selection_event = format!("{SELECTION_EVENT_PREFIX}{request_id}");
-    let update_event = format!("{selection_event}{SELECTION_UPDATE_EVENT_SUFFIX}");
-    let window_label = format!("{SELECTION_WINDOW_PREFIX}{request_id}");
+    let SelectionNames {
+      request_id,
+      selection_event,
+      update_event,
+      window_label,
+    } = self.selection_names();
+    let _active_request = ActiveRequestGuard::new(self.inner.active_request.clone(), &request_id);
+    self.inner.selection_dismissed.store(false, Ordering::SeqCst);
+    #[cfg(feature = "scan-replay")]
+    let mut recorder = self.inner.scan_recording.clone().map(ScanRecorder::new);
 
     log::info!(
-      "request_device invoked | accept_all_devices={} | filter_count={} | timeout_ms={} | full_scan={}",
+      "request_device invoked | accept_all_devices={} | filter_count={} | timeout_ms={} | full_scan={} | multiple={}",
       request_options.accept_all_devices,
       request_options.filters.len(),
-      request_options.scan_timeout_ms,
-      require_full_scan
+      request_options.scan_timeout_ms.unwrap_or_default(),
+      require_full_scan,
+      multiple
     );
 
     if require_full_scan {
       let mut matched: HashMap<String, Peripheral> = HashMap::new();
-      while Instant::now() < deadline {
+      while Instant::now() < deadline && !self.selection_dismissed() {
         let peripherals = adapter.peripherals().await?;
         for peripheral in peripherals {
           if let Some(properties) = peripheral.properties().await? {
             if normalized.matches(&properties) {
-              let device_id = peripheral_key(&peripheral);
-              if matched.contains_key(&device_id) {
+              let device_id = self.device_key(&peripheral);
+              if matched.contains_key(&device_id) || excluded.contains(&device_id) {
                 continue;
               }
               log::info!(
@@ -781,11 +2298,14 @@ impl<R: Runtime> WebBluetooth<R> {
         }
         sleep(SCAN_POLL_INTERVAL).await;
       }
-      adapter.stop_scan().await.ok();
+      self.finish_scan().await;
+      if self.selection_dismissed() {
+        return Err(Error::SelectionCancelled);
+      }
 
       if matched.is_empty() {
         log::warn!("Full scan completed with 0 matching devices");
-        return Err(Error::DeviceNotFound("No devices matched the provided filters".into()));
+        return Err(Error::NoDevicesFound);
       }
 
       let matched_peripherals: Vec<Peripheral> = matched.values().cloned().collect();
@@ -793,6 +2313,11 @@ impl<R: Runtime> WebBluetooth<R> {
       for peripheral in &matched_peripherals {
         devices.push(self.describe_device(peripheral).await?);
       }
+      #[cfg(feature = "scan-replay")]
+      if let Some(mut recorder) = recorder.take() {
+        recorder.record(&devices);
+        recorder.save();
+      }
 
       let context = DeviceSelectionContext {
         app: self.inner.app.clone(),
@@ -801,63 +2326,85 @@ impl<R: Runtime> WebBluetooth<R> {
         selection_event,
         update_event,
         window_label,
+        selection_page: self.inner.selection_page.clone(),
         initial_scanning: false,
       };
       log::info!("Presenting {} devices to selection handler (full-scan mode)", devices.len());
-      let selected_id = self
-        .inner
-        .selection_handler
-        .select(context)
+      let selected_ids = self
+        .start_selection(context, multiple)
         .await?
         .ok_or(Error::SelectionCancelled)?;
 
-      let selected_device = devices
-        .into_iter()
-        .find(|device| device.id == selected_id)
-        .ok_or_else(|| Error::DeviceNotFound(selected_id.clone()))?;
-
-      if let Some(selected_peripheral) = matched.remove(&selected_id) {
-        let mut cache = self.inner.peripherals.write().await;
-        cache.insert(selected_id.clone(), selected_peripheral);
-      }
-
-      return Ok(selected_device);
+      return self
+        .finish_selection(&normalized, devices, &mut matched, &selected_ids)
+        .await;
     }
 
     let app = self.inner.app.clone();
+    // Known devices may not re-advertise during the scan window, so start from
+    // the cached peripherals that already match.
+    let mut matched = self.cached_matches(&normalized, &excluded).await;
+    let mut devices: Vec<BluetoothDevice> = Vec::with_capacity(matched.len());
+    for peripheral in matched.values() {
+      match self.describe_device(peripheral).await {
+        Ok(device) => devices.push(device),
+        Err(err) => log::warn!("Skipping cached match | err={:?}", err),
+      }
+    }
+    matched.retain(|device_id, _| devices.iter().any(|device| &device.id == device_id));
+    log::info!("Streaming scan seeded from cache | devices={}", devices.len());
+    #[cfg(feature = "scan-replay")]
+    if let Some(recorder) = recorder.as_mut() {
+      recorder.record(&devices);
+    }
     let context = DeviceSelectionContext {
       app: self.inner.app.clone(),
       options: request_options,
-      devices: Vec::new(),
+      devices: devices.clone(),
       selection_event: selection_event.clone(),
       update_event: update_event.clone(),
       window_label: window_label.clone(),
+      selection_page: self.inner.selection_page.clone(),
       initial_scanning: true,
     };
-    let mut selection_future = Box::pin(self.inner.selection_handler.select(context));
-    let mut selection_result: Option<Option<String>> = None;
-    let mut matched: HashMap<String, Peripheral> = HashMap::new();
-    let mut devices: Vec<BluetoothDevice> = Vec::new();
+    let mut selection_future = self.start_selection(context, multiple);
+    let mut selection_result: Option<Option<Vec<String>>> = None;
     let mut last_emit = Instant::now();
 
+    let keep_scanning = self.inner.empty_scan_behavior == EmptyScanBehavior::KeepScanning;
+    let mut signal_strengths: HashMap<String, i16> = HashMap::new();
+    let mut prewarmed: Option<PrewarmedConnection> = None;
+    // Subscribe before the first sweep so nothing discovered in between is
+    // missed. After the sweep only peripherals named by events are examined.
+    let mut events = adapter.events().await?;
+    let mut pending = adapter.peripherals().await?;
     log::info!("Streaming scan started | request_id={request_id}");
-    while Instant::now() < deadline {
+    // With `KeepScanning`, an empty scan keeps going past the deadline until
+    // something matches or the selection handler gives up.
+    while Instant::now() < deadline || (keep_scanning && devices.is_empty()) {
       if let Some(value) = selection_future.as_mut().now_or_never() {
         selection_result = Some(value?);
         break;
       }
+      if self.selection_dismissed() {
+        selection_result = Some(None);
+        break;
+      }
 
-      sleep(SCAN_POLL_INTERVAL).await;
-      let peripherals = adapter.peripherals().await?;
       let mut updated = false;
-      for peripheral in peripherals {
+      for peripheral in pending.drain(..) {
         if let Some(properties) = peripheral.properties().await? {
           if normalized.matches(&properties) {
-            let device_id = peripheral_key(&peripheral);
-            if matched.contains_key(&device_id) {
+            let device_id = self.device_key(&peripheral);
+            if excluded.contains(&device_id) {
+              continue;
+            }
+            if let Some(rssi) = properties.rssi {
+              signal_strengths.insert(device_id.clone(), rssi);
+            }
+            if !insert_new_match(&mut matched, &device_id, &peripheral) {
               continue;
             }
-            matched.insert(device_id.clone(), peripheral.clone());
             devices.push(self.describe_device(&peripheral).await?);
             log::info!(
               "Streaming scan match | device_id={} | name={:?}",
@@ -868,17 +2415,39 @@ impl<R: Runtime> WebBluetooth<R> {
           }
         }
       }
-      if updated {
-        emit_selection_update(&app, &window_label, &update_event, &devices, false);
-        last_emit = Instant::now();
-      } else if !devices.is_empty() && last_emit.elapsed() >= Duration::from_millis(800) {
+      if updated || (!devices.is_empty() && last_emit.elapsed() >= Duration::from_millis(800)) {
         emit_selection_update(&app, &window_label, &update_event, &devices, false);
         last_emit = Instant::now();
+        #[cfg(feature = "scan-replay")]
+        if let (true, Some(recorder)) = (updated, recorder.as_mut()) {
+          recorder.record(&devices);
+        }
+      }
+      if self.inner.prewarm_connection && prewarmed.is_none() {
+        let strongest = signal_strengths
+          .iter()
+          .max_by_key(|(_, rssi)| **rssi)
+          .and_then(|(device_id, _)| matched.get(device_id).map(|peripheral| (device_id.clone(), peripheral.clone())));
+        if let Some((device_id, peripheral)) = strongest {
+          if !peripheral.is_connected().await.unwrap_or(false) {
+            prewarmed = Some(PrewarmedConnection::start(device_id, peripheral));
+          }
+        }
+      }
+
+      for id in collect_changed_peripherals(&mut events, SCAN_POLL_INTERVAL).await {
+        if let Ok(peripheral) = adapter.peripheral(&id).await {
+          pending.push(peripheral);
+        }
       }
     }
 
-    adapter.stop_scan().await.ok();
+    self.finish_scan().await;
     emit_selection_update(&app, &window_label, &update_event, &devices, true);
+    #[cfg(feature = "scan-replay")]
+    if let Some(recorder) = recorder {
+      recorder.save();
+    }
     log::info!(
       "Streaming scan completed | request_id={request_id} | devices_found={} | selection_resolved={}",
       devices.len(),
@@ -891,59 +2460,596 @@ impl<R: Runtime> WebBluetooth<R> {
 
     if devices.is_empty() {
       log::warn!("Streaming scan produced no matching devices");
-      if selection_result.is_none() {
-        let _ = app.emit(&selection_event, SelectionEventPayload { device_id: None });
-        let _ = selection_future.await?;
-      }
-      return Err(Error::DeviceNotFound("No devices matched the provided filters".into()));
+      return Err(
+        finish_empty_selection(selection_result, selection_future, || {
+          let _ = app.emit(&selection_event, SelectionEventPayload::cancelled());
+        })
+        .await,
+      );
     }
 
-    let selected_id = match selection_result {
+    let selected_ids = match selection_result {
       Some(result) => result,
       None => selection_future.await?,
     }
     .ok_or(Error::SelectionCancelled)?;
 
-    let selected_device = devices
-      .into_iter()
-      .find(|device| device.id == selected_id)
-      .ok_or_else(|| Error::DeviceNotFound(selected_id.clone()))?;
-
-    if let Some(selected_peripheral) = matched.remove(&selected_id) {
-      let mut cache = self.inner.peripherals.write().await;
-      cache.insert(selected_id.clone(), selected_peripheral);
+    let selected = self
+      .finish_selection(&normalized, devices, &mut matched, &selected_ids)
+      .await?;
+    if let Some(prewarmed) = prewarmed.as_mut() {
+      prewarmed.promote_if(&selected_ids);
     }
-
-    log::info!("Device selected | device_id={} | name={:?}", selected_device.id, selected_device.name);
-    Ok(selected_device)
+    Ok(selected)
   }
 
-  pub async fn connect_gatt(&self, request: DeviceRequest) -> Result<GattServerInfo> {
-    let peripheral = self.get_or_try_load_peripheral(&request.device_id).await?;
-    if !peripheral.is_connected().await.unwrap_or(false) {
-      peripheral.connect().await?;
-    }
-    peripheral.discover_services().await?;
-    Ok(self.describe_gatt_server(&request.device_id, &peripheral).await?)
-  }
+  /// Feeds a scan recorded with `PluginConfig::record_scan` through the
+  /// selection handler with its original timings, so the picker can be
+  /// worked on without hardware. Only the `record_scan` file and files in
+  /// `PluginConfig::replay_scan_dir` are accepted. The returned device has no
+  /// peripheral behind it and is not cached or granted any services.
+  #[cfg(feature = "scan-replay")]
+  pub async fn replay_scan(&self, request: ReplayScanRequest) -> Result<BluetoothDevice> {
+    let path = replay_path(
+      std::path::Path::new(&request.path),
+      self.inner.scan_recording.as_deref(),
+      self.inner.scan_replay_dir.as_deref(),
+    )?;
+    let recording = RecordedScan::load(&path)?;
+    let SelectionNames {
+      request_id,
+      selection_event,
+      update_event,
+      window_label,
+    } = self.selection_names();
+    let _active_request = ActiveRequestGuard::new(self.inner.active_request.clone(), &request_id);
+    self.inner.selection_dismissed.store(false, Ordering::SeqCst);
+    let full_scan = self.inner.selection_handler.wants_full_scan();
+    let options = RequestDeviceOptions {
+      accept_all_devices: true,
+      filters: Vec::new(),
+      optional_services: Vec::new(),
+      scan_timeout_ms: recording.updates.last().map(|update| update.elapsed_ms),
+      exclude_known_devices: KnownDeviceExclusion::None,
+    };
+    log::info!(
+      "Replaying scan | request_id={request_id} | path={} | updates={}",
+      request.path,
+      recording.updates.len()
+    );
 
-  pub async fn disconnect_gatt(&self, request: DeviceRequest) -> Result<()> {
-    let peripheral = self.get_or_try_load_peripheral(&request.device_id).await?;
-    if peripheral.is_connected().await.unwrap_or(false) {
-      peripheral.disconnect().await?;
+    // Full-scan handlers only ever see the final list.
+    let mut devices = if full_scan {
+      recording.updates.last().map(|update| update.devices.clone()).unwrap_or_default()
+    } else {
+      Vec::new()
+    };
+    if full_scan && devices.is_empty() {
+      return Err(Error::NoDevicesFound);
+    }
+    let context = DeviceSelectionContext {
+      app: self.inner.app.clone(),
+      options,
+      devices: devices.clone(),
+      selection_event: selection_event.clone(),
+      update_event: update_event.clone(),
+      window_label: window_label.clone(),
+      selection_page: self.inner.selection_page.clone(),
+      initial_scanning: !full_scan,
+    };
+    let mut selection_future = self.start_selection(context, false);
+    let mut selection_result: Option<Option<Vec<String>>> = None;
+
+    if !full_scan {
+      let started = Instant::now();
+      'replay: for update in recording.updates {
+        let due = Duration::from_millis(update.elapsed_ms);
+        while started.elapsed() < due {
+          if let Some(value) = selection_future.as_mut().now_or_never() {
+            selection_result = Some(value?);
+            break 'replay;
+          }
+          if self.selection_dismissed() {
+            selection_result = Some(None);
+            break 'replay;
+          }
+          sleep(SCAN_POLL_INTERVAL.min(due.saturating_sub(started.elapsed()))).await;
+        }
+        devices = update.devices;
+        emit_selection_update(&self.inner.app, &window_label, &update_event, &devices, false);
+      }
+      emit_selection_update(&self.inner.app, &window_label, &update_event, &devices, true);
+      if devices.is_empty() {
+        return Err(
+          finish_empty_selection(selection_result, selection_future, || {
+            let _ = self.inner.app.emit(&selection_event, SelectionEventPayload::cancelled());
+          })
+          .await,
+        );
+      }
+    }
+
+    let selected_ids = match selection_result {
+      Some(result) => result,
+      None => selection_future.await?,
+    }
+    .ok_or(Error::SelectionCancelled)?;
+    let selected_id = selected_ids.first().ok_or(Error::SelectionCancelled)?;
+    devices
+      .into_iter()
+      .find(|device| &device.id == selected_id)
+      .ok_or_else(|| Error::DeviceNotFound(selected_id.clone()))
+  }
+
+  /// Resolves the picked ids in selection order, caches their peripherals and
+  /// records the first pick for `get_last_selection`.
+  async fn finish_selection(
+    &self,
+    normalized: &NormalizedRequestDeviceOptions,
+    devices: Vec<BluetoothDevice>,
+    matched: &mut HashMap<String, Peripheral>,
+    selected_ids: &[String],
+  ) -> Result<Vec<BluetoothDevice>> {
+    let mut offered: HashMap<String, BluetoothDevice> =
+      devices.into_iter().map(|device| (device.id.clone(), device)).collect();
+    let mut selected = Vec::with_capacity(selected_ids.len());
+    for selected_id in selected_ids {
+      if selected.iter().any(|device: &BluetoothDevice| &device.id == selected_id) {
+        continue;
+      }
+      let device = offered
+        .remove(selected_id)
+        .ok_or_else(|| Error::DeviceNotFound(selected_id.clone()))?;
+      selected.push(device);
+    }
+
+    {
+      let mut allowed = self.inner.allowed_services.lock().await;
+      for device in &selected {
+        allowed
+          .entry(device.id.clone())
+          .or_default()
+          .extend(normalized.allowed_services.iter().copied());
+      }
+    }
+    for (index, device) in selected.iter().enumerate() {
+      if let Some(peripheral) = matched.remove(&device.id) {
+        if index == 0 {
+          self.record_selection(normalized, &peripheral, device).await;
+        }
+        self.grant_device(device, &peripheral).await;
+        self.cache_peripheral(&device.id, peripheral).await;
+      }
+      log::info!("Device selected | device_id={} | name={:?}", device.id, device.name);
+    }
+    self.save_granted_devices();
+    Ok(selected)
+  }
+
+  /// Cancels the picker of the in-flight `request_device` (or
+  /// `request_devices`) call, which then fails with `SelectionCancelled`.
+  /// Without a `request_id` the active request is dismissed. Unknown or
+  /// finished request ids are ignored.
+  pub async fn dismiss_selection(&self, request: DismissSelectionRequest) -> Result<()> {
+    let Some(request_id) = self.active_request_id() else {
+      return Ok(());
+    };
+    if request.request_id.as_deref().is_some_and(|id| id != request_id) {
+      return Ok(());
+    }
+    log::info!("Dismissing selection | request_id={request_id}");
+    self.inner.selection_dismissed.store(true, Ordering::SeqCst);
+    let app = &self.inner.app;
+    let _ = app.emit(
+      &format!("{SELECTION_EVENT_PREFIX}{request_id}"),
+      SelectionEventPayload::cancelled(),
+    );
+    if let Some(window) = app.get_webview_window(&self.inner.selection_page.window_label(&request_id)) {
+      let _ = window.close();
+    }
+    Ok(())
+  }
+
+  fn selection_dismissed(&self) -> bool {
+    self.inner.selection_dismissed.load(Ordering::SeqCst)
+  }
+
+  /// Describes the configured selection handler.
+  pub async fn get_selection_info(&self) -> Result<SelectionHandlerInfo> {
+    let handler = &self.inner.selection_handler;
+    Ok(SelectionHandlerInfo {
+      mode: handler.mode(),
+      wants_full_scan: handler.wants_full_scan(),
+    })
+  }
+
+  /// Runs `request_device`, connects to the chosen device and discovers its
+  /// services in one call. Errors from each step are returned unchanged.
+  pub async fn connect_matching(&self, options: RequestDeviceOptions) -> Result<GattServerInfo> {
+    let device = self.request_device(options).await?;
+    self
+      .connect_gatt(ConnectRequest {
+        device_id: device.id,
+        services: Vec::new(),
+      })
+      .await
+  }
+
+  /// Returns which filter matched the last selected device and the
+  /// advertisement data seen when it was picked.
+  pub async fn get_last_selection(&self) -> Result<Option<SelectionInfo>> {
+    Ok(self.inner.last_selection.lock().await.clone())
+  }
+
+  async fn grant_device(&self, device: &BluetoothDevice, peripheral: &Peripheral) {
+    self.inner.device_ids.remember(&device.id, peripheral.id());
+    let allowed_services = self.allowed_services(&device.id).await.unwrap_or_default();
+    self
+      .inner
+      .granted_devices
+      .lock()
+      .unwrap_or_else(|err| err.into_inner())
+      .insert(
+        device.id.clone(),
+        GrantedDevice {
+          device_id: device.id.clone(),
+          peripheral_id: peripheral.id(),
+          name: device.name.clone(),
+          allowed_services,
+        },
+      );
+  }
+
+  async fn record_selection(
+    &self,
+    normalized: &NormalizedRequestDeviceOptions,
+    peripheral: &Peripheral,
+    device: &BluetoothDevice,
+  ) {
+    let properties = peripheral.properties().await.ok().flatten().unwrap_or_default();
+    let info = SelectionInfo {
+      device: device.clone(),
+      matched_filter_index: normalized.matched_filter_index(&properties),
+      advertisement: advertisement_snapshot(&properties, self.inner.max_advertisement_data_len),
+    };
+    *self.inner.last_selection.lock().await = Some(info);
+    let selected_at_ms = SystemTime::now()
+      .duration_since(UNIX_EPOCH)
+      .map_or(0, |elapsed| elapsed.as_millis() as u64);
+    self.set_last_device(Some(LastDevice {
+      device_id: device.id.clone(),
+      name: device.name.clone(),
+      selected_at_ms,
+    }));
+  }
+
+  /// The device most recently picked through `request_device`, if any. It
+  /// may be out of range; pass its id to `connect_gatt` to reconnect.
+  pub async fn get_last_device(&self) -> Result<Option<LastDevice>> {
+    Ok(
+      self
+        .inner
+        .last_device
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+        .clone(),
+    )
+  }
+
+  fn set_last_device(&self, device: Option<LastDevice>) {
+    if let Some(path) = &self.inner.last_device_store {
+      save_last_device(path, device.as_ref());
+    }
+    *self.inner.last_device.lock().unwrap_or_else(|err| err.into_inner()) = device;
+  }
+
+  /// Connects and discovers services. btleplug has no targeted discovery on
+  /// any backend, so the full table is always discovered and cached; a
+  /// `services` filter only narrows the returned snapshot. The MTU is stored
+  /// where BlueZ reports it, so writes are chunked without a `get_mtu` call.
+  pub async fn connect_gatt(&self, request: ConnectRequest) -> Result<GattServerInfo> {
+    let wanted = request
+      .services
+      .iter()
+      .map(|uuid| parse_uuid(uuid))
+      .collect::<Result<HashSet<Uuid>>>()?;
+    let _queued = self.queue_gatt_operation(&request.device_id).await;
+    let peripheral = self.get_or_try_load_peripheral(&request.device_id).await?;
+    if !peripheral.is_connected().await.unwrap_or(false) {
+      let _connecting = ConnectingGuard::new(&self.inner.connecting, &request.device_id);
+      peripheral.connect().await?;
+    }
+    let discovery_errors = self.discover_services_tolerant(&request.device_id, &peripheral).await?;
+    if let Err(err) = self.refresh_mtu(&request.device_id, &peripheral).await {
+      log::debug!("MTU not queried | device_id={} | err={:?}", request.device_id, err);
+    }
+    if let Err(err) = self.start_service_changed_watch(&request.device_id, &peripheral).await {
+      log::debug!("Service Changed not watched | device_id={} | err={:?}", request.device_id, err);
+    }
+    let mut info = self.describe_gatt_server(&request.device_id, &peripheral).await?;
+    info.discovery_errors = discovery_errors;
+    if self.inner.reconnect_policy.is_some() {
+      self
+        .inner
+        .reconnect_devices
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+        .insert(request.device_id.clone());
+    }
+    if !wanted.is_empty() {
+      info
+        .services
+        .retain(|service| parse_uuid(&service.uuid).is_ok_and(|uuid| wanted.contains(&uuid)));
+      info.partial = true;
+    }
+    Ok(info)
+  }
+
+  /// Reports whether the device is connected, or being connected by
+  /// `connect_gatt`, `start_notifications` or an automatic reconnect.
+  pub async fn get_connection_state(&self, request: DeviceRequest) -> Result<ConnectionState> {
+    let peripheral = self.get_or_try_load_peripheral(&request.device_id).await?;
+    if peripheral.is_connected().await.unwrap_or(false) {
+      return Ok(ConnectionState::Connected);
+    }
+    let connecting = self
+      .inner
+      .connecting
+      .lock()
+      .unwrap_or_else(|err| err.into_inner())
+      .contains(&request.device_id)
+      || self.inner.reconnect_tasks.lock().await.contains_key(&request.device_id);
+    Ok(if connecting {
+      ConnectionState::Connecting
+    } else {
+      ConnectionState::Disconnected
+    })
+  }
+
+  /// Re-reads the service UUIDs the device is currently advertising, without
+  /// connecting.
+  pub async fn get_advertised_services(&self, request: DeviceRequest) -> Result<Vec<String>> {
+    let cached = self.get_or_try_load_peripheral(&request.device_id).await?;
+    // The cached handle can outlive the adapter's own record of the device.
+    let peripheral = self
+      .inner
+      .adapter()
+      .peripheral(&cached.id())
+      .await
+      .map_err(|_| Error::DeviceNotFound(request.device_id.clone()))?;
+    let properties = peripheral.properties().await?;
+    Ok(
+      properties
+        .map(|properties| properties.services.iter().map(format_uuid).collect())
+        .unwrap_or_default(),
+    )
+  }
+
+  /// Drops the cached attribute table for a device and runs service discovery again.
+  pub async fn refresh_gatt(&self, request: DeviceRequest) -> Result<GattServerInfo> {
+    let peripheral = self.get_or_try_load_peripheral(&request.device_id).await?;
+    self.inner.discovered_services.lock().await.remove(&request.device_id);
+    let discovery_errors = self.discover_services_tolerant(&request.device_id, &peripheral).await?;
+    log::info!("GATT cache refreshed | device_id={}", request.device_id);
+    let mut info = self.describe_gatt_server(&request.device_id, &peripheral).await?;
+    info.discovery_errors = discovery_errors;
+    Ok(info)
+  }
+
+  pub async fn disconnect_gatt(&self, request: DeviceRequest) -> Result<()> {
+    let peripheral = self.get_or_try_load_peripheral(&request.device_id).await?;
+    self.inner.cancel_reconnect(&request.device_id).await;
+    if peripheral.is_connected().await.unwrap_or(false) {
+      peripheral.disconnect().await?;
+    }
+    self.inner.discovered_services.lock().await.remove(&request.device_id);
+    self.inner.mtus.lock().await.remove(&request.device_id);
+    Ok(())
+  }
+
+  /// Stops every subscription, watcher and background scan and disconnects
+  /// all cached devices, e.g. on app exit or logout. Devices are
+  /// disconnected concurrently; failures are logged and do not stop the
+  /// others. A running `request_device` scan is left alone.
+  pub async fn disconnect_all(&self) -> Result<()> {
+    {
+      let mut background_scan = self.inner.background_scan.lock().await;
+      let mut watchers = self.inner.advertisement_watchers.lock().await;
+      let scanning = background_scan.is_some() || !watchers.is_empty();
+      if let Some(handle) = background_scan.take() {
+        handle.abort();
+      }
+      for (_, handle) in watchers.drain() {
+        handle.abort();
+      }
+      if scanning && self.active_request_id().is_none() {
+        self.inner.adapter().stop_scan().await.ok();
+      }
+    }
+    let tasks: Vec<JoinHandle<()>> = self
+      .inner
+      .notification_tasks
+      .lock()
+      .await
+      .drain()
+      .map(|(_, handle)| handle)
+      .collect();
+    for handle in tasks {
+      handle.abort();
+    }
+    self.inner.notification_gates.lock().await.clear();
+    let peripherals: Vec<(String, Peripheral)> = self
+      .inner
+      .peripherals
+      .read()
+      .await
+      .iter()
+      .map(|(device_id, peripheral)| (device_id.clone(), peripheral.clone()))
+      .collect();
+    let disconnects = peripherals.into_iter().map(|(device_id, peripheral)| async move {
+      self.inner.cancel_reconnect(&device_id).await;
+      if peripheral.is_connected().await.unwrap_or(false) {
+        match peripheral.disconnect().await {
+          Ok(()) => log::info!("Disconnected | device_id={device_id}"),
+          Err(err) => log::warn!("Disconnect failed | device_id={} | err={:?}", device_id, err),
+        }
+      }
+      self.inner.discovered_services.lock().await.remove(&device_id);
+      self.inner.mtus.lock().await.remove(&device_id);
+    });
+    futures::future::join_all(disconnects).await;
+    Ok(())
+  }
+
+  /// Stops the device's notifications and watchers, disconnects it and
+  /// drops everything the plugin knows about it. A failed disconnect is
+  /// logged and does not keep the device known.
+  pub async fn forget_device(&self, request: DeviceRequest) -> Result<()> {
+    self.inner.cancel_reconnect(&request.device_id).await;
+    clear_notifications_for(&self.inner.notification_tasks, &request.device_id).await;
+    let prefix = notification_key(&request.device_id, "");
+    self
+      .inner
+      .notification_gates
+      .lock()
+      .await
+      .retain(|key, _| !key.starts_with(&prefix));
+    self.remove_advertisement_watcher(&request.device_id).await;
+    let mut cache = self.inner.peripherals.write().await;
+    if let Some(peripheral) = cache.get(&request.device_id) {
+      if peripheral.is_connected().await.unwrap_or(false) {
+        if let Err(err) = peripheral.disconnect().await {
+          log::warn!("Disconnect failed while forgetting | device_id={} | err={:?}", request.device_id, err);
+        }
+      }
+    }
+    cache.remove(&request.device_id);
+    self.inner.peripheral_recency.lock().await.retain(|id| id != &request.device_id);
+    self.inner.discovered_services.lock().await.remove(&request.device_id);
+    self.inner.appearances.lock().await.remove(&request.device_id);
+    self.inner.mtus.lock().await.remove(&request.device_id);
+    self.inner.allowed_services.lock().await.remove(&request.device_id);
+    self.inner.gatt_queues.remove(|key| key == request.device_id);
+    self.inner.value_waits.remove(|key| key.starts_with(&prefix));
+    self.inner.device_ids.forget(&request.device_id);
+    self
+      .inner
+      .value_formats
+      .lock()
+      .unwrap_or_else(|err| err.into_inner())
+      .retain(|(device_id, _), _| device_id.as_deref() != Some(request.device_id.as_str()));
+    self
+      .inner
+      .characteristic_stats
+      .lock()
+      .unwrap_or_else(|err| err.into_inner())
+      .retain(|key, _| !key.starts_with(&prefix));
+    let is_last_device = self
+      .inner
+      .last_device
+      .lock()
+      .unwrap_or_else(|err| err.into_inner())
+      .as_ref()
+      .is_some_and(|last| last.device_id == request.device_id);
+    if is_last_device {
+      self.set_last_device(None);
+    }
+    let was_granted = self
+      .inner
+      .granted_devices
+      .lock()
+      .unwrap_or_else(|err| err.into_inner())
+      .remove(&request.device_id)
+      .is_some();
+    if was_granted {
+      self.save_granted_devices();
+    }
+    Ok(())
+  }
+
+  /// Registers how values of a characteristic are decoded into the
+  /// `interpreted` field of value events, for the rest of the session.
+  pub async fn register_characteristic_format(&self, request: RegisterFormatRequest) -> Result<()> {
+    let characteristic_uuid = parse_uuid(&request.characteristic_uuid)?;
+    self
+      .inner
+      .value_formats
+      .lock()
+      .unwrap_or_else(|err| err.into_inner())
+      .insert((request.device_id, characteristic_uuid), request.format);
+    Ok(())
+  }
+
+  fn value_format(&self, device_id: &str, characteristic_uuid: &str) -> Option<ValueFormat> {
+    let characteristic_uuid = parse_uuid(characteristic_uuid).ok()?;
+    let formats = self
+      .inner
+      .value_formats
+      .lock()
+      .unwrap_or_else(|err| err.into_inner());
+    formats
+      .get(&(Some(device_id.to_string()), characteristic_uuid))
+      .or_else(|| formats.get(&(None, characteristic_uuid)))
+      .copied()
+  }
+
+  /// Requests a PHY for the connection. btleplug has no PHY API on any
+  /// backend, so known devices get `UnsupportedPlatform`.
+  pub async fn set_preferred_phy(&self, request: SetPhyRequest) -> Result<()> {
+    self.get_or_try_load_peripheral(&request.device_id).await?;
+    Err(Error::UnsupportedPlatform)
+  }
+
+  /// Reads the PHY in use for the connection. See [`Self::set_preferred_phy`].
+  pub async fn get_phy(&self, request: DeviceRequest) -> Result<PhyInfo> {
+    self.get_or_try_load_peripheral(&request.device_id).await?;
+    Err(Error::UnsupportedPlatform)
+  }
+
+  /// Drops the live peripheral handle to free resources while keeping the
+  /// device known. Unlike [`Self::forget_device`], later calls with the same
+  /// id reload the peripheral from the adapter instead of failing.
+  pub async fn release_device(&self, request: DeviceRequest) -> Result<()> {
+    self.inner.cancel_reconnect(&request.device_id).await;
+    let peripheral = self.inner.peripherals.write().await.remove(&request.device_id);
+    self.inner.peripheral_recency.lock().await.retain(|id| id != &request.device_id);
+    clear_notifications_for(&self.inner.notification_tasks, &request.device_id).await;
+    let prefix = notification_key(&request.device_id, "");
+    self
+      .inner
+      .notification_gates
+      .lock()
+      .await
+      .retain(|key, _| !key.starts_with(&prefix));
+    self
+      .inner
+      .subscriptions
+      .lock()
+      .unwrap_or_else(|err| err.into_inner())
+      .retain(|key, _| !key.starts_with(&prefix));
+    self
+      .inner
+      .characteristic_stats
+      .lock()
+      .unwrap_or_else(|err| err.into_inner())
+      .retain(|key, _| !key.starts_with(&prefix));
+    self.inner.discovered_services.lock().await.remove(&request.device_id);
+    if let Some(peripheral) = peripheral {
+      if peripheral.is_connected().await.unwrap_or(false) {
+        peripheral.disconnect().await?;
+      }
     }
     Ok(())
   }
 
-  pub async fn forget_device(&self, request: DeviceRequest) -> Result<()> {
-    let mut cache = self.inner.peripherals.write().await;
-    cache.remove(&request.device_id);
-    Ok(())
-  }
-
   pub async fn get_primary_services(&self, request: ServiceRequest) -> Result<Vec<BluetoothService>> {
+    let _queued = self.queue_gatt_operation(&request.device_id).await;
     let peripheral = self.get_or_try_load_peripheral(&request.device_id).await?;
-    peripheral.discover_services().await?;
+    self.ensure_services_discovered(&request.device_id, &peripheral).await?;
+    if let Some(target) = &request.service_uuid {
+      self.check_service_allowed(&request.device_id, &parse_uuid(target)?).await?;
+    }
+    let allowed = self.allowed_services(&request.device_id).await;
     let services = peripheral.services();
     let response = services
       .into_iter()
@@ -951,16 +3057,43 @@ impl<R: Runtime> WebBluetooth<R> {
         Some(target) => format_uuid(&service.uuid) == normalize_uuid_string(target),
         None => true,
       })
+      .filter(|service| service_allowed(allowed.as_ref(), &service.uuid))
       .map(service_to_model)
       .collect();
     Ok(response)
   }
 
+  /// Single-service variant of [`Self::get_primary_services`], like Web
+  /// Bluetooth's `getPrimaryService()`: `service_uuid` is required, and a
+  /// service that is absent or not primary is `ServiceNotFound`.
+  pub async fn get_primary_service(&self, request: ServiceRequest) -> Result<BluetoothService> {
+    let target = request
+      .service_uuid
+      .as_deref()
+      .ok_or_else(|| Error::InvalidRequest("get_primary_service requires serviceUuid".to_string()))?;
+    let service_uuid = parse_uuid(target)?;
+    let _queued = self.queue_gatt_operation(&request.device_id).await;
+    let peripheral = self.get_or_try_load_peripheral(&request.device_id).await?;
+    self.ensure_services_discovered(&request.device_id, &peripheral).await?;
+    self.check_service_allowed(&request.device_id, &service_uuid).await?;
+    peripheral
+      .services()
+      .into_iter()
+      .find(|service| service.uuid == service_uuid && service.primary)
+      .map(service_to_model)
+      .ok_or_else(|| Error::ServiceNotFound {
+        device_id: request.device_id.clone(),
+        service_uuid: format_uuid(&service_uuid),
+      })
+  }
+
   pub async fn get_characteristics(&self, request: CharacteristicsRequest) -> Result<Vec<BluetoothCharacteristic>> {
+    let _queued = self.queue_gatt_operation(&request.device_id).await;
     let peripheral = self.get_or_try_load_peripheral(&request.device_id).await?;
-    peripheral.discover_services().await?;
+    self.ensure_services_discovered(&request.device_id, &peripheral).await?;
     let services = peripheral.services();
     let service_uuid = parse_uuid(&request.service_uuid)?;
+    self.check_service_allowed(&request.device_id, &service_uuid).await?;
     let service = services
       .into_iter()
       .find(|service| service.uuid == service_uuid)
@@ -968,18 +3101,166 @@ impl<R: Runtime> WebBluetooth<R> {
         device_id: request.device_id.clone(),
         service_uuid: request.service_uuid.clone(),
       })?;
-    let mut chars: Vec<BluetoothCharacteristic> = service
-      .characteristics
-      .iter()
-      .map(characteristic_to_model)
-      .collect();
-    if let Some(target) = request.characteristic_uuid.as_ref() {
-      chars.retain(|item| item.uuid.eq_ignore_ascii_case(target));
+    let target = request.characteristic_uuid.as_deref().map(parse_uuid).transpose()?;
+    Ok(
+      service
+        .characteristics
+        .iter()
+        .filter(|characteristic| target.map_or(true, |uuid| characteristic.uuid == uuid))
+        .map(characteristic_to_model)
+        .collect(),
+    )
+  }
+
+  /// Lists a characteristic's descriptors, like Web Bluetooth's `getDescriptors()`.
+  pub async fn get_descriptors(&self, request: DescriptorsRequest) -> Result<Vec<BluetoothDescriptor>> {
+    let _queued = self.queue_gatt_operation(&request.device_id).await;
+    let (_, characteristic) = self
+      .resolve_characteristic(&request.device_id, &request.service_uuid, &request.characteristic_uuid)
+      .await?;
+    let target = request.descriptor_uuid.as_deref().map(parse_uuid).transpose()?;
+    Ok(
+      characteristic
+        .descriptors
+        .iter()
+        .filter(|descriptor| target.map_or(true, |uuid| descriptor.uuid == uuid))
+        .map(|descriptor| BluetoothDescriptor {
+          uuid: format_uuid(&descriptor.uuid),
+        })
+        .collect(),
+    )
+  }
+
+  /// Lists every characteristic of the device's allowed services with its
+  /// properties and descriptor presence, from the cached attribute table.
+  pub async fn get_characteristic_capabilities(
+    &self,
+    request: DeviceRequest,
+  ) -> Result<Vec<CharacteristicCapabilities>> {
+    let _queued = self.queue_gatt_operation(&request.device_id).await;
+    let peripheral = self.get_or_try_load_peripheral(&request.device_id).await?;
+    self.ensure_services_discovered(&request.device_id, &peripheral).await?;
+    let allowed = self.allowed_services(&request.device_id).await;
+    Ok(
+      peripheral
+        .services()
+        .iter()
+        .filter(|service| service_allowed(allowed.as_ref(), &service.uuid))
+        .flat_map(|service| {
+          service.characteristics.iter().map(|characteristic| CharacteristicCapabilities {
+            service_uuid: format_uuid(&service.uuid),
+            uuid: format_uuid(&characteristic.uuid),
+            properties: characteristic_to_model(characteristic).properties,
+            has_client_configuration: characteristic
+              .descriptors
+              .iter()
+              .any(|descriptor| descriptor.uuid == CLIENT_CONFIGURATION_DESCRIPTOR_UUID),
+            has_descriptors: !characteristic.descriptors.is_empty(),
+          })
+        })
+        .collect(),
+    )
+  }
+
+  /// Reads the GAP Appearance characteristic and labels it.
+  pub async fn read_appearance(&self, request: DeviceRequest) -> Result<Appearance> {
+    let _queued = self.queue_gatt_operation(&request.device_id).await;
+    let (peripheral, characteristic) = self
+      .resolve_characteristic(
+        &request.device_id,
+        &format_uuid(&GENERIC_ACCESS_SERVICE_UUID),
+        &format_uuid(&APPEARANCE_CHARACTERISTIC_UUID),
+      )
+      .await?;
+    let bytes = peripheral.read(&characteristic).await?;
+    let value = match bytes.as_slice() {
+      [low, high, ..] => u16::from_le_bytes([*low, *high]),
+      _ => {
+        return Err(Error::InvalidRequest(format!(
+          "Appearance value for device {} is {} bytes, expected 2",
+          request.device_id,
+          bytes.len()
+        )))
+      }
+    };
+    self
+      .inner
+      .appearances
+      .lock()
+      .await
+      .insert(request.device_id, value);
+    Ok(describe_appearance(value))
+  }
+
+  /// Reads the Battery Level of every Battery Service instance, e.g. both
+  /// earbuds of a pair. btleplug merges services sharing a UUID, so only
+  /// BlueZ can tell instances apart; other backends report at most one.
+  /// Devices without a Battery Service yield an empty list.
+  pub async fn read_all_battery_levels(&self, request: DeviceRequest) -> Result<Vec<BatteryLevel>> {
+    let _queued = self.queue_gatt_operation(&request.device_id).await;
+    let peripheral = self.get_or_try_load_peripheral(&request.device_id).await?;
+    self.ensure_services_discovered(&request.device_id, &peripheral).await?;
+    let characteristic = peripheral
+      .services()
+      .into_iter()
+      .filter(|service| service.uuid == BATTERY_SERVICE_UUID)
+      .flat_map(|service| service.characteristics)
+      .find(|characteristic| characteristic.uuid == BATTERY_LEVEL_CHARACTERISTIC_UUID);
+    let Some(characteristic) = characteristic else {
+      return Ok(Vec::new());
+    };
+    self.check_service_allowed(&request.device_id, &BATTERY_SERVICE_UUID).await?;
+    #[cfg(target_os = "linux")]
+    {
+      let adapter_info = self.inner.adapter().adapter_info().await?;
+      let adapter_id = adapter_info.split(' ').next().unwrap_or_default();
+      match bluez_battery_levels(adapter_id, &peripheral.address().to_string()).await {
+        Ok(levels) if !levels.is_empty() => return Ok(levels),
+        Ok(_) => {}
+        Err(err) => log::warn!(
+          "Failed to read BlueZ battery levels | device_id={} | err={:?}",
+          request.device_id,
+          err
+        ),
+      }
     }
-    Ok(chars)
+    let value = peripheral.read(&characteristic).await?;
+    let level = value.first().copied().ok_or_else(|| {
+      Error::InvalidRequest(format!("Battery level for device {} is empty", request.device_id))
+    })?;
+    Ok(vec![BatteryLevel {
+      service_instance_id: format_uuid(&BATTERY_SERVICE_UUID),
+      level,
+    }])
+  }
+
+  /// Reads the firmware, hardware and software revision strings from the
+  /// Device Information Service in one call.
+  pub async fn read_revisions(&self, request: DeviceRequest) -> Result<DeviceRevisions> {
+    let _queued = self.queue_gatt_operation(&request.device_id).await;
+    self
+      .check_service_allowed(&request.device_id, &DEVICE_INFORMATION_SERVICE_UUID)
+      .await?;
+    let peripheral = self.get_or_try_load_peripheral(&request.device_id).await?;
+    self.ensure_services_discovered(&request.device_id, &peripheral).await?;
+    let service = peripheral
+      .services()
+      .into_iter()
+      .find(|service| service.uuid == DEVICE_INFORMATION_SERVICE_UUID)
+      .ok_or_else(|| Error::ServiceNotFound {
+        device_id: request.device_id.clone(),
+        service_uuid: format_uuid(&DEVICE_INFORMATION_SERVICE_UUID),
+      })?;
+    // One read at a time, like every other operation under the queue.
+    Ok(DeviceRevisions {
+      firmware: read_optional_string(&peripheral, &service, FIRMWARE_REVISION_CHARACTERISTIC_UUID).await?,
+      hardware: read_optional_string(&peripheral, &service, HARDWARE_REVISION_CHARACTERISTIC_UUID).await?,
+      software: read_optional_string(&peripheral, &service, SOFTWARE_REVISION_CHARACTERISTIC_UUID).await?,
+    })
   }
 
   pub async fn read_characteristic_value(&self, request: ReadValueRequest) -> Result<BluetoothValue> {
+    let _queued = self.queue_gatt_operation(&request.device_id).await;
     let (peripheral, characteristic) = self.resolve_characteristic(&request.device_id, &request.service_uuid, &request.characteristic_uuid).await?;
     let bytes = peripheral.read(&characteristic).await?;
     Ok(BluetoothValue {
@@ -987,107 +3268,1651 @@ impl<R: Runtime> WebBluetooth<R> {
     })
   }
 
-  pub async fn write_characteristic_value(&self, request: WriteValueRequest) -> Result<()> {
-    let (peripheral, characteristic) = self
-      .resolve_characteristic(&request.device_id, &request.service_uuid, &request.characteristic_uuid)
+  /// Reads any descriptor by UUID. With `interpret`, standard descriptors are
+  /// also decoded; unknown ones only carry the raw value.
+  pub async fn read_descriptor_value(&self, request: DescriptorRequest) -> Result<DescriptorValue> {
+    let _queued = self.queue_gatt_operation(&request.device_id).await;
+    let (peripheral, descriptor) = self
+      .resolve_descriptor(
+        &request.device_id,
+        &request.service_uuid,
+        &request.characteristic_uuid,
+        &request.descriptor_uuid,
+      )
       .await?;
-    let payload = BASE64_STANDARD.decode(request.value)?;
-    let write_type = if request.with_response {
-      WriteType::WithResponse
+    let bytes = peripheral.read_descriptor(&descriptor).await?;
+    let interpreted = if request.interpret {
+      decode_standard_descriptor(descriptor.uuid, &bytes)
     } else {
-      WriteType::WithoutResponse
+      None
     };
-    peripheral.write(&characteristic, &payload, write_type).await?;
-    Ok(())
+    Ok(DescriptorValue {
+      value: BASE64_STANDARD.encode(bytes),
+      interpreted,
+    })
   }
 
-  pub async fn start_notifications(&self, request: NotificationRequest) -> Result<()> {
+  /// Reads several descriptors of one characteristic one after another, keyed
+  /// by descriptor UUID. Failed reads and requested descriptors the
+  /// characteristic lacks are reported per entry.
+  pub async fn read_descriptors(
+    &self,
+    request: ReadDescriptorsRequest,
+  ) -> Result<HashMap<String, DescriptorReadResult>> {
+    let _queued = self.queue_gatt_operation(&request.device_id).await;
     let (peripheral, characteristic) = self
       .resolve_characteristic(&request.device_id, &request.service_uuid, &request.characteristic_uuid)
       .await?;
-    let key = notification_key(&request.device_id, &request.characteristic_uuid);
-    {
-      let tasks = self.inner.notification_tasks.lock().await;
-      if tasks.contains_key(&key) {
-        return Err(Error::NotificationsAlreadyActive {
-          device_id: request.device_id.clone(),
-          characteristic_uuid: request.characteristic_uuid.clone(),
-        });
-      }
-    }
-    peripheral.subscribe(&characteristic).await?;
+    let targets = descriptor_targets(&characteristic, request.descriptor_uuids.as_deref())?;
+    Ok(
+      read_descriptor_targets(&request.device_id, targets, |descriptor| {
+        let peripheral = peripheral.clone();
+        async move { Ok(peripheral.read_descriptor(&descriptor).await?) }
+      })
+      .await,
+    )
+  }
+
+  /// Writes any descriptor by UUID, from raw bytes or a typed value for a
+  /// standard descriptor.
+  pub async fn write_descriptor_value(&self, request: WriteDescriptorRequest) -> Result<()> {
+    let _queued = self.queue_gatt_operation(&request.device_id).await;
+    let (peripheral, descriptor) = self
+      .resolve_descriptor(
+        &request.device_id,
+        &request.service_uuid,
+        &request.characteristic_uuid,
+        &request.descriptor_uuid,
+      )
+      .await?;
+    let payload = match (&request.value, &request.interpreted) {
+      (Some(value), None) => BASE64_STANDARD.decode(value)?,
+      (None, Some(typed)) => {
+        if standard_descriptor_uuid(typed) != descriptor.uuid {
+          return Err(Error::InvalidRequest(format!(
+            "Typed value does not match descriptor {}",
+            request.descriptor_uuid
+          )));
+        }
+        encode_standard_descriptor(typed)
+      }
+      _ => {
+        return Err(Error::InvalidRequest(
+          "Exactly one of value and interpreted must be given".into(),
+        ))
+      }
+    };
+    peripheral.write_descriptor(&descriptor, &payload).await?;
+    Ok(())
+  }
+
+  /// Measures read round trips to a connected device. Each sample is one GATT
+  /// read of the probe characteristic, since btleplug cannot read a live RSSI.
+  pub async fn ping_device(&self, request: PingDeviceRequest) -> Result<PingResult> {
+    let samples = ping_samples(request.samples)?;
+    let _queued = self.queue_gatt_operation(&request.device_id).await;
+    let peripheral = self.get_or_try_load_peripheral(&request.device_id).await?;
+    if !peripheral.is_connected().await.unwrap_or(false) {
+      return Err(Error::NotConnected(request.device_id));
+    }
+    let characteristic = match (&request.service_uuid, &request.characteristic_uuid) {
+      (Some(service_uuid), Some(characteristic_uuid)) => {
+        self
+          .resolve_characteristic(&request.device_id, service_uuid, characteristic_uuid)
+          .await?
+          .1
+      }
+      (None, None) => {
+        self.ensure_services_discovered(&request.device_id, &peripheral).await?;
+        let allowed = self.allowed_services(&request.device_id).await;
+        let readable: Vec<Characteristic> = peripheral
+          .characteristics()
+          .into_iter()
+          .filter(|characteristic| characteristic.properties.contains(CharPropFlags::READ))
+          .filter(|characteristic| service_allowed(allowed.as_ref(), &characteristic.service_uuid))
+          .collect();
+        readable
+          .iter()
+          .find(|characteristic| characteristic.uuid == DEVICE_NAME_CHARACTERISTIC_UUID)
+          .or_else(|| readable.first())
+          .cloned()
+          .ok_or_else(|| Error::InvalidRequest(format!("Device {} has no readable characteristic", request.device_id)))?
+      }
+      _ => {
+        return Err(Error::InvalidRequest(
+          "serviceUuid and characteristicUuid must be given together".into(),
+        ))
+      }
+    };
+
+    let mut timings = Vec::with_capacity(samples as usize);
+    for _ in 0..samples {
+      let started = Instant::now();
+      peripheral.read(&characteristic).await?;
+      timings.push(started.elapsed().as_secs_f64() * 1000.0);
+    }
+    Ok(PingResult {
+      average_ms: timings.iter().sum::<f64>() / timings.len() as f64,
+      min_ms: timings.iter().copied().fold(f64::INFINITY, f64::min),
+      max_ms: timings.iter().copied().fold(0.0, f64::max),
+      samples,
+      probe_characteristic_uuid: format_uuid(&characteristic.uuid),
+    })
+  }
+
+  /// Resolves once the device disconnects, immediately if it already is, or
+  /// fails with `OperationTimeout` after `timeout_ms`.
+  pub async fn await_disconnect(&self, request: AwaitDisconnectRequest) -> Result<()> {
+    let peripheral = self.get_or_try_load_peripheral(&request.device_id).await?;
+    let (sender, receiver) = oneshot::channel();
+    {
+      // Register before checking the connection so a disconnect in between is not missed.
+      let mut waiters = self.inner.disconnect_waiters.lock().await;
+      let entry = waiters.entry(request.device_id.clone()).or_default();
+      entry.retain(|waiter| !waiter.is_closed());
+      entry.push(sender);
+    }
+    if !peripheral.is_connected().await.unwrap_or(false) {
+      return Ok(());
+    }
+    match timeout(Duration::from_millis(request.timeout_ms), receiver).await {
+      Ok(_) => Ok(()),
+      Err(_) => Err(Error::OperationTimeout),
+    }
+  }
+
+  /// Resolves with the first value of a characteristic that meets the
+  /// condition, checking the current value first. Notifying characteristics
+  /// are subscribed to for the wait unless already subscribed; others are
+  /// polled. Fails with `OperationTimeout` after `timeout_ms`.
+  pub async fn await_value(&self, request: AwaitValueRequest) -> Result<BluetoothValue> {
+    let expected = match &request.condition {
+      ValueCondition::Equals { value } => BASE64_STANDARD.decode(value)?,
+      ValueCondition::HasPrefix { prefix } => BASE64_STANDARD.decode(prefix)?,
+    };
+    let exact = matches!(request.condition, ValueCondition::Equals { .. });
+    let matches = |value: &[u8]| {
+      if exact {
+        value == expected.as_slice()
+      } else {
+        value.starts_with(&expected)
+      }
+    };
+    // The app's own subscription must survive the wait, and concurrent waits
+    // on the characteristic take turns so one cannot unsubscribe another.
+    let key = characteristic_key(&request.device_id, &request.characteristic_uuid)?;
+    let _wait_guard = self.inner.value_waits.lock(&key).await;
+
+    // Only the setup holds the GATT queue; the wait itself would block every
+    // other operation on the device until it ends.
+    let queued = self.queue_gatt_operation(&request.device_id).await;
+    let (peripheral, characteristic) = self
+      .resolve_characteristic(&request.device_id, &request.service_uuid, &request.characteristic_uuid)
+      .await?;
+    let can_subscribe = characteristic
+      .properties
+      .intersects(CharPropFlags::NOTIFY | CharPropFlags::INDICATE);
+    let can_read = characteristic.properties.contains(CharPropFlags::READ);
+    if !can_subscribe && !can_read {
+      return Err(Error::InvalidRequest(format!(
+        "Characteristic {} can be neither read nor subscribed to",
+        request.characteristic_uuid
+      )));
+    }
+    let subscribe = can_subscribe && !self.inner.notification_tasks.lock().await.contains_key(&key);
+    let mut notifications = if can_subscribe {
+      Some(peripheral.notifications().await?)
+    } else {
+      None
+    };
+    if subscribe {
+      peripheral.subscribe(&characteristic).await?;
+    }
+    drop(queued);
+    let poll_interval = Duration::from_millis(request.poll_interval_ms).max(MIN_MONITOR_POLL_INTERVAL);
+    let wait = async {
+      if can_read {
+        let value = self.queued_read(&request.device_id, &peripheral, &characteristic).await?;
+        if matches(&value) {
+          return Ok(value);
+        }
+      }
+      match notifications.as_mut() {
+        Some(stream) => {
+          while let Some(notification) = stream.next().await {
+            if notification.uuid == characteristic.uuid && matches(&notification.value) {
+              return Ok(notification.value);
+            }
+          }
+          Err(Error::NotConnected(request.device_id.clone()))
+        }
+        None => loop {
+          sleep(poll_interval).await;
+          let value = self.queued_read(&request.device_id, &peripheral, &characteristic).await?;
+          if matches(&value) {
+            return Ok(value);
+          }
+        },
+      }
+    };
+    let outcome: std::result::Result<Result<Vec<u8>>, _> =
+      timeout(Duration::from_millis(request.timeout_ms), wait).await;
+    // The app may have started notifications on it during the wait.
+    if subscribe && !self.inner.notification_tasks.lock().await.contains_key(&key) {
+      let _queued = self.queue_gatt_operation(&request.device_id).await;
+      if let Err(err) = peripheral.unsubscribe(&characteristic).await {
+        log::debug!(
+          "Failed to unsubscribe after await_value | characteristic={} | err={:?}",
+          request.characteristic_uuid,
+          err
+        );
+      }
+    }
+    match outcome {
+      Ok(value) => Ok(BluetoothValue {
+        value: BASE64_STANDARD.encode(value?),
+      }),
+      Err(_) => Err(Error::OperationTimeout),
+    }
+  }
+
+  /// Reads a characteristic and streams the value to `on_chunk` in slices.
+  /// btleplug performs long reads (read blob) inside the backend and only
+  /// hands back the assembled value, so chunks are sent once the read
+  /// completes rather than per blob; this still avoids one huge IPC payload.
+  pub async fn read_long_streamed(
+    &self,
+    request: ReadLongStreamedRequest,
+    on_chunk: Channel<ReadChunk>,
+  ) -> Result<ReadLongSummary> {
+    let bytes = {
+      let _queued = self.queue_gatt_operation(&request.device_id).await;
+      let (peripheral, characteristic) = self
+        .resolve_characteristic(&request.device_id, &request.service_uuid, &request.characteristic_uuid)
+        .await?;
+      peripheral.read(&characteristic).await?
+    };
+    let chunk_size = request.chunk_size.max(1);
+    if bytes.is_empty() {
+      on_chunk.send(ReadChunk {
+        offset: 0,
+        chunk_base64: String::new(),
+        done: true,
+      })?;
+    }
+    for (index, chunk) in bytes.chunks(chunk_size).enumerate() {
+      let offset = index * chunk_size;
+      on_chunk.send(ReadChunk {
+        offset,
+        chunk_base64: BASE64_STANDARD.encode(chunk),
+        done: offset + chunk.len() == bytes.len(),
+      })?;
+    }
+    Ok(ReadLongSummary {
+      total_bytes: bytes.len(),
+    })
+  }
+
+  /// Returns a characteristic with its standard descriptors read and
+  /// decoded. Absent or unreadable descriptors are left out.
+  pub async fn describe_characteristic(&self, request: ReadValueRequest) -> Result<CharacteristicDetails> {
+    let _queued = self.queue_gatt_operation(&request.device_id).await;
+    let (peripheral, characteristic) = self
+      .resolve_characteristic(&request.device_id, &request.service_uuid, &request.characteristic_uuid)
+      .await?;
+    let extended = read_standard_descriptor(&peripheral, &characteristic, EXTENDED_PROPERTIES_DESCRIPTOR_UUID).await;
+    let description = read_standard_descriptor(&peripheral, &characteristic, USER_DESCRIPTION_DESCRIPTOR_UUID).await;
+    let configuration =
+      read_standard_descriptor(&peripheral, &characteristic, CLIENT_CONFIGURATION_DESCRIPTOR_UUID).await;
+    let format = read_standard_descriptor(&peripheral, &characteristic, PRESENTATION_FORMAT_DESCRIPTOR_UUID).await;
+
+    let extended_properties = extended.as_deref().and_then(decode_extended_properties);
+    let mut model = characteristic_to_model(&characteristic);
+    if let Some(extended) = &extended_properties {
+      model.properties.reliable_write = extended.reliable_write;
+      model.properties.writable_auxiliaries = extended.writable_auxiliaries;
+    }
+    Ok(CharacteristicDetails {
+      service_uuid: format_uuid(&characteristic.service_uuid),
+      characteristic: model,
+      user_description: description.as_deref().map(decode_user_description),
+      client_configuration: configuration.as_deref().and_then(decode_client_configuration),
+      presentation_format: format.as_deref().and_then(decode_presentation_format),
+      extended_properties,
+    })
+  }
+
+  /// Reads characteristics across devices with bounded concurrency. Failures,
+  /// including disconnected devices, are reported per item and never fail
+  /// the batch. Results keep the order of the request.
+  pub async fn read_across_devices(&self, request: BatchReadRequest) -> Result<Vec<BatchReadResult>> {
+    let results = futures::stream::iter(request.reads)
+      .map(|read| async move {
+        let outcome = self.read_connected(&read).await;
+        BatchReadResult {
+          device_id: read.device_id,
+          service_uuid: read.service_uuid,
+          characteristic_uuid: read.characteristic_uuid,
+          value: outcome.as_ref().ok().map(|bytes| BASE64_STANDARD.encode(bytes)),
+          error: outcome.err().map(|err| err.to_string()),
+        }
+      })
+      .buffered(request.max_concurrency.max(1))
+      .collect()
+      .await;
+    Ok(results)
+  }
+
+  async fn read_connected(&self, read: &ReadValueRequest) -> Result<Vec<u8>> {
+    let _queued = self.queue_gatt_operation(&read.device_id).await;
+    let peripheral = self.get_or_try_load_peripheral(&read.device_id).await?;
+    if !peripheral.is_connected().await.unwrap_or(false) {
+      return Err(Error::NotConnected(read.device_id.clone()));
+    }
+    let (peripheral, characteristic) = self
+      .resolve_characteristic(&read.device_id, &read.service_uuid, &read.characteristic_uuid)
+      .await?;
+    Ok(peripheral.read(&characteristic).await?)
+  }
+
+  /// Reads a characteristic and also emits the value as [`EVENT_NOTIFICATION`]
+  /// with `from_read` set, for frontends that handle every value in one place.
+  pub async fn read_and_emit(&self, request: ReadValueRequest) -> Result<BluetoothValue> {
+    let _queued = self.queue_gatt_operation(&request.device_id).await;
+    let (peripheral, characteristic) = self
+      .resolve_characteristic(&request.device_id, &request.service_uuid, &request.characteristic_uuid)
+      .await?;
+    let bytes = peripheral.read(&characteristic).await?;
+    emit_notification(
+      &self.inner.app,
+      &request.device_id,
+      &request.service_uuid,
+      &request.characteristic_uuid,
+      &bytes,
+      true,
+    );
+    Ok(BluetoothValue {
+      value: BASE64_STANDARD.encode(bytes),
+    })
+  }
+
+  pub async fn write_characteristic_value(&self, request: WriteValueRequest) -> Result<()> {
+    let _queued = self.queue_gatt_operation(&request.device_id).await;
+    let (peripheral, characteristic) = self
+      .resolve_characteristic(&request.device_id, &request.service_uuid, &request.characteristic_uuid)
+      .await?;
+    let payload = BASE64_STANDARD.decode(request.value)?;
+    let kind = request.write_type.unwrap_or(if request.with_response {
+      WriteTypeKind::WithResponse
+    } else {
+      WriteTypeKind::WithoutResponse
+    });
+    let write_type = match kind {
+      WriteTypeKind::WithResponse => WriteType::WithResponse,
+      WriteTypeKind::WithoutResponse => WriteType::WithoutResponse,
+      WriteTypeKind::SignedWithoutResponse => {
+        if !characteristic
+          .properties
+          .contains(CharPropFlags::AUTHENTICATED_SIGNED_WRITES)
+        {
+          return Err(Error::InvalidRequest(format!(
+            "Characteristic {} does not support authenticated signed writes",
+            request.characteristic_uuid
+          )));
+        }
+        // btleplug has no signed write type on any backend.
+        return Err(Error::UnsupportedPlatform);
+      }
+    };
+    // A write without response must fit one ATT packet, so a known MTU
+    // bounds it even when chunking is not configured.
+    let mtu_chunk_size = match write_type {
+      WriteType::WithoutResponse => self
+        .inner
+        .mtus
+        .lock()
+        .await
+        .get(&request.device_id)
+        .map(|mtu| usize::from(*mtu).saturating_sub(ATT_WRITE_HEADER_LEN).max(1)),
+      WriteType::WithResponse => None,
+    };
+    match request.chunk_size.or(mtu_chunk_size).or(self.inner.write_chunk_size) {
+      Some(0) => Err(Error::InvalidRequest("Chunk size must be at least 1".into())),
+      Some(chunk_size) if payload.len() > chunk_size => {
+        log::debug!(
+          "Writing in chunks | device_id={} | characteristic={} | len={} | chunk_size={}",
+          request.device_id,
+          request.characteristic_uuid,
+          payload.len(),
+          chunk_size
+        );
+        write_chunked(&peripheral, &characteristic, &payload, chunk_size, write_type).await
+      }
+      _ => Ok(peripheral.write(&characteristic, &payload, write_type).await?),
+    }
+  }
+
+  /// Writes characteristics in order and stops at the first failure. See
+  /// [`WriteSequenceRequest`] for the rollback semantics.
+  pub async fn write_sequence(&self, request: WriteSequenceRequest) -> Result<WriteSequenceResult> {
+    // Held across the steps and any rollback, so nothing interleaves.
+    let _queued = self.queue_gatt_operation(&request.device_id).await;
+    let mut written: Vec<(Peripheral, Characteristic, Option<Vec<u8>>)> = Vec::with_capacity(request.writes.len());
+    for (index, write) in request.writes.iter().enumerate() {
+      let step = self
+        .write_sequence_step(&request.device_id, write, request.rollback_on_failure)
+        .await;
+      let err = match step {
+        Ok(entry) => {
+          written.push(entry);
+          continue;
+        }
+        Err(err) => err,
+      };
+      log::warn!(
+        "write_sequence stopped | device_id={} | index={} | err={:?}",
+        request.device_id,
+        index,
+        err
+      );
+      let rolled_back = request.rollback_on_failure && restore_written(&written).await;
+      return Ok(WriteSequenceResult {
+        completed: index,
+        failed_index: Some(index),
+        error: Some(err.to_string()),
+        rolled_back,
+      });
+    }
+    Ok(WriteSequenceResult {
+      completed: written.len(),
+      failed_index: None,
+      error: None,
+      rolled_back: false,
+    })
+  }
+
+  async fn write_sequence_step(
+    &self,
+    device_id: &str,
+    write: &SequenceWrite,
+    snapshot: bool,
+  ) -> Result<(Peripheral, Characteristic, Option<Vec<u8>>)> {
+    let (peripheral, characteristic) = self
+      .resolve_characteristic(device_id, &write.service_uuid, &write.characteristic_uuid)
+      .await?;
+    let payload = BASE64_STANDARD.decode(&write.value)?;
+    let previous = if snapshot && characteristic.properties.contains(CharPropFlags::READ) {
+      Some(peripheral.read(&characteristic).await?)
+    } else {
+      None
+    };
+    let write_type = if write.with_response {
+      WriteType::WithResponse
+    } else {
+      WriteType::WithoutResponse
+    };
+    peripheral.write(&characteristic, &payload, write_type).await?;
+    Ok((peripheral, characteristic, previous))
+  }
+
+  /// Fails with `NotConnected` for a disconnected device unless
+  /// `PluginConfig::connect_on_subscribe` is set, in which case it connects
+  /// first. Subscribing while disconnected fails obscurely on most backends.
+  pub async fn start_notifications(&self, request: NotificationRequest) -> Result<()> {
+    let _queued = self.queue_gatt_operation(&request.device_id).await;
+    self.inner.value_source_started();
+    let peripheral = self.get_or_try_load_peripheral(&request.device_id).await?;
+    let connected = peripheral.is_connected().await.unwrap_or(false);
+    if connect_before_subscribing(&request.device_id, connected, self.inner.connect_on_subscribe)? {
+      log::info!("Connecting before subscribing | device_id={}", request.device_id);
+      let _connecting = ConnectingGuard::new(&self.inner.connecting, &request.device_id);
+      peripheral.connect().await?;
+    }
+    let (peripheral, characteristic) = self
+      .resolve_characteristic(&request.device_id, &request.service_uuid, &request.characteristic_uuid)
+      .await?;
+    let key = characteristic_key(&request.device_id, &request.characteristic_uuid)?;
+    {
+      let mut tasks = self.inner.notification_tasks.lock().await;
+      let policy = request.on_duplicate.unwrap_or(self.inner.duplicate_notification_policy);
+      if !resolve_duplicate_subscription(&mut tasks, &key, policy, &request.device_id, &request.characteristic_uuid)?
+      {
+        return Ok(());
+      }
+    }
+    peripheral.subscribe(&characteristic).await?;
     let mut stream = peripheral.notifications().await?;
     let app = self.inner.app.clone();
     let device_id = request.device_id.clone();
     let service_uuid = request.service_uuid.clone();
     let characteristic_uuid = request.characteristic_uuid.clone();
+    let tasks = self.inner.notification_tasks.clone();
+    let gates = self.inner.notification_gates.clone();
+    let task_key = key.clone();
+    let gate = Arc::new(NotificationGate::default());
+    gates.lock().await.insert(key.clone(), gate.clone());
+    let stats = Arc::new(CharacteristicCounters::default());
+    self
+      .inner
+      .characteristic_stats
+      .lock()
+      .unwrap_or_else(|err| err.into_inner())
+      .insert(key.clone(), stats.clone());
+    self
+      .inner
+      .subscriptions
+      .lock()
+      .unwrap_or_else(|err| err.into_inner())
+      .insert(key.clone(), request.clone());
+    // Hold the map while spawning so a stream that ends immediately cannot
+    // remove its entry before it is inserted.
+    let queue = NotificationQueue::new(
+      self.inner.notification_queue_capacity,
+      self.inner.notification_overflow_policy,
+      self.inner.notification_counters.clone(),
+    );
+    let mut guard = self.inner.notification_tasks.lock().await;
     let handle = async_runtime::spawn(async move {
-      while let Some(notification) = stream.next().await {
-        if notification.uuid == characteristic.uuid {
-          emit_notification(&app, &device_id, &service_uuid, &characteristic_uuid, &notification);
+      forward_notifications(&mut stream, characteristic.uuid, &queue, &gate, &stats, |value| {
+        emit_notification(&app, &device_id, &service_uuid, &characteristic_uuid, value, false);
+      })
+      .await;
+      // Explicit stops abort the task, so reaching this point means the
+      // backend closed the stream on its own.
+      end_subscription(&tasks, &gates, &task_key).await;
+      log::warn!(
+        "Notification stream ended | device_id={} | characteristic={}",
+        device_id,
+        characteristic_uuid
+      );
+      let payload = CharacteristicEventPayload {
+        device_id,
+        service_uuid,
+        characteristic_uuid,
+      };
+      emit_device_event(&app, EventKind::NotificationsEnded, &payload.device_id, &payload);
+    });
+    guard.insert(key, handle);
+    Ok(())
+  }
+
+  pub async fn stop_notifications(&self, request: NotificationRequest) -> Result<()> {
+    let _queued = self.queue_gatt_operation(&request.device_id).await;
+    let (peripheral, characteristic) = self
+      .resolve_characteristic(&request.device_id, &request.service_uuid, &request.characteristic_uuid)
+      .await?;
+    let key = characteristic_key(&request.device_id, &request.characteristic_uuid)?;
+    let handle = self.inner.notification_tasks.lock().await.remove(&key).ok_or(Error::NotificationsNotActive {
+      device_id: request.device_id.clone(),
+      characteristic_uuid: request.characteristic_uuid.clone(),
+    })?;
+    handle.abort();
+    self.inner.notification_gates.lock().await.remove(&key);
+    self
+      .inner
+      .subscriptions
+      .lock()
+      .unwrap_or_else(|err| err.into_inner())
+      .remove(&key);
+    peripheral.unsubscribe(&characteristic).await?;
+    Ok(())
+  }
+
+  /// Stops emitting values for an active subscription while keeping it open
+  /// on the device, so resuming needs no GATT round trip. The latest value
+  /// received while paused is kept for [`Self::resume_notifications`].
+  pub async fn pause_notifications(&self, request: NotificationRequest) -> Result<()> {
+    let gate = self
+      .notification_gate(&request.device_id, &request.characteristic_uuid)
+      .await?;
+    gate.pause();
+    Ok(())
+  }
+
+  pub async fn resume_notifications(&self, request: ResumeNotificationsRequest) -> Result<()> {
+    let gate = self
+      .notification_gate(&request.device_id, &request.characteristic_uuid)
+      .await?;
+    let latest = gate.resume();
+    if let (true, Some(value)) = (request.emit_latest, latest) {
+      emit_notification(
+        &self.inner.app,
+        &request.device_id,
+        &request.service_uuid,
+        &request.characteristic_uuid,
+        &value,
+        false,
+      );
+      self
+        .inner
+        .notification_counters
+        .emitted
+        .fetch_add(1, Ordering::Relaxed);
+    }
+    Ok(())
+  }
+
+  /// Polling monitors have no gate; only subscriptions can be paused.
+  async fn notification_gate(&self, device_id: &str, characteristic_uuid: &str) -> Result<Arc<NotificationGate>> {
+    let key = characteristic_key(device_id, characteristic_uuid)?;
+    let active = self.inner.notification_tasks.lock().await.contains_key(&key);
+    let gate = self.inner.notification_gates.lock().await.get(&key).cloned();
+    match gate {
+      Some(gate) if active => Ok(gate),
+      _ => Err(Error::NotificationsNotActive {
+        device_id: device_id.to_string(),
+        characteristic_uuid: characteristic_uuid.to_string(),
+      }),
+    }
+  }
+
+  /// Delivers value updates for a characteristic the best way it supports:
+  /// notifications when available, otherwise periodic reads. Every update is
+  /// emitted as [`EVENT_NOTIFICATION`].
+  pub async fn monitor_characteristic(&self, request: MonitorRequest) -> Result<MonitorInfo> {
+    let queued = self.queue_gatt_operation(&request.device_id).await;
+    self.inner.value_source_started();
+    let (peripheral, characteristic) = self
+      .resolve_characteristic(&request.device_id, &request.service_uuid, &request.characteristic_uuid)
+      .await?;
+    let key = characteristic_key(&request.device_id, &request.characteristic_uuid)?;
+    if self.inner.notification_tasks.lock().await.contains_key(&key) {
+      return Err(Error::NotificationsAlreadyActive {
+        device_id: request.device_id.clone(),
+        characteristic_uuid: request.characteristic_uuid.clone(),
+      });
+    }
+    let can_read = characteristic.properties.contains(CharPropFlags::READ);
+    let can_subscribe = characteristic
+      .properties
+      .intersects(CharPropFlags::NOTIFY | CharPropFlags::INDICATE);
+    if !can_read && !can_subscribe {
+      return Err(Error::InvalidRequest(format!(
+        "Characteristic {} supports neither read nor notify",
+        request.characteristic_uuid
+      )));
+    }
+
+    let app = self.inner.app.clone();
+    let initial_value = if can_read {
+      let bytes = peripheral.read(&characteristic).await?;
+      emit_notification(
+        &app,
+        &request.device_id,
+        &request.service_uuid,
+        &request.characteristic_uuid,
+        &bytes,
+        true,
+      );
+      Some(BluetoothValue {
+        value: BASE64_STANDARD.encode(bytes),
+      })
+    } else {
+      None
+    };
+    // `start_notifications` takes the queue itself.
+    drop(queued);
+
+    if can_subscribe {
+      self
+        .start_notifications(NotificationRequest {
+          device_id: request.device_id,
+          service_uuid: request.service_uuid,
+          characteristic_uuid: request.characteristic_uuid,
+          // A subscription or poll that started since the check above wins.
+          on_duplicate: Some(DuplicateNotificationPolicy::Error),
+        })
+        .await?;
+      return Ok(MonitorInfo {
+        mode: MonitorMode::Notify,
+        initial_value,
+      });
+    }
+
+    let interval = Duration::from_millis(request.poll_interval_ms).max(MIN_MONITOR_POLL_INTERVAL);
+    let device_id = request.device_id.clone();
+    let service_uuid = request.service_uuid.clone();
+    let characteristic_uuid = request.characteristic_uuid.clone();
+    log::info!(
+      "Monitoring by polling | device_id={} | characteristic={} | interval_ms={}",
+      device_id,
+      characteristic_uuid,
+      interval.as_millis()
+    );
+    let gatt_queue = self.inner.gatt_queues.get(&device_id);
+    // The queue was released for the notify path, so check again and insert
+    // under one lock, as `poll_characteristic` does.
+    let mut tasks = self.inner.notification_tasks.lock().await;
+    if tasks.contains_key(&key) {
+      return Err(Error::NotificationsAlreadyActive {
+        device_id,
+        characteristic_uuid,
+      });
+    }
+    let handle = async_runtime::spawn(async move {
+      loop {
+        sleep(interval).await;
+        let read = {
+          let _queued = gatt_queue.lock().await;
+          peripheral.read(&characteristic).await
+        };
+        match read {
+          Ok(bytes) => emit_notification(&app, &device_id, &service_uuid, &characteristic_uuid, &bytes, true),
+          Err(err) => log::warn!(
+            "Polling read failed | device_id={} | characteristic={} | err={:?}",
+            device_id,
+            characteristic_uuid,
+            err
+          ),
+        }
+      }
+    });
+    tasks.insert(key, handle);
+    Ok(MonitorInfo {
+      mode: MonitorMode::Poll,
+      initial_value,
+    })
+  }
+
+  /// Stops updates started by [`Self::monitor_characteristic`], whether they come
+  /// from a subscription or from polling.
+  pub async fn stop_monitoring(&self, request: NotificationRequest) -> Result<()> {
+    let _queued = self.queue_gatt_operation(&request.device_id).await;
+    let (peripheral, characteristic) = self
+      .resolve_characteristic(&request.device_id, &request.service_uuid, &request.characteristic_uuid)
+      .await?;
+    let key = characteristic_key(&request.device_id, &request.characteristic_uuid)?;
+    let handle = self.inner.notification_tasks.lock().await.remove(&key).ok_or(Error::NotificationsNotActive {
+      device_id: request.device_id.clone(),
+      characteristic_uuid: request.characteristic_uuid.clone(),
+    })?;
+    handle.abort();
+    self.inner.notification_gates.lock().await.remove(&key);
+    // Otherwise auto-reconnect would subscribe again to a stopped monitor.
+    self
+      .inner
+      .subscriptions
+      .lock()
+      .unwrap_or_else(|err| err.into_inner())
+      .remove(&key);
+    self
+      .inner
+      .characteristic_stats
+      .lock()
+      .unwrap_or_else(|err| err.into_inner())
+      .remove(&key);
+    if characteristic
+      .properties
+      .intersects(CharPropFlags::NOTIFY | CharPropFlags::INDICATE)
+    {
+      peripheral.unsubscribe(&characteristic).await?;
+    }
+    Ok(())
+  }
+
+  /// Reads a characteristic on a timer and emits each value, or one aggregate
+  /// per window, as [`EVENT_NOTIFICATION`]. Aggregates are carried in
+  /// `interpreted`, with `value` holding the window's last raw read. Polling
+  /// shares the subscription slot for the characteristic and ends when the
+  /// device disconnects.
+  pub async fn poll_characteristic(&self, request: PollRequest) -> Result<()> {
+    let queued = self.queue_gatt_operation(&request.device_id).await;
+    self.inner.value_source_started();
+    let (peripheral, characteristic) = self
+      .resolve_characteristic(&request.device_id, &request.service_uuid, &request.characteristic_uuid)
+      .await?;
+    if !characteristic.properties.contains(CharPropFlags::READ) {
+      return Err(Error::InvalidRequest(format!(
+        "Characteristic {} is not readable",
+        request.characteristic_uuid
+      )));
+    }
+    if let Some(aggregate) = &request.aggregate {
+      if aggregate.window == 0 {
+        return Err(Error::InvalidRequest("Aggregation window must be at least 1".into()));
+      }
+      if matches!(aggregate.format, ValueFormat::Utf8 | ValueFormat::Hex) {
+        return Err(Error::InvalidRequest("Aggregation requires a numeric format".into()));
+      }
+    }
+    drop(queued);
+    let key = characteristic_key(&request.device_id, &request.characteristic_uuid)?;
+    let mut tasks = self.inner.notification_tasks.lock().await;
+    if tasks.contains_key(&key) {
+      return Err(Error::NotificationsAlreadyActive {
+        device_id: request.device_id.clone(),
+        characteristic_uuid: request.characteristic_uuid.clone(),
+      });
+    }
+
+    let app = self.inner.app.clone();
+    let gatt_queue = self.inner.gatt_queues.get(&request.device_id);
+    let interval = Duration::from_millis(request.interval_ms).max(MIN_MONITOR_POLL_INTERVAL);
+    let PollRequest {
+      device_id,
+      service_uuid,
+      characteristic_uuid,
+      aggregate,
+      ..
+    } = request;
+    log::info!(
+      "Polling characteristic | device_id={} | characteristic={} | interval_ms={} | aggregate={:?}",
+      device_id,
+      characteristic_uuid,
+      interval.as_millis(),
+      aggregate.as_ref().map(|aggregate| aggregate.function)
+    );
+    let handle = async_runtime::spawn(async move {
+      let mut samples: Vec<f64> = Vec::new();
+      loop {
+        let read = {
+          let _queued = gatt_queue.lock().await;
+          peripheral.read(&characteristic).await
+        };
+        let bytes = match read {
+          Ok(bytes) => bytes,
+          Err(err) => {
+            log::warn!(
+              "Polling read failed | device_id={} | characteristic={} | err={:?}",
+              device_id,
+              characteristic_uuid,
+              err
+            );
+            sleep(interval).await;
+            continue;
+          }
+        };
+        match &aggregate {
+          None => emit_notification(&app, &device_id, &service_uuid, &characteristic_uuid, &bytes, true),
+          Some(aggregate) => {
+            match interpret(aggregate.format, &bytes).and_then(|value| value.as_f64()) {
+              Some(sample) => samples.push(sample),
+              None => log::debug!("Skipping undecodable sample | characteristic={characteristic_uuid}"),
+            }
+            if samples.len() >= aggregate.window {
+              let value = aggregate_samples(aggregate.function, &samples);
+              samples.clear();
+              emit_notification_with(
+                &app,
+                &device_id,
+                &service_uuid,
+                &characteristic_uuid,
+                &bytes,
+                true,
+                Some(value.into()),
+              );
+            }
+          }
+        }
+        sleep(interval).await;
+      }
+    });
+    tasks.insert(key, handle);
+    Ok(())
+  }
+
+  /// Stops a [`Self::poll_characteristic`] task. Fails with
+  /// `NotificationsNotActive` when the characteristic is not being polled,
+  /// including while it is subscribed to instead.
+  pub async fn stop_polling(&self, request: NotificationRequest) -> Result<()> {
+    let key = characteristic_key(&request.device_id, &request.characteristic_uuid)?;
+    // Subscriptions have a gate, polls do not.
+    let gates = self.inner.notification_gates.lock().await;
+    let mut tasks = self.inner.notification_tasks.lock().await;
+    let handle = if gates.contains_key(&key) { None } else { tasks.remove(&key) };
+    let handle = handle.ok_or(Error::NotificationsNotActive {
+      device_id: request.device_id.clone(),
+      characteristic_uuid: request.characteristic_uuid.clone(),
+    })?;
+    handle.abort();
+    Ok(())
+  }
+
+  /// Device ids `request_device` should hide for the given exclusion.
+  async fn known_device_ids(&self, exclusion: KnownDeviceExclusion) -> Result<HashSet<String>> {
+    let mut known = HashSet::new();
+    match exclusion {
+      KnownDeviceExclusion::None => {}
+      KnownDeviceExclusion::Cached => {
+        known.extend(self.inner.peripherals.read().await.keys().cloned());
+      }
+      KnownDeviceExclusion::Connected => {
+        for peripheral in self.inner.adapter().peripherals().await? {
+          if peripheral.is_connected().await.unwrap_or(false) {
+            known.insert(self.device_key(&peripheral));
+          }
+        }
+      }
+    }
+    if !known.is_empty() {
+      log::info!("Excluding known devices | mode={:?} | count={}", exclusion, known.len());
+    }
+    Ok(known)
+  }
+
+  async fn cached_matches(
+    &self,
+    normalized: &NormalizedRequestDeviceOptions,
+    excluded: &HashSet<String>,
+  ) -> HashMap<String, Peripheral> {
+    let cache = self.inner.peripherals.read().await;
+    let mut cached = Vec::with_capacity(cache.len());
+    for (device_id, peripheral) in cache.iter() {
+      if let Ok(Some(properties)) = peripheral.properties().await {
+        cached.push((device_id.clone(), peripheral.clone(), properties));
+      }
+    }
+    seed_matches(cached, normalized, excluded)
+  }
+
+  /// Totals across all subscriptions since the plugin started.
+  pub async fn get_notification_stats(&self) -> Result<NotificationStats> {
+    let counters = &self.inner.notification_counters;
+    Ok(NotificationStats {
+      emitted: counters.emitted.load(Ordering::Relaxed),
+      dropped: counters.dropped.load(Ordering::Relaxed),
+      queue_capacity: self.inner.notification_queue_capacity,
+      overflow_policy: self.inner.notification_overflow_policy,
+    })
+  }
+
+  /// Records that a frontend listener for value events is (still) alive, or
+  /// gone when `active` is false. Ignored unless `auto_unsubscribe` is set.
+  pub async fn notification_listener_heartbeat(&self, request: ListenerHeartbeatRequest) -> Result<()> {
+    if self.inner.auto_unsubscribe_grace.is_none() {
+      return Ok(());
+    }
+    let mut leases = self.inner.listener_leases.lock().unwrap_or_else(|err| err.into_inner());
+    if request.active {
+      leases.listeners.insert(request.listener_id, Instant::now());
+    } else if leases.listeners.remove(&request.listener_id).is_some() {
+      leases.last_activity = Instant::now();
+    }
+    Ok(())
+  }
+
+  /// Counters for the latest subscription to a characteristic, including one
+  /// stopped with `stop_notifications`. `stop_monitoring` drops them.
+  pub async fn get_characteristic_stats(&self, request: CharacteristicStatsRequest) -> Result<CharacteristicStats> {
+    let key = characteristic_key(&request.device_id, &request.characteristic_uuid)?;
+    let stats = self
+      .inner
+      .characteristic_stats
+      .lock()
+      .unwrap_or_else(|err| err.into_inner())
+      .get(&key)
+      .cloned()
+      .ok_or(Error::NotificationsNotActive {
+        device_id: request.device_id,
+        characteristic_uuid: request.characteristic_uuid,
+      })?;
+    Ok(stats.snapshot())
+  }
+
+  /// Starts watching adapter events if not already running. The listener
+  /// emits `gattserver-disconnected`, cleans up subscriptions on disconnect
+  /// and wakes `await_disconnect`.
+  pub async fn start_event_monitor(&self) -> Result<()> {
+    WebBluetoothState::ensure_event_listener(&self.inner);
+    Ok(())
+  }
+
+  /// Stops the adapter event listener. Until it is restarted, disconnects are
+  /// neither reported nor cleaned up.
+  pub async fn stop_event_monitor(&self) -> Result<()> {
+    self.inner.stop_event_listener();
+    Ok(())
+  }
+
+  /// Internal state for diagnosing support reports. Only available when
+  /// enabled through `PluginConfig::debug_snapshot` (on by default in debug
+  /// builds).
+  pub async fn get_debug_snapshot(&self) -> Result<DebugSnapshot> {
+    if !self.inner.debug_snapshot {
+      return Err(Error::InvalidRequest("Debug snapshots are disabled".into()));
+    }
+    let adapter_info = self.inner.adapter().adapter_info().await.ok();
+
+    let mut cached_device_ids = Vec::new();
+    let mut connected_device_ids = Vec::new();
+    for (device_id, peripheral) in self.inner.peripherals.read().await.iter() {
+      cached_device_ids.push(device_id.clone());
+      if peripheral.is_connected().await.unwrap_or(false) {
+        connected_device_ids.push(device_id.clone());
+      }
+    }
+    let mut discovered_service_device_ids: Vec<String> =
+      self.inner.discovered_services.lock().await.iter().cloned().collect();
+
+    let (mut watcher_keys, mut notification_keys): (Vec<String>, Vec<String>) = self
+      .inner
+      .notification_tasks
+      .lock()
+      .await
+      .keys()
+      .cloned()
+      .partition(|key| is_watcher_key(key));
+    let mut paused_notification_keys: Vec<String> = self
+      .inner
+      .notification_gates
+      .lock()
+      .await
+      .iter()
+      .filter(|(_, gate)| gate.is_paused())
+      .map(|(key, _)| key.clone())
+      .collect();
+    let active_request_id = self.active_request_id();
+
+    for keys in [
+      &mut cached_device_ids,
+      &mut connected_device_ids,
+      &mut discovered_service_device_ids,
+      &mut notification_keys,
+      &mut paused_notification_keys,
+      &mut watcher_keys,
+    ] {
+      keys.sort();
+    }
+    Ok(DebugSnapshot {
+      adapter_index: self.inner.adapter_index.load(Ordering::SeqCst),
+      adapter_info,
+      cached_device_ids,
+      connected_device_ids,
+      discovered_service_device_ids,
+      notification_keys,
+      paused_notification_keys,
+      watcher_keys,
+      scanning: active_request_id.is_some(),
+      active_request_id,
+    })
+  }
+
+  /// Read-only summary of the plugin's background tasks, for checking what
+  /// is still running, e.g. after a teardown.
+  pub async fn list_active_tasks(&self) -> Result<ActiveTasks> {
+    let gates = self.inner.notification_gates.lock().await;
+    let mut tasks = ActiveTasks::default();
+    for key in self.inner.notification_tasks.lock().await.keys() {
+      // Device ids may themselves contain colons (MAC addresses).
+      let Some((device_id, suffix)) = key.rsplit_once(':') else {
+        continue;
+      };
+      let device_id = device_id.to_string();
+      if suffix == RSSI_TASK_SUFFIX {
+        tasks.rssi_watchers.push(device_id);
+      } else if suffix == SERVICE_CHANGED_TASK_SUFFIX {
+        tasks.service_changed_watchers.push(device_id);
+      } else {
+        let gate = gates.get(key);
+        let task = CharacteristicTask {
+          device_id,
+          characteristic_uuid: suffix.to_string(),
+          paused: gate.is_some_and(|gate| gate.is_paused()),
+        };
+        // Only subscriptions register a pause gate.
+        if gate.is_some() {
+          tasks.notifications.push(task);
+        } else {
+          tasks.polling.push(task);
+        }
+      }
+    }
+    drop(gates);
+    tasks.advertisement_watchers = self.inner.advertisement_watchers.lock().await.keys().cloned().collect();
+    tasks.reconnects = self.inner.reconnect_tasks.lock().await.keys().cloned().collect();
+    tasks.event_listener = self
+      .inner
+      .event_listener
+      .lock()
+      .unwrap_or_else(|err| err.into_inner())
+      .is_some();
+    tasks.background_scan = self.inner.background_scan.lock().await.is_some();
+    tasks.active_request_id = self.active_request_id();
+
+    for list in [&mut tasks.notifications, &mut tasks.polling] {
+      list.sort_by(|a, b| (&a.device_id, &a.characteristic_uuid).cmp(&(&b.device_id, &b.characteristic_uuid)));
+    }
+    for ids in [
+      &mut tasks.rssi_watchers,
+      &mut tasks.service_changed_watchers,
+      &mut tasks.advertisement_watchers,
+      &mut tasks.reconnects,
+    ] {
+      ids.sort();
+    }
+    Ok(tasks)
+  }
+
+  /// Waits for the device's earlier GATT operations to finish. Hold the
+  /// guard for the whole operation, including service resolution.
+  async fn queue_gatt_operation(&self, device_id: &str) -> OwnedMutexGuard<()> {
+    self.inner.gatt_queues.lock(device_id).await
+  }
+
+  /// One read through the device's GATT queue, for loops that must not hold
+  /// the queue between reads.
+  async fn queued_read(
+    &self,
+    device_id: &str,
+    peripheral: &Peripheral,
+    characteristic: &Characteristic,
+  ) -> Result<Vec<u8>> {
+    let _queued = self.queue_gatt_operation(device_id).await;
+    Ok(peripheral.read(characteristic).await?)
+  }
+
+  async fn get_or_try_load_peripheral(&self, device_id: &str) -> Result<Peripheral> {
+    let cached = self.inner.peripherals.read().await.get(device_id).cloned();
+    if let Some(peripheral) = cached {
+      self.touch_peripheral(device_id).await;
+      return Ok(peripheral);
+    }
+    let adapter = self.inner.adapter();
+    if let Some(id) = self.inner.device_ids.peripheral_id(device_id) {
+      if let Ok(peripheral) = adapter.peripheral(&id).await {
+        self.cache_peripheral(device_id, peripheral.clone()).await;
+        return Ok(peripheral);
+      }
+    }
+    let peripherals = adapter.peripherals().await?;
+    for peripheral in peripherals {
+      if self.device_key(&peripheral) == device_id {
+        self.cache_peripheral(device_id, peripheral.clone()).await;
+        return Ok(peripheral);
+      }
+    }
+    Err(Error::DeviceNotFound(device_id.to_string()))
+  }
+
+  /// Inserts a peripheral and, when a cap is configured, evicts the least
+  /// recently used disconnected devices until the cache fits. Connected
+  /// devices are never evicted, so the cache may exceed the cap while they
+  /// stay connected.
+  async fn cache_peripheral(&self, device_id: &str, peripheral: Peripheral) {
+    self.inner.device_ids.remember(device_id, peripheral.id());
+    let candidates: Vec<(String, Peripheral)> = {
+      let mut cache = self.inner.peripherals.write().await;
+      cache.insert(device_id.to_string(), peripheral);
+      let Some(cap) = self.inner.max_cached_devices else {
+        return;
+      };
+      let mut recency = self.inner.peripheral_recency.lock().await;
+      recency.retain(|id| id != device_id);
+      recency.push_back(device_id.to_string());
+      if cache.len() <= cap {
+        return;
+      }
+      cache
+        .iter()
+        .filter(|(id, _)| id.as_str() != device_id)
+        .map(|(id, peripheral)| (id.clone(), peripheral.clone()))
+        .collect()
+    };
+    // Querying the connection state can be slow, so it runs without the
+    // cache lock; every other device lookup would wait on it otherwise.
+    // Devices cached meanwhile were not checked, so they count as connected.
+    let mut disconnected = HashSet::new();
+    for (id, peripheral) in candidates {
+      if !peripheral.is_connected().await.unwrap_or(false) {
+        disconnected.insert(id);
+      }
+    }
+
+    let Some(cap) = self.inner.max_cached_devices else {
+      return;
+    };
+    let mut cache = self.inner.peripherals.write().await;
+    if cache.len() <= cap {
+      return;
+    }
+    let mut recency = self.inner.peripheral_recency.lock().await;
+    let evicted = lru_evictions(
+      &mut recency,
+      cache.len() - cap,
+      |id| cache.contains_key(id),
+      |id| !disconnected.contains(id),
+    );
+    for candidate in evicted {
+      if cache.remove(&candidate).is_some() {
+        self.inner.discovered_services.lock().await.remove(&candidate);
+        let granted = self
+          .inner
+          .granted_devices
+          .lock()
+          .unwrap_or_else(|err| err.into_inner())
+          .contains_key(&candidate);
+        if !granted {
+          self.inner.device_ids.forget(&candidate);
+        }
+        log::debug!("Evicted cached peripheral | device_id={}", candidate);
+      }
+    }
+  }
+
+  async fn touch_peripheral(&self, device_id: &str) {
+    if self.inner.max_cached_devices.is_none() {
+      return;
+    }
+    let mut recency = self.inner.peripheral_recency.lock().await;
+    if let Some(position) = recency.iter().position(|id| id == device_id) {
+      if let Some(id) = recency.remove(position) {
+        recency.push_back(id);
+      }
+    }
+  }
+
+  async fn discover_services(&self, device_id: &str, peripheral: &Peripheral) -> Result<()> {
+    peripheral.discover_services().await?;
+    self
+      .inner
+      .discovered_services
+      .lock()
+      .await
+      .insert(device_id.to_string());
+    Ok(())
+  }
+
+  /// Like `discover_services`, but keeps whatever the backend managed to
+  /// enumerate when discovery fails part-way. Only a failure that leaves no
+  /// services at all is returned as an error.
+  async fn discover_services_tolerant(&self, device_id: &str, peripheral: &Peripheral) -> Result<Vec<DiscoveryError>> {
+    let discovery_errors = match peripheral.discover_services().await {
+      Ok(()) => Vec::new(),
+      Err(err) if peripheral.services().is_empty() => return Err(err.into()),
+      Err(err) => {
+        log::warn!("Service discovery incomplete | device_id={} | err={:?}", device_id, err);
+        vec![DiscoveryError {
+          uuid: None,
+          message: Error::from(err).to_string(),
+        }]
+      }
+    };
+    self
+      .inner
+      .discovered_services
+      .lock()
+      .await
+      .insert(device_id.to_string());
+    Ok(discovery_errors)
+  }
+
+  async fn ensure_services_discovered(&self, device_id: &str, peripheral: &Peripheral) -> Result<()> {
+    if self.inner.discovered_services.lock().await.contains(device_id) {
+      return Ok(());
+    }
+    self.discover_services(device_id, peripheral).await
+  }
+
+  /// Subscribes to Service Changed indications so the cached attribute table is
+  /// invalidated whenever the peripheral reports a change. `connect_gatt` does
+  /// this automatically; the command is for re-arming after an unwatch.
+  pub async fn watch_service_changed(&self, request: DeviceRequest) -> Result<()> {
+    let _queued = self.queue_gatt_operation(&request.device_id).await;
+    let peripheral = self.get_or_try_load_peripheral(&request.device_id).await?;
+    self.ensure_services_discovered(&request.device_id, &peripheral).await?;
+    self.start_service_changed_watch(&request.device_id, &peripheral).await
+  }
+
+  pub async fn unwatch_service_changed(&self, request: DeviceRequest) -> Result<()> {
+    let _queued = self.queue_gatt_operation(&request.device_id).await;
+    let peripheral = self.get_or_try_load_peripheral(&request.device_id).await?;
+    let key = notification_key(&request.device_id, SERVICE_CHANGED_TASK_SUFFIX);
+    let handle = self
+      .inner
+      .notification_tasks
+      .lock()
+      .await
+      .remove(&key)
+      .ok_or_else(|| Error::NotificationsNotActive {
+        device_id: request.device_id.clone(),
+        characteristic_uuid: format_uuid(&SERVICE_CHANGED_CHARACTERISTIC_UUID),
+      })?;
+    handle.abort();
+    if let Some(characteristic) = service_changed_characteristic(&peripheral) {
+      peripheral.unsubscribe(&characteristic).await?;
+    }
+    Ok(())
+  }
+
+  /// Emits [`EVENT_ADVERTISEMENT_RECEIVED`] whenever the adapter reports new
+  /// advertisement data for the device, like Web Bluetooth's
+  /// `watchAdvertisements()`. Keeps the adapter scanning until the last
+  /// watcher is removed with `unwatch_advertisements`.
+  pub async fn watch_advertisements(&self, request: DeviceRequest) -> Result<()> {
+    let peripheral = self.get_or_try_load_peripheral(&request.device_id).await?;
+    let background_scan = self.inner.background_scan.lock().await;
+    let mut watchers = self.inner.advertisement_watchers.lock().await;
+    if watchers.contains_key(&request.device_id) {
+      return Ok(());
+    }
+    let mut events = self.inner.adapter().events().await?;
+    if watchers.is_empty() && self.active_request_id().is_none() && background_scan.is_none() {
+      self.inner.adapter().start_scan(ScanFilter::default()).await?;
+    }
+    let app = self.inner.app.clone();
+    let device_id = request.device_id.clone();
+    let peripheral_id = peripheral.id();
+    let max_data_len = self.inner.max_advertisement_data_len;
+    let handle = async_runtime::spawn(async move {
+      while let Some(event) = events.next().await {
+        // Every backend reports a received advertisement as an update of the
+        // device's properties, so the data-specific events add nothing.
+        let (CentralEvent::DeviceDiscovered(id) | CentralEvent::DeviceUpdated(id)) = event else {
+          continue;
+        };
+        if id != peripheral_id {
+          continue;
+        }
+        if let Ok(Some(properties)) = peripheral.properties().await {
+          let payload = AdvertisementReceivedPayload {
+            device_id: device_id.clone(),
+            advertisement: advertisement_snapshot(&properties, max_data_len),
+          };
+          emit_device_event(&app, EventKind::AdvertisementReceived, &device_id, &payload);
+        }
+      }
+    });
+    watchers.insert(request.device_id, handle);
+    Ok(())
+  }
+
+  /// Stops [`Self::watch_advertisements`]; does nothing if the device is not
+  /// watched.
+  pub async fn unwatch_advertisements(&self, request: DeviceRequest) -> Result<()> {
+    self.remove_advertisement_watcher(&request.device_id).await;
+    Ok(())
+  }
+
+  /// Stops the device's advertisement watcher, and the scan once no other
+  /// watcher, picker or background scan needs it.
+  async fn remove_advertisement_watcher(&self, device_id: &str) {
+    let background_scan = self.inner.background_scan.lock().await;
+    let mut watchers = self.inner.advertisement_watchers.lock().await;
+    if let Some(handle) = watchers.remove(device_id) {
+      handle.abort();
+      if watchers.is_empty() && self.active_request_id().is_none() && background_scan.is_none() {
+        self.inner.adapter().stop_scan().await.ok();
+      }
+    }
+  }
+
+  /// Keeps the adapter scanning in the background and emits
+  /// [`EVENT_DEVICE_DISCOVERED`] for every newly matched device and
+  /// [`EVENT_DEVICE_UPDATED`] whenever a matched device's properties change,
+  /// without showing a picker. `options` are matched like `request_device`'s;
+  /// `scan_timeout_ms` is ignored, the scan runs until `stop_scan`. Calling it
+  /// again replaces the running scan.
+  pub async fn start_scan(&self, options: RequestDeviceOptions) -> Result<()> {
+    let adapter = self.inner.adapter();
+    ensure_powered(adapter.adapter_state().await)?;
+    let normalized = NormalizedRequestDeviceOptions::try_from(options)?;
+    let excluded = self.known_device_ids(normalized.exclude_known_devices).await?;
+    let mut background_scan = self.inner.background_scan.lock().await;
+    if let Some(previous) = background_scan.take() {
+      previous.abort();
+    }
+    let mut events = adapter.events().await?;
+    let exclusive = self.inner.advertisement_watchers.lock().await.is_empty() && self.active_request_id().is_none();
+    let scan_filter = if exclusive {
+      normalized.scan_filter()
+    } else {
+      ScanFilter::default()
+    };
+    adapter.start_scan(scan_filter).await?;
+    log::info!(
+      "Background scan started | accept_all_devices={} | filter_count={}",
+      normalized.accept_all_devices,
+      normalized.filters.len()
+    );
+    let state = Arc::downgrade(&self.inner);
+    let handle = async_runtime::spawn(async move {
+      let mut seen = HashSet::new();
+      let mut pending: Vec<Peripheral> = adapter.peripherals().await.unwrap_or_default();
+      loop {
+        let Some(state) = state.upgrade() else {
+          return;
+        };
+        let bluetooth = WebBluetooth { inner: state };
+        for peripheral in pending.drain(..) {
+          let Ok(Some(properties)) = peripheral.properties().await else {
+            continue;
+          };
+          if !normalized.matches(&properties) {
+            continue;
+          }
+          let device_id = bluetooth.device_key(&peripheral);
+          if excluded.contains(&device_id) {
+            continue;
+          }
+          let Ok(device) = bluetooth.describe_device(&peripheral).await else {
+            continue;
+          };
+          let kind = if seen.insert(device_id.clone()) {
+            EventKind::DeviceDiscovered
+          } else {
+            EventKind::DeviceUpdated
+          };
+          emit_device_event(&bluetooth.inner.app, kind, &device_id, &device);
+        }
+        drop(bluetooth);
+        let Some(event) = events.next().await else {
+          return;
+        };
+        let (CentralEvent::DeviceDiscovered(id) | CentralEvent::DeviceUpdated(id)) = event else {
+          continue;
+        };
+        if let Ok(peripheral) = adapter.peripheral(&id).await {
+          pending.push(peripheral);
         }
       }
     });
+    *background_scan = Some(handle);
+    Ok(())
+  }
+
+  /// Stops [`Self::start_scan`]; does nothing if no background scan is
+  /// running. The adapter keeps scanning while advertisement watchers or a
+  /// `request_device` call still need it.
+  pub async fn stop_scan(&self) -> Result<()> {
+    let mut background_scan = self.inner.background_scan.lock().await;
+    if let Some(handle) = background_scan.take() {
+      handle.abort();
+      log::info!("Background scan stopped");
+      if self.inner.advertisement_watchers.lock().await.is_empty() && self.active_request_id().is_none() {
+        self.inner.adapter().stop_scan().await.ok();
+      }
+    }
+    Ok(())
+  }
+
+  /// Ends a `request_device` scan unless advertisement watchers or a
+  /// background scan still need it.
+  async fn finish_scan(&self) {
+    if !self.scan_shared().await {
+      self.inner.adapter().stop_scan().await.ok();
+    }
+  }
+
+  /// Whether advertisement watchers or a background scan rely on the
+  /// adapter scanning without a filter.
+  async fn scan_shared(&self) -> bool {
+    let background_scan = self.inner.background_scan.lock().await;
+    background_scan.is_some() || !self.inner.advertisement_watchers.lock().await.is_empty()
+  }
+
+  fn active_request_id(&self) -> Option<String> {
     self
       .inner
-      .notification_tasks
+      .active_request
       .lock()
-      .await
-      .insert(key, handle);
+      .unwrap_or_else(|err| err.into_inner())
+      .clone()
+  }
+
+  /// Samples the device's RSSI every `interval_ms` and emits
+  /// [`EVENT_RSSI_CHANGED`] with the first value and whenever it moves by at
+  /// least `threshold` dBm. btleplug cannot read the RSSI of a live
+  /// connection, so the value is the one from the last advertisement the
+  /// adapter received; it only changes while the adapter is scanning and the
+  /// device advertises. Watching ends on disconnect or `unwatch_rssi`.
+  pub async fn watch_rssi(&self, request: WatchRssiRequest) -> Result<()> {
+    let peripheral = self.get_or_try_load_peripheral(&request.device_id).await?;
+    let key = notification_key(&request.device_id, RSSI_TASK_SUFFIX);
+    let mut tasks = self.inner.notification_tasks.lock().await;
+    if let Some(previous) = tasks.remove(&key) {
+      previous.abort();
+    }
+    let app = self.inner.app.clone();
+    let device_id = request.device_id.clone();
+    let threshold = i32::from(request.threshold);
+    let interval = Duration::from_millis(request.interval_ms).max(MIN_MONITOR_POLL_INTERVAL);
+    let handle = async_runtime::spawn(async move {
+      let mut last: Option<i16> = None;
+      loop {
+        let rssi = peripheral
+          .properties()
+          .await
+          .ok()
+          .flatten()
+          .and_then(|properties| properties.rssi);
+        if let Some(rssi) = rssi {
+          let changed = last.map_or(true, |last| (i32::from(rssi) - i32::from(last)).abs() >= threshold);
+          if changed {
+            last = Some(rssi);
+            let payload = RssiChangedPayload {
+              device_id: device_id.clone(),
+              rssi,
+            };
+            emit_device_event(&app, EventKind::RssiChanged, &device_id, &payload);
+          }
+        }
+        sleep(interval).await;
+      }
+    });
+    tasks.insert(key, handle);
     Ok(())
   }
 
-  pub async fn stop_notifications(&self, request: NotificationRequest) -> Result<()> {
-    let (peripheral, characteristic) = self
-      .resolve_characteristic(&request.device_id, &request.service_uuid, &request.characteristic_uuid)
-      .await?;
-    let key = notification_key(&request.device_id, &request.characteristic_uuid);
-    let handle = self.inner.notification_tasks.lock().await.remove(&key).ok_or(Error::NotificationsNotActive {
-      device_id: request.device_id.clone(),
-      characteristic_uuid: request.characteristic_uuid.clone(),
-    })?;
-    handle.abort();
-    peripheral.unsubscribe(&characteristic).await?;
+  /// Stops [`Self::watch_rssi`]; does nothing if the device is not watched.
+  pub async fn unwatch_rssi(&self, request: DeviceRequest) -> Result<()> {
+    let key = notification_key(&request.device_id, RSSI_TASK_SUFFIX);
+    if let Some(handle) = self.inner.notification_tasks.lock().await.remove(&key) {
+      handle.abort();
+    }
     Ok(())
   }
 
-  async fn get_or_try_load_peripheral(&self, device_id: &str) -> Result<Peripheral> {
-    if let Some(peripheral) = self.inner.peripherals.read().await.get(device_id) {
-      return Ok(peripheral.clone());
+  /// Callers hold the device's GATT queue.
+  async fn start_service_changed_watch(&self, device_id: &str, peripheral: &Peripheral) -> Result<()> {
+    let key = notification_key(device_id, SERVICE_CHANGED_TASK_SUFFIX);
+    if self.inner.notification_tasks.lock().await.contains_key(&key) {
+      return Ok(());
     }
-    let adapter = self.inner.adapter.clone();
-    let peripherals = adapter.peripherals().await?;
-    for peripheral in peripherals {
-      if peripheral_key(&peripheral) == device_id {
-        let mut cache = self.inner.peripherals.write().await;
-        cache.insert(device_id.to_string(), peripheral.clone());
-        return Ok(peripheral);
+    let characteristic = service_changed_characteristic(peripheral).ok_or_else(|| Error::CharacteristicNotFound {
+      device_id: device_id.to_string(),
+      characteristic_uuid: format_uuid(&SERVICE_CHANGED_CHARACTERISTIC_UUID),
+    })?;
+    let mut stream = peripheral.notifications().await?;
+    peripheral.subscribe(&characteristic).await?;
+    let app = self.inner.app.clone();
+    let discovered = self.inner.discovered_services.clone();
+    let device_id_owned = device_id.to_string();
+    let handle = async_runtime::spawn(async move {
+      while let Some(notification) = stream.next().await {
+        if notification.uuid == SERVICE_CHANGED_CHARACTERISTIC_UUID {
+          log::info!("Service Changed indication received | device_id={device_id_owned}");
+          discovered.lock().await.remove(&device_id_owned);
+          let handle_at = |offset: usize| {
+            notification
+              .value
+              .get(offset..offset + 2)
+              .map(|bytes| u16::from_le_bytes([bytes[0], bytes[1]]))
+          };
+          let payload = ServiceChangedPayload {
+            device_id: device_id_owned.clone(),
+            start_handle: handle_at(0),
+            end_handle: handle_at(2),
+          };
+          emit_device_event(&app, EventKind::ServiceChanged, &device_id_owned, &payload);
+        }
       }
-    }
-    Err(Error::DeviceNotFound(device_id.to_string()))
+    });
+    self.inner.notification_tasks.lock().await.insert(key, handle);
+    Ok(())
+  }
+
+  fn device_key(&self, peripheral: &Peripheral) -> String {
+    self.inner.device_ids.key(peripheral)
   }
 
   async fn describe_device(&self, peripheral: &Peripheral) -> Result<BluetoothDevice> {
     let properties = peripheral.properties().await?;
     let connected = peripheral.is_connected().await.unwrap_or(false);
+    let device_id = self.device_key(peripheral);
+    let appearance = self
+      .inner
+      .appearances
+      .lock()
+      .await
+      .get(&device_id)
+      .copied()
+      .map(describe_appearance);
+    let watching_advertisements = self.inner.advertisement_watchers.lock().await.contains_key(&device_id);
+    let manufacturer_name = if self.inner.manufacturer_names {
+      properties
+        .as_ref()
+        .and_then(|p| p.manufacturer_data.keys().min().copied())
+        .and_then(company_name)
+        .map(str::to_string)
+    } else {
+      None
+    };
+    let mut truncated = false;
+    let manufacturer_data = properties
+      .as_ref()
+      .map(|p| {
+        p.manufacturer_data
+          .iter()
+          .map(|(company, data)| {
+            let encoded = encode_advertisement_data(data, self.inner.max_advertisement_data_len, &mut truncated);
+            (*company, encoded)
+          })
+          .collect()
+      })
+      .unwrap_or_default();
     Ok(BluetoothDevice {
-      id: peripheral_key(peripheral),
+      id: device_id,
       name: properties.as_ref().and_then(|p| p.local_name.clone()),
       uuids: properties
         .as_ref()
         .map(|p| p.services.iter().map(format_uuid).collect())
         .unwrap_or_default(),
-      watching_advertisements: false,
+      watching_advertisements,
       connected,
+      appearance,
+      manufacturer_name,
+      rssi: properties.as_ref().and_then(|p| p.rssi),
+      tx_power: properties.as_ref().and_then(|p| p.tx_power_level),
+      manufacturer_data,
+      truncated,
+      paired: false,
     })
   }
 
   async fn describe_gatt_server(&self, device_id: &str, peripheral: &Peripheral) -> Result<GattServerInfo> {
-    let services = peripheral.services().into_iter().map(service_to_model).collect();
+    let allowed = self.allowed_services(device_id).await;
+    let services = peripheral
+      .services()
+      .into_iter()
+      .filter(|service| service_allowed(allowed.as_ref(), &service.uuid))
+      .map(service_to_model)
+      .collect();
     Ok(GattServerInfo {
       device_id: device_id.to_string(),
       connected: peripheral.is_connected().await.unwrap_or(false),
       services,
+      partial: false,
+      discovery_errors: Vec::new(),
     })
   }
 
@@ -1098,8 +4923,9 @@ impl<R: Runtime> WebBluetooth<R> {
     characteristic_uuid: &str,
   ) -> Result<(Peripheral, Characteristic)> {
     let peripheral = self.get_or_try_load_peripheral(device_id).await?;
-    peripheral.discover_services().await?;
+    self.ensure_services_discovered(device_id, &peripheral).await?;
     let target_service = parse_uuid(service_uuid)?;
+    self.check_service_allowed(device_id, &target_service).await?;
     let services = peripheral.services();
     let service = services
       .into_iter()
@@ -1119,64 +4945,1083 @@ impl<R: Runtime> WebBluetooth<R> {
       })?;
     Ok((peripheral, characteristic))
   }
+
+  async fn allowed_services(&self, device_id: &str) -> Option<HashSet<Uuid>> {
+    self.inner.allowed_services.lock().await.get(device_id).cloned()
+  }
+
+  async fn check_service_allowed(&self, device_id: &str, service_uuid: &Uuid) -> Result<()> {
+    ensure_service_allowed(self.inner.allowed_services.lock().await.get(device_id), device_id, service_uuid)
+  }
+
+  async fn resolve_descriptor(
+    &self,
+    device_id: &str,
+    service_uuid: &str,
+    characteristic_uuid: &str,
+    descriptor_uuid: &str,
+  ) -> Result<(Peripheral, Descriptor)> {
+    let (peripheral, characteristic) = self
+      .resolve_characteristic(device_id, service_uuid, characteristic_uuid)
+      .await?;
+    let target = parse_uuid(descriptor_uuid)?;
+    let descriptor = characteristic
+      .descriptors
+      .into_iter()
+      .find(|descriptor| descriptor.uuid == target)
+      .ok_or_else(|| Error::DescriptorNotFound {
+        device_id: device_id.to_string(),
+        descriptor_uuid: descriptor_uuid.to_string(),
+      })?;
+    Ok((peripheral, descriptor))
+  }
+}
+
+impl<R: Runtime> WebBluetoothState<R> {
+  fn adapter(&self) -> Adapter {
+    self.adapter.read().unwrap_or_else(|err| err.into_inner()).clone()
+  }
+
+  /// Starts the adapter event listener unless one is already running. A
+  /// listener whose event stream ended is replaced.
+  fn ensure_event_listener(state: &Arc<Self>) {
+    let mut listener = state.event_listener.lock().unwrap_or_else(|err| err.into_inner());
+    if listener.as_ref().map_or(true, |handle| handle.inner().is_finished()) {
+      *listener = Some(Self::spawn_event_listener(state));
+    }
+  }
+
+  /// Periodically tears down value event sources once no frontend listener
+  /// has been alive for `grace`. Holds only a weak reference to the state.
+  fn spawn_listener_reaper(state: &Arc<Self>, grace: Duration) {
+    let state = Arc::downgrade(state);
+    let tick = (grace / 2).clamp(Duration::from_millis(500), Duration::from_secs(5));
+    async_runtime::spawn(async move {
+      loop {
+        sleep(tick).await;
+        let Some(state) = state.upgrade() else {
+          return;
+        };
+        if state.listeners_gone(grace) {
+          state.stop_value_sources().await;
+        }
+      }
+    });
+  }
+
+  /// Polls the adapter, since btleplug reports neither adapters coming and
+  /// going nor power changes on every backend, and emits
+  /// [`EVENT_AVAILABILITY_CHANGED`] when the availability flips.
+  fn spawn_availability_watcher(state: &Arc<Self>) {
+    let state = Arc::downgrade(state);
+    async_runtime::spawn(async move {
+      let mut available = None;
+      loop {
+        let Some(state) = state.upgrade() else {
+          return;
+        };
+        let now_available = state.availability().await.unwrap_or(false);
+        if available.is_some_and(|available| available != now_available) {
+          log::info!("Bluetooth availability changed | available={now_available}");
+          let payload = AvailabilityChangedPayload { value: now_available };
+          let _ = state.app.emit(EVENT_AVAILABILITY_CHANGED, payload);
+        }
+        available = Some(now_available);
+        drop(state);
+        sleep(AVAILABILITY_POLL_INTERVAL).await;
+      }
+    });
+  }
+
+  async fn availability(&self) -> Result<bool> {
+    let present = self.manager.adapters().await?.len() > self.adapter_index.load(Ordering::SeqCst);
+    Ok(present && ensure_powered(self.adapter().adapter_state().await).is_ok())
+  }
+
+  /// Restarts the grace period, so a value source started while no listener
+  /// is alive is not torn down before one attaches.
+  fn value_source_started(&self) {
+    self.listener_leases.lock().unwrap_or_else(|err| err.into_inner()).last_activity = Instant::now();
+  }
+
+  fn listeners_gone(&self, grace: Duration) -> bool {
+    let mut leases = self.listener_leases.lock().unwrap_or_else(|err| err.into_inner());
+    let now = Instant::now();
+    let mut expired = None;
+    leases.listeners.retain(|_, seen| {
+      let alive = now.duration_since(*seen) < LISTENER_HEARTBEAT_TIMEOUT;
+      if !alive {
+        expired = expired.max(Some(*seen));
+      }
+      alive
+    });
+    if let Some(seen) = expired {
+      leases.last_activity = leases.last_activity.max(seen);
+    }
+    leases.listeners.is_empty() && now.duration_since(leases.last_activity) >= grace
+  }
+
+  /// Stops every task emitting [`EVENT_NOTIFICATION`] and unsubscribes on the
+  /// device where needed. Watchers for other events are left running.
+  async fn stop_value_sources(&self) {
+    let stopped: Vec<String> = {
+      let mut tasks = self.notification_tasks.lock().await;
+      let keys: Vec<String> = tasks.keys().filter(|key| !is_watcher_key(key)).cloned().collect();
+      for key in &keys {
+        if let Some(handle) = tasks.remove(key) {
+          handle.abort();
+        }
+      }
+      keys
+    };
+    if stopped.is_empty() {
+      return;
+    }
+    let mut unsubscribes = Vec::new();
+    {
+      let mut gates = self.notification_gates.lock().await;
+      let peripherals = self.peripherals.read().await;
+      for key in stopped {
+        gates.remove(&key);
+        self
+          .subscriptions
+          .lock()
+          .unwrap_or_else(|err| err.into_inner())
+          .remove(&key);
+        let Some((device_id, characteristic_uuid)) = key.rsplit_once(':') else {
+          continue;
+        };
+        log::info!(
+          "No value listeners left, stopping | device_id={} | characteristic={}",
+          device_id,
+          characteristic_uuid
+        );
+        let (Some(peripheral), Ok(uuid)) = (peripherals.get(device_id), parse_uuid(characteristic_uuid)) else {
+          continue;
+        };
+        let characteristic = peripheral.characteristics().into_iter().find(|characteristic| {
+          characteristic.uuid == uuid
+            && characteristic.properties.intersects(CharPropFlags::NOTIFY | CharPropFlags::INDICATE)
+        });
+        if let Some(characteristic) = characteristic {
+          unsubscribes.push((device_id.to_string(), peripheral.clone(), characteristic));
+        }
+      }
+    }
+    // Outside the maps' locks: a queued operation may be waiting on them.
+    for (device_id, peripheral, characteristic) in unsubscribes {
+      let _queued = self.gatt_queues.lock(&device_id).await;
+      peripheral.unsubscribe(&characteristic).await.ok();
+    }
+  }
+
+  fn stop_event_listener(&self) {
+    let listener = self
+      .event_listener
+      .lock()
+      .unwrap_or_else(|err| err.into_inner())
+      .take();
+    if let Some(handle) = listener {
+      handle.abort();
+    }
+  }
+
+  fn spawn_event_listener(state: &Arc<Self>) -> JoinHandle<()> {
+    let adapter = state.adapter();
+    let app = state.app.clone();
+    let notifications = state.notification_tasks.clone();
+    let gates = state.notification_gates.clone();
+    let discovered = state.discovered_services.clone();
+    let mtus = state.mtus.clone();
+    let disconnect_waiters = state.disconnect_waiters.clone();
+    let device_ids = state.device_ids.clone();
+    let state = Arc::downgrade(state);
+    async_runtime::spawn(async move {
+      let events = adapter.events().await;
+      let mut events = match events {
+        Ok(stream) => stream,
+        Err(err) => {
+          log::error!("Failed to subscribe to Bluetooth adapter events: {err}");
+          return;
+        }
+      };
+      while let Some(event) = events.next().await {
+        if let CentralEvent::DeviceDisconnected(id) = event {
+          if let Ok(peripheral) = adapter.peripheral(&id).await {
+            let device_id = device_ids.key(&peripheral);
+            clear_notifications_for(&notifications, &device_id).await;
+            let prefix = notification_key(&device_id, "");
+            gates.lock().await.retain(|key, _| !key.starts_with(&prefix));
+            discovered.lock().await.remove(&device_id);
+            mtus.lock().await.remove(&device_id);
+            if let Some(waiters) = disconnect_waiters.lock().await.remove(&device_id) {
+              for waiter in waiters {
+                let _ = waiter.send(());
+              }
+            }
+            let payload = DeviceEventPayload {
+              device_id: device_id.clone(),
+            };
+            emit_device_event(&app, EventKind::GattServerDisconnected, &device_id, &payload);
+            if let Some(state) = state.upgrade() {
+              Self::schedule_reconnect(&state, peripheral, device_id).await;
+            }
+          }
+        }
+      }
+    })
+  }
+
+  /// Starts a reconnect loop for a device that dropped, if auto-reconnect is
+  /// enabled and the device was connected through `connect_gatt`. Otherwise
+  /// its subscriptions are forgotten.
+  async fn schedule_reconnect(state: &Arc<Self>, peripheral: Peripheral, device_id: String) {
+    let wanted = state
+      .reconnect_devices
+      .lock()
+      .unwrap_or_else(|err| err.into_inner())
+      .contains(&device_id);
+    let Some(policy) = state.reconnect_policy.filter(|_| wanted) else {
+      state.forget_subscriptions(&device_id);
+      return;
+    };
+    log::info!("Scheduling reconnect | device_id={device_id}");
+    let mut tasks = state.reconnect_tasks.lock().await;
+    let handle = async_runtime::spawn(Self::reconnect(
+      Arc::downgrade(state),
+      peripheral,
+      device_id.clone(),
+      policy,
+    ));
+    if let Some(previous) = tasks.insert(device_id, handle) {
+      previous.abort();
+    }
+  }
+
+  /// Retries `connect` with exponential backoff, then restores the device's
+  /// subscriptions and Service Changed watch. Gives up quietly once the
+  /// plugin state is dropped.
+  async fn reconnect(state: Weak<Self>, peripheral: Peripheral, device_id: String, policy: ReconnectPolicy) {
+    let mut delay = policy.initial_delay;
+    for attempt in 1..=policy.max_attempts {
+      sleep(delay).await;
+      delay = (delay * 2).min(policy.max_delay);
+      if state.upgrade().is_none() {
+        return;
+      }
+      let connected = peripheral.is_connected().await.unwrap_or(false) || peripheral.connect().await.is_ok();
+      let Some(state) = state.upgrade() else {
+        return;
+      };
+      if !connected {
+        log::debug!("Reconnect attempt failed | device_id={} | attempt={}", device_id, attempt);
+        continue;
+      }
+      state.reconnect_tasks.lock().await.remove(&device_id);
+      log::info!("Reconnected | device_id={} | attempt={}", device_id, attempt);
+      let requests: Vec<NotificationRequest> = state
+        .subscriptions
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+        .values()
+        .filter(|request| request.device_id == device_id)
+        .cloned()
+        .collect();
+      let bluetooth = WebBluetooth { inner: state.clone() };
+      let queued = bluetooth.queue_gatt_operation(&device_id).await;
+      if let Err(err) = bluetooth.start_service_changed_watch(&device_id, &peripheral).await {
+        log::debug!("Service Changed not watched | device_id={} | err={:?}", device_id, err);
+      }
+      drop(queued);
+      for request in requests {
+        let characteristic_uuid = request.characteristic_uuid.clone();
+        let request = NotificationRequest {
+          on_duplicate: Some(DuplicateNotificationPolicy::Ignore),
+          ..request
+        };
+        if let Err(err) = bluetooth.start_notifications(request).await {
+          log::warn!(
+            "Failed to restore notifications | device_id={} | characteristic={} | err={:?}",
+            device_id,
+            characteristic_uuid,
+            err
+          );
+        }
+      }
+      let payload = DeviceEventPayload {
+        device_id: device_id.clone(),
+      };
+      emit_device_event(&state.app, EventKind::GattServerConnected, &device_id, &payload);
+      return;
+    }
+    let Some(state) = state.upgrade() else {
+      return;
+    };
+    log::warn!("Giving up reconnecting | device_id={} | attempts={}", device_id, policy.max_attempts);
+    state.reconnect_tasks.lock().await.remove(&device_id);
+    state
+      .reconnect_devices
+      .lock()
+      .unwrap_or_else(|err| err.into_inner())
+      .remove(&device_id);
+    state.forget_subscriptions(&device_id);
+    let payload = ReconnectFailedPayload {
+      device_id: device_id.clone(),
+      attempts: policy.max_attempts,
+    };
+    emit_device_event(&state.app, EventKind::ReconnectFailed, &device_id, &payload);
+  }
+
+  /// Stops any reconnect loop for the device and keeps it from being
+  /// reconnected later, for disconnects the app asked for.
+  async fn cancel_reconnect(&self, device_id: &str) {
+    self
+      .reconnect_devices
+      .lock()
+      .unwrap_or_else(|err| err.into_inner())
+      .remove(device_id);
+    if let Some(handle) = self.reconnect_tasks.lock().await.remove(device_id) {
+      handle.abort();
+    }
+    self.forget_subscriptions(device_id);
+  }
+
+  fn forget_subscriptions(&self, device_id: &str) {
+    self
+      .subscriptions
+      .lock()
+      .unwrap_or_else(|err| err.into_inner())
+      .retain(|_, request| request.device_id != device_id);
+  }
+}
+
+/// Background connection to the strongest candidate while the user is still
+/// choosing. Unless promoted, dropping it aborts the attempt and disconnects,
+/// which covers every early return from `request_device`.
+struct PrewarmedConnection {
+  device_id: String,
+  peripheral: Peripheral,
+  task: JoinHandle<()>,
+  promoted: bool,
+}
+
+impl PrewarmedConnection {
+  fn start(device_id: String, peripheral: Peripheral) -> Self {
+    log::info!("Pre-warming connection | device_id={device_id}");
+    let connecting = peripheral.clone();
+    let label = device_id.clone();
+    let task = async_runtime::spawn(async move {
+      if let Err(err) = connecting.connect().await {
+        log::debug!("Pre-warm connect failed | device_id={} | err={:?}", label, err);
+      }
+    });
+    Self {
+      device_id,
+      peripheral,
+      task,
+      promoted: false,
+    }
+  }
+
+  /// Keeps the connection when the user picked the pre-warmed device.
+  fn promote_if(&mut self, selected_ids: &[String]) {
+    self.promoted = selected_ids.contains(&self.device_id);
+  }
+}
+
+impl Drop for PrewarmedConnection {
+  fn drop(&mut self) {
+    if self.promoted {
+      return;
+    }
+    self.task.abort();
+    let peripheral = self.peripheral.clone();
+    let device_id = self.device_id.clone();
+    async_runtime::spawn(async move {
+      if peripheral.is_connected().await.unwrap_or(false) {
+        log::info!("Dropping pre-warmed connection | device_id={device_id}");
+        let _ = peripheral.disconnect().await;
+      }
+    });
+  }
+}
+
+/// Records the in-flight `request_device` call and clears it on every exit path.
+struct ActiveRequestGuard {
+  slot: Arc<StdMutex<Option<String>>>,
+}
+
+impl ActiveRequestGuard {
+  fn new(slot: Arc<StdMutex<Option<String>>>, request_id: &str) -> Self {
+    *slot.lock().unwrap_or_else(|err| err.into_inner()) = Some(request_id.to_string());
+    Self { slot }
+  }
+}
+
+impl Drop for ActiveRequestGuard {
+  fn drop(&mut self) {
+    self.slot.lock().unwrap_or_else(|err| err.into_inner()).take();
+  }
+}
+
+/// Marks a device as connecting until the `connect` call returns or is
+/// cancelled.
+struct ConnectingGuard<'a> {
+  devices: &'a StdMutex<HashSet<String>>,
+  device_id: String,
+}
+
+impl<'a> ConnectingGuard<'a> {
+  fn new(devices: &'a StdMutex<HashSet<String>>, device_id: &str) -> Self {
+    devices
+      .lock()
+      .unwrap_or_else(|err| err.into_inner())
+      .insert(device_id.to_string());
+    Self {
+      devices,
+      device_id: device_id.to_string(),
+    }
+  }
+}
+
+impl Drop for ConnectingGuard<'_> {
+  fn drop(&mut self) {
+    self
+      .devices
+      .lock()
+      .unwrap_or_else(|err| err.into_inner())
+      .remove(&self.device_id);
+  }
+}
+
+/// Frontend listeners for value events, by id, with their last heartbeat.
+struct ListenerLeases {
+  listeners: HashMap<String, Instant>,
+  /// When the last listener was removed or last heard from, or a value
+  /// source last started.
+  last_activity: Instant,
+}
+
+impl ListenerLeases {
+  fn new() -> Self {
+    Self {
+      listeners: HashMap::new(),
+      last_activity: Instant::now(),
+    }
+  }
+}
+
+/// One async lock per key, created on first use.
+#[derive(Default)]
+struct KeyedLocks {
+  locks: StdMutex<HashMap<String, Arc<Mutex<()>>>>,
+}
+
+impl KeyedLocks {
+  /// The key's lock, for tasks that take it repeatedly.
+  fn get(&self, key: &str) -> Arc<Mutex<()>> {
+    self
+      .locks
+      .lock()
+      .unwrap_or_else(|err| err.into_inner())
+      .entry(key.to_string())
+      .or_default()
+      .clone()
+  }
+
+  async fn lock(&self, key: &str) -> OwnedMutexGuard<()> {
+    self.get(key).lock_owned().await
+  }
+
+  fn all(&self) -> Vec<Arc<Mutex<()>>> {
+    self
+      .locks
+      .lock()
+      .unwrap_or_else(|err| err.into_inner())
+      .values()
+      .cloned()
+      .collect()
+  }
+
+  /// Drops the locks whose key matches. Holders keep theirs until released.
+  fn remove(&self, mut matches: impl FnMut(&str) -> bool) {
+    self
+      .locks
+      .lock()
+      .unwrap_or_else(|err| err.into_inner())
+      .retain(|key, _| !matches(key));
+  }
+}
+
+/// Lets a subscription be paused without unsubscribing. While paused, values
+/// are swallowed except for the most recent one.
+#[derive(Default)]
+struct NotificationGate {
+  state: StdMutex<GateState>,
+}
+
+#[derive(Default)]
+struct GateState {
+  paused: bool,
+  latest: Option<Vec<u8>>,
+}
+
+impl NotificationGate {
+  fn lock(&self) -> std::sync::MutexGuard<'_, GateState> {
+    self.state.lock().unwrap_or_else(|err| err.into_inner())
+  }
+
+  /// Returns the value when it should be emitted, or holds on to it while paused.
+  fn pass(&self, value: Vec<u8>) -> Option<Vec<u8>> {
+    let mut state = self.lock();
+    if state.paused {
+      state.latest = Some(value);
+      None
+    } else {
+      Some(value)
+    }
+  }
+
+  fn pause(&self) {
+    self.lock().paused = true;
+  }
+
+  fn is_paused(&self) -> bool {
+    self.lock().paused
+  }
+
+  fn resume(&self) -> Option<Vec<u8>> {
+    let mut state = self.lock();
+    state.paused = false;
+    state.latest.take()
+  }
 }
 
-impl<R: Runtime> WebBluetoothState<R> {
-  fn spawn_event_listener(self: &Arc<Self>) {
-    let adapter = self.adapter.clone();
-    let app = self.app.clone();
-    let notifications = self.notification_tasks.clone();
-    async_runtime::spawn(async move {
-      let events = adapter.events().await;
-      let mut events = match events {
-        Ok(stream) => stream,
-        Err(err) => {
-          log::error!("Failed to subscribe to Bluetooth adapter events: {err}");
+#[derive(Default)]
+struct NotificationCounters {
+  emitted: AtomicU64,
+  dropped: AtomicU64,
+}
+
+/// Values received by one subscription. `last_value_ms` is 0 until the first value.
+#[derive(Default)]
+struct CharacteristicCounters {
+  notification_count: AtomicU64,
+  last_value_ms: AtomicU64,
+  bytes_total: AtomicU64,
+}
+
+impl CharacteristicCounters {
+  fn record(&self, len: usize) {
+    let now = SystemTime::now()
+      .duration_since(UNIX_EPOCH)
+      .map_or(0, |elapsed| elapsed.as_millis() as u64);
+    self.notification_count.fetch_add(1, Ordering::Relaxed);
+    self.bytes_total.fetch_add(len as u64, Ordering::Relaxed);
+    self.last_value_ms.store(now, Ordering::Relaxed);
+  }
+
+  fn snapshot(&self) -> CharacteristicStats {
+    let last_value_ms = self.last_value_ms.load(Ordering::Relaxed);
+    CharacteristicStats {
+      notification_count: self.notification_count.load(Ordering::Relaxed),
+      last_value_ms: (last_value_ms != 0).then_some(last_value_ms),
+      bytes_total: self.bytes_total.load(Ordering::Relaxed),
+    }
+  }
+}
+
+/// Bounded hand-off between a notification stream and the task emitting
+/// events, so a slow emitter cannot grow memory without limit.
+struct NotificationQueue {
+  items: StdMutex<VecDeque<Vec<u8>>>,
+  capacity: usize,
+  policy: NotificationOverflowPolicy,
+  closed: AtomicBool,
+  readable: Notify,
+  writable: Notify,
+  counters: Arc<NotificationCounters>,
+}
+
+impl NotificationQueue {
+  fn new(capacity: usize, policy: NotificationOverflowPolicy, counters: Arc<NotificationCounters>) -> Self {
+    Self {
+      items: StdMutex::new(VecDeque::with_capacity(capacity)),
+      capacity: capacity.max(1),
+      policy,
+      closed: AtomicBool::new(false),
+      readable: Notify::new(),
+      writable: Notify::new(),
+      counters,
+    }
+  }
+
+  async fn push(&self, value: Vec<u8>) {
+    loop {
+      {
+        let mut items = self.items.lock().unwrap_or_else(|err| err.into_inner());
+        if items.len() < self.capacity {
+          items.push_back(value);
+          self.readable.notify_one();
           return;
         }
-      };
-      while let Some(event) = events.next().await {
-        if let CentralEvent::DeviceDisconnected(id) = event {
-          if let Ok(peripheral) = adapter.peripheral(&id).await {
-            let device_id = peripheral_key(&peripheral);
-            clear_notifications_for(&notifications, &device_id).await;
-            let _ = app.emit(
-              EVENT_GATT_DISCONNECTED,
-              DeviceEventPayload {
-                device_id,
-              },
-            );
+        match self.policy {
+          NotificationOverflowPolicy::DropOldest => {
+            items.pop_front();
+            items.push_back(value);
+            self.counters.dropped.fetch_add(1, Ordering::Relaxed);
+            self.readable.notify_one();
+            return;
           }
+          NotificationOverflowPolicy::DropNewest => {
+            self.counters.dropped.fetch_add(1, Ordering::Relaxed);
+            return;
+          }
+          NotificationOverflowPolicy::Block => {}
         }
       }
-    });
+      self.writable.notified().await;
+    }
+  }
+
+  /// Returns `None` once the queue is closed and drained.
+  async fn pop(&self) -> Option<Vec<u8>> {
+    loop {
+      {
+        let mut items = self.items.lock().unwrap_or_else(|err| err.into_inner());
+        if let Some(value) = items.pop_front() {
+          self.writable.notify_one();
+          return Some(value);
+        }
+        if self.closed.load(Ordering::Acquire) {
+          return None;
+        }
+      }
+      self.readable.notified().await;
+    }
+  }
+
+  fn close(&self) {
+    self.closed.store(true, Ordering::Release);
+    self.readable.notify_one();
   }
 }
 
+/// Passes values of `uuid` from `stream` through `queue` and `gate` to `emit`
+/// until the backend closes the stream.
+async fn forward_notifications(
+  stream: &mut (impl Stream<Item = ValueNotification> + Unpin),
+  uuid: Uuid,
+  queue: &NotificationQueue,
+  gate: &NotificationGate,
+  stats: &CharacteristicCounters,
+  mut emit: impl FnMut(&[u8]),
+) {
+  let read = async {
+    while let Some(notification) = stream.next().await {
+      if notification.uuid == uuid {
+        stats.record(notification.value.len());
+        queue.push(notification.value).await;
+      }
+    }
+    queue.close();
+  };
+  let emit = async {
+    while let Some(value) = queue.pop().await {
+      let Some(value) = gate.pass(value) else {
+        continue;
+      };
+      emit(&value);
+      queue.counters.emitted.fetch_add(1, Ordering::Relaxed);
+    }
+  };
+  futures::join!(read, emit);
+}
+
+/// Drops the task and gate of a subscription whose stream has ended.
+async fn end_subscription(
+  tasks: &Mutex<HashMap<String, JoinHandle<()>>>,
+  gates: &Mutex<HashMap<String, Arc<NotificationGate>>>,
+  key: &str,
+) {
+  tasks.lock().await.remove(key);
+  gates.lock().await.remove(key);
+}
+
 fn emit_notification<R: Runtime>(
   app: &AppHandle<R>,
   device_id: &str,
   service_uuid: &str,
   characteristic_uuid: &str,
-  notification: &ValueNotification,
+  value: &[u8],
+  from_read: bool,
+) {
+  let interpreted = app
+    .try_state::<WebBluetooth<R>>()
+    .and_then(|state| state.value_format(device_id, characteristic_uuid))
+    .and_then(|format| interpret(format, value));
+  emit_notification_with(app, device_id, service_uuid, characteristic_uuid, value, from_read, interpreted);
+}
+
+fn emit_notification_with<R: Runtime>(
+  app: &AppHandle<R>,
+  device_id: &str,
+  service_uuid: &str,
+  characteristic_uuid: &str,
+  value: &[u8],
+  from_read: bool,
+  interpreted: Option<serde_json::Value>,
 ) {
   let payload = NotificationEventPayload {
     device_id: device_id.to_string(),
     service_uuid: service_uuid.to_string(),
     characteristic_uuid: characteristic_uuid.to_string(),
-    value: BASE64_STANDARD.encode(&notification.value),
+    value: BASE64_STANDARD.encode(value),
+    from_read,
+    interpreted,
+  };
+  emit_device_event(app, EventKind::CharacteristicValueChanged, device_id, &payload);
+}
+
+/// Emits a device event under its own name and, with unified events
+/// enabled, wrapped in an [`EventEnvelope`] on [`EVENT_WEB_BLUETOOTH`].
+fn emit_device_event<R: Runtime, T: Serialize>(app: &AppHandle<R>, kind: EventKind, device_id: &str, payload: &T) {
+  let _ = app.emit(kind.event_name(), payload);
+  let unified = app
+    .try_state::<WebBluetooth<R>>()
+    .is_some_and(|state| state.inner.unified_events);
+  if unified {
+    let envelope = EventEnvelope {
+      kind,
+      device_id: device_id.to_string(),
+      timestamp_ms: SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_millis() as u64),
+      data: payload,
+    };
+    let _ = app.emit(EVENT_WEB_BLUETOOTH, envelope);
+  }
+}
+
+fn aggregate_samples(function: AggregateFunction, samples: &[f64]) -> f64 {
+  match function {
+    AggregateFunction::Min => samples.iter().copied().fold(f64::INFINITY, f64::min),
+    AggregateFunction::Max => samples.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+    AggregateFunction::Avg => samples.iter().sum::<f64>() / samples.len() as f64,
+  }
+}
+
+/// Waits up to `window` for adapter events and returns the peripherals they
+/// mention. Batching bounds how often the picker is updated in busy
+/// environments.
+async fn collect_changed_peripherals(
+  events: &mut Pin<Box<dyn futures::Stream<Item = CentralEvent> + Send>>,
+  window: Duration,
+) -> HashSet<PeripheralId> {
+  let deadline = Instant::now() + window;
+  let mut changed = HashSet::new();
+  loop {
+    let remaining = deadline.saturating_duration_since(Instant::now());
+    match timeout(remaining, events.next()).await {
+      Ok(Some(
+        CentralEvent::DeviceDiscovered(id)
+        | CentralEvent::DeviceUpdated(id)
+        | CentralEvent::ManufacturerDataAdvertisement { id, .. }
+        | CentralEvent::ServiceDataAdvertisement { id, .. }
+        | CentralEvent::ServicesAdvertisement { id, .. },
+      )) => {
+        changed.insert(id);
+      }
+      Ok(Some(_)) => {}
+      // The adapter closed the stream; keep the loop paced anyway.
+      Ok(None) => {
+        sleep(remaining).await;
+        return changed;
+      }
+      Err(_) => return changed,
+    }
+  }
+}
+
+/// Looks the adapter up over a short-lived D-Bus session, since btleplug does
+/// not surface BlueZ's adapter properties.
+#[cfg(target_os = "linux")]
+async fn bluez_adapter_address(adapter_id: &str) -> std::result::Result<LocalAdapterAddress, bluez_async::BluetoothError> {
+  let (connection, session) = bluez_async::BluetoothSession::new().await?;
+  let connection = async_runtime::spawn(connection);
+  let adapters = session.get_adapters().await;
+  connection.abort();
+  let address = adapters?
+    .into_iter()
+    .find(|adapter| adapter.id.to_string() == adapter_id)
+    .map(|adapter| LocalAdapterAddress {
+      address: Some(adapter.mac_address.to_string()),
+      random_address: Some(adapter.address_type == bluez_async::AddressType::Random),
+      name: Some(adapter.alias),
+    })
+    .unwrap_or_default();
+  Ok(address)
+}
+
+/// Secondary channels BlueZ can advertise on, e.g. `["1M", "2M", "Coded"]`.
+/// bluez-async does not wrap `LEAdvertisingManager1`, so the property is read
+/// over D-Bus directly. Fails when BlueZ does not expose it.
+#[cfg(target_os = "linux")]
+async fn bluez_secondary_channels(adapter_id: &str) -> std::result::Result<Vec<String>, dbus::Error> {
+  use dbus::nonblock::stdintf::org_freedesktop_dbus::Properties;
+
+  let (resource, connection) = dbus_tokio::connection::new_system_sync()?;
+  let resource = async_runtime::spawn(resource);
+  let proxy = dbus::nonblock::Proxy::new(
+    "org.bluez",
+    format!("/org/bluez/{adapter_id}"),
+    Duration::from_secs(5),
+    connection,
+  );
+  let channels = proxy
+    .get::<Vec<String>>("org.bluez.LEAdvertisingManager1", "SupportedSecondaryChannels")
+    .await;
+  resource.abort();
+  channels
+}
+
+/// Secondary advertising channels only exist with extended advertising, and
+/// each one names a PHY the controller supports. Without extended advertising
+/// the PHYs cannot be told apart.
+#[cfg(any(target_os = "linux", test))]
+fn capabilities_from_secondary_channels(channels: &[String]) -> AdapterCapabilities {
+  let extended_advertising = !channels.is_empty();
+  let supports = |phy: &str| extended_advertising.then(|| channels.iter().any(|channel| channel == phy));
+  AdapterCapabilities {
+    extended_advertising: Some(extended_advertising),
+    coded_phy: supports("Coded"),
+    le_2m_phy: supports("2M"),
+    max_connections: None,
+  }
+}
+
+/// Lowercase addresses of the devices BlueZ reports as paired on the adapter.
+#[cfg(target_os = "linux")]
+async fn bluez_paired_addresses(adapter_id: &str) -> std::result::Result<HashSet<String>, bluez_async::BluetoothError> {
+  let (connection, session) = bluez_async::BluetoothSession::new().await?;
+  let connection = async_runtime::spawn(connection);
+  let devices = session.get_devices().await;
+  connection.abort();
+  Ok(
+    devices?
+      .into_iter()
+      .filter(|device| device.paired && device.id.adapter().to_string() == adapter_id)
+      .map(|device| device.mac_address.to_string().to_ascii_lowercase())
+      .collect(),
+  )
+}
+
+/// BlueZ exposes the MTU as a property of each characteristic; the first one
+/// reporting it is used, as they all share the connection's MTU.
+#[cfg(target_os = "linux")]
+async fn bluez_mtu(adapter_id: &str, address: &str) -> std::result::Result<Option<u16>, bluez_async::BluetoothError> {
+  let (connection, session) = bluez_async::BluetoothSession::new().await?;
+  let connection = async_runtime::spawn(connection);
+  let mtu: std::result::Result<Option<u16>, bluez_async::BluetoothError> = async {
+    let device = session.get_devices().await?.into_iter().find(|device| {
+      device.id.adapter().to_string() == adapter_id && device.mac_address.to_string().eq_ignore_ascii_case(address)
+    });
+    let Some(device) = device else {
+      return Ok(None);
+    };
+    for service in session.get_services(&device.id).await? {
+      for characteristic in session.get_characteristics(&service.id).await? {
+        if characteristic.mtu.is_some() {
+          return Ok(characteristic.mtu);
+        }
+      }
+    }
+    Ok(None)
+  }
+  .await;
+  connection.abort();
+  mtu
+}
+
+fn load_last_device(path: &std::path::Path) -> Option<LastDevice> {
+  let json = match std::fs::read(path) {
+    Ok(json) => json,
+    Err(err) if err.kind() == std::io::ErrorKind::NotFound => return None,
+    Err(err) => {
+      log::warn!("Failed to read last device | path={} | err={:?}", path.display(), err);
+      return None;
+    }
+  };
+  serde_json::from_slice(&json)
+    .map_err(|err| log::warn!("Ignoring unreadable last device | path={} | err={:?}", path.display(), err))
+    .ok()
+}
+
+/// A device granted through `request_device`, as kept across restarts.
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GrantedDevice {
+  device_id: String,
+  /// Platform peripheral the id was derived from, so the device can be
+  /// looked up without a scan.
+  peripheral_id: PeripheralId,
+  name: Option<String>,
+  allowed_services: HashSet<Uuid>,
+}
+
+impl GrantedDevice {
+  /// Known-but-disconnected entry for a device the adapter cannot resolve.
+  fn describe(&self) -> BluetoothDevice {
+    BluetoothDevice {
+      id: self.device_id.clone(),
+      name: self.name.clone(),
+      uuids: Vec::new(),
+      watching_advertisements: false,
+      connected: false,
+      appearance: None,
+      manufacturer_name: None,
+      rssi: None,
+      tx_power: None,
+      manufacturer_data: HashMap::new(),
+      truncated: false,
+      paired: false,
+    }
+  }
+}
+
+fn load_granted_devices(path: &std::path::Path) -> Vec<GrantedDevice> {
+  let json = match std::fs::read(path) {
+    Ok(json) => json,
+    Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Vec::new(),
+    Err(err) => {
+      log::warn!("Failed to read granted devices | path={} | err={:?}", path.display(), err);
+      return Vec::new();
+    }
+  };
+  serde_json::from_slice(&json)
+    .map_err(|err| log::warn!("Ignoring unreadable granted devices | path={} | err={:?}", path.display(), err))
+    .unwrap_or_default()
+}
+
+fn save_granted_devices(path: &std::path::Path, devices: &[GrantedDevice]) {
+  let saved = path
+    .parent()
+    .map_or(Ok(()), std::fs::create_dir_all)
+    .map_err(Error::from)
+    .and_then(|()| Ok(serde_json::to_vec_pretty(devices)?))
+    .and_then(|json| Ok(std::fs::write(path, json)?));
+  if let Err(err) = saved {
+    log::warn!("Failed to save granted devices | path={} | err={:?}", path.display(), err);
+  }
+}
+
+/// Reads the per-install device id salt, creating it and its directory on
+/// first use. A salt that cannot be read is not replaced, as that would
+/// change every id; the unsalted scheme is used instead.
+fn load_or_create_salt(path: &std::path::Path) -> String {
+  match std::fs::read_to_string(path) {
+    Ok(salt) if !salt.trim().is_empty() => return salt.trim().to_string(),
+    Ok(_) => {}
+    Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+    Err(err) => {
+      log::warn!("Failed to read device id salt | path={} | err={:?}", path.display(), err);
+      return String::new();
+    }
+  }
+  let salt = Uuid::new_v4().simple().to_string();
+  let saved = path
+    .parent()
+    .map_or(Ok(()), std::fs::create_dir_all)
+    .and_then(|()| std::fs::write(path, &salt));
+  if let Err(err) = saved {
+    log::warn!("Failed to save device id salt | path={} | err={:?}", path.display(), err);
+  }
+  salt
+}
+
+/// Writes the device to `path`, or removes the file when there is none.
+fn save_last_device(path: &std::path::Path, device: Option<&LastDevice>) {
+  let saved = match device {
+    Some(device) => serde_json::to_vec_pretty(device)
+      .map_err(Error::from)
+      .and_then(|json| Ok(std::fs::write(path, json)?)),
+    None => match std::fs::remove_file(path) {
+      Err(err) if err.kind() != std::io::ErrorKind::NotFound => Err(err.into()),
+      _ => Ok(()),
+    },
   };
-  let _ = app.emit(EVENT_NOTIFICATION, payload);
+  if let Err(err) = saved {
+    log::warn!("Failed to save last device | path={} | err={:?}", path.display(), err);
+  }
+}
+
+/// Battery levels of every Battery Service instance, identified by the
+/// service's object path segment, which carries its attribute handle.
+#[cfg(target_os = "linux")]
+async fn bluez_battery_levels(
+  adapter_id: &str,
+  address: &str,
+) -> std::result::Result<Vec<BatteryLevel>, bluez_async::BluetoothError> {
+  let (connection, session) = bluez_async::BluetoothSession::new().await?;
+  let connection = async_runtime::spawn(connection);
+  let levels: std::result::Result<Vec<BatteryLevel>, bluez_async::BluetoothError> = async {
+    let device = session.get_devices().await?.into_iter().find(|device| {
+      device.id.adapter().to_string() == adapter_id && device.mac_address.to_string().eq_ignore_ascii_case(address)
+    });
+    let Some(device) = device else {
+      return Ok(Vec::new());
+    };
+    let mut levels = Vec::new();
+    for service in session.get_services(&device.id).await? {
+      if service.uuid != BATTERY_SERVICE_UUID {
+        continue;
+      }
+      let characteristic = session
+        .get_characteristic_by_uuid(&service.id, BATTERY_LEVEL_CHARACTERISTIC_UUID)
+        .await?;
+      let value = session.read_characteristic_value(&characteristic.id).await?;
+      let Some(level) = value.first().copied() else {
+        continue;
+      };
+      let service_id = service.id.to_string();
+      levels.push(BatteryLevel {
+        service_instance_id: service_id.rsplit('/').next().unwrap_or(&service_id).to_string(),
+        level,
+      });
+    }
+    Ok(levels)
+  }
+  .await;
+  connection.abort();
+  levels
+}
+
+/// Restores snapshotted values newest first. Returns `false` if any write could
+/// not be undone, including writes to characteristics that were not readable.
+async fn restore_written(written: &[(Peripheral, Characteristic, Option<Vec<u8>>)]) -> bool {
+  let mut restored = true;
+  for (peripheral, characteristic, previous) in written.iter().rev() {
+    let Some(previous) = previous else {
+      restored = false;
+      continue;
+    };
+    if let Err(err) = peripheral.write(characteristic, previous, WriteType::WithResponse).await {
+      log::warn!("Failed to roll back {} | err={:?}", characteristic.uuid, err);
+      restored = false;
+    }
+  }
+  restored
 }
 
 async fn clear_notifications_for(
   tasks: &Mutex<HashMap<String, JoinHandle<()>>>,
   device_id: &str,
 ) {
+  let prefix = notification_key(device_id, "");
   let mut guard = tasks.lock().await;
   let keys: Vec<String> = guard
     .keys()
-    .filter(|key| key.starts_with(device_id))
+    .filter(|key| key.starts_with(&prefix))
     .cloned()
     .collect();
   for key in keys {
@@ -1186,40 +6031,333 @@ async fn clear_notifications_for(
   }
 }
 
-fn service_to_model(service: Service) -> BluetoothService {
-  BluetoothService {
-    uuid: format_uuid(&service.uuid),
-    is_primary: service.primary,
-    characteristics: service
-      .characteristics
-      .iter()
-      .map(characteristic_to_model)
-      .collect(),
+fn service_to_model(service: Service) -> BluetoothService {
+  BluetoothService {
+    uuid: format_uuid(&service.uuid),
+    alias: uuid_alias(&service.uuid),
+    is_primary: service.primary,
+    characteristics: service
+      .characteristics
+      .iter()
+      .map(characteristic_to_model)
+      .collect(),
+  }
+}
+
+fn characteristic_to_model(characteristic: &Characteristic) -> BluetoothCharacteristic {
+  let flags = characteristic.properties;
+  BluetoothCharacteristic {
+    uuid: format_uuid(&characteristic.uuid),
+    alias: uuid_alias(&characteristic.uuid),
+    properties: CharacteristicProperties {
+      broadcast: flags.contains(CharPropFlags::BROADCAST),
+      read: flags.contains(CharPropFlags::READ),
+      write_without_response: flags.contains(CharPropFlags::WRITE_WITHOUT_RESPONSE),
+      write: flags.contains(CharPropFlags::WRITE),
+      notify: flags.contains(CharPropFlags::NOTIFY),
+      indicate: flags.contains(CharPropFlags::INDICATE),
+      authenticated_signed_writes: flags.contains(CharPropFlags::AUTHENTICATED_SIGNED_WRITES),
+      reliable_write: false,
+      writable_auxiliaries: false,
+    },
+    descriptors: characteristic
+      .descriptors
+      .iter()
+      .map(|descriptor| BluetoothDescriptor {
+        uuid: format_uuid(&descriptor.uuid),
+      })
+      .collect(),
+  }
+}
+
+fn advertisement_snapshot(properties: &PeripheralProperties, max_data_len: Option<usize>) -> AdvertisementSnapshot {
+  let mut truncated = false;
+  let manufacturer_data = properties
+    .manufacturer_data
+    .iter()
+    .map(|(company, data)| (*company, encode_advertisement_data(data, max_data_len, &mut truncated)))
+    .collect();
+  let service_data = properties
+    .service_data
+    .iter()
+    .map(|(uuid, data)| (format_uuid(uuid), encode_advertisement_data(data, max_data_len, &mut truncated)))
+    .collect();
+  AdvertisementSnapshot {
+    name: properties.local_name.clone(),
+    rssi: properties.rssi,
+    tx_power: properties.tx_power_level,
+    uuids: properties.services.iter().map(format_uuid).collect(),
+    manufacturer_data,
+    service_data,
+    truncated,
+  }
+}
+
+/// Base64 of an advertisement data blob cut to `max_len` bytes, flagging
+/// `truncated` when anything was dropped.
+fn encode_advertisement_data(data: &[u8], max_len: Option<usize>, truncated: &mut bool) -> String {
+  match max_len {
+    Some(max_len) if data.len() > max_len => {
+      *truncated = true;
+      BASE64_STANDARD.encode(&data[..max_len])
+    }
+    _ => BASE64_STANDARD.encode(data),
+  }
+}
+
+/// Reads a UTF-8 characteristic, or `None` when the service lacks it.
+async fn read_optional_string(peripheral: &Peripheral, service: &Service, uuid: Uuid) -> Result<Option<String>> {
+  let Some(characteristic) = service
+    .characteristics
+    .iter()
+    .find(|characteristic| characteristic.uuid == uuid)
+  else {
+    return Ok(None);
+  };
+  let bytes = peripheral.read(characteristic).await?;
+  Ok(Some(String::from_utf8_lossy(&bytes).trim_end_matches('\0').to_string()))
+}
+
+fn service_changed_characteristic(peripheral: &Peripheral) -> Option<Characteristic> {
+  peripheral
+    .services()
+    .into_iter()
+    .filter(|service| service.uuid == GENERIC_ATTRIBUTE_SERVICE_UUID)
+    .flat_map(|service| service.characteristics)
+    .find(|chr| chr.uuid == SERVICE_CHANGED_CHARACTERISTIC_UUID && chr.properties.contains(CharPropFlags::INDICATE))
+}
+
+/// Fills in the session's default scan timeout when the request omits one.
+fn with_default_scan_timeout(mut options: RequestDeviceOptions, default_ms: u64) -> RequestDeviceOptions {
+  options.scan_timeout_ms.get_or_insert(default_ms);
+  options
+}
+
+fn ping_samples(samples: u32) -> Result<u32> {
+  if (1..=MAX_PING_SAMPLES).contains(&samples) {
+    Ok(samples)
+  } else {
+    Err(Error::InvalidRequest(format!(
+      "samples must be between 1 and {MAX_PING_SAMPLES}, got {samples}"
+    )))
+  }
+}
+
+/// Describes every device with at most `concurrency` queries in flight. One
+/// misbehaving peripheral should not hide the rest of the list, so failures
+/// are logged and skipped.
+async fn describe_skipping_failures<T, F, Fut>(
+  devices: Vec<(String, T)>,
+  concurrency: usize,
+  describe: F,
+) -> Vec<BluetoothDevice>
+where
+  F: Fn(T) -> Fut,
+  Fut: Future<Output = Result<BluetoothDevice>>,
+{
+  futures::stream::iter(devices)
+    .map(|(device_id, device)| {
+      let described = describe(device);
+      async move {
+        match described.await {
+          Ok(device) => Some(device),
+          Err(err) => {
+            log::warn!("Skipping device in get_devices | device_id={} | err={:?}", device_id, err);
+            None
+          }
+        }
+      }
+    })
+    .buffer_unordered(concurrency)
+    .filter_map(|device| async move { device })
+    .collect()
+    .await
+}
+
+/// Picks the cached devices a request matches, so known devices that do not
+/// re-advertise during the scan window are listed from the start.
+fn seed_matches<T>(
+  cached: impl IntoIterator<Item = (String, T, PeripheralProperties)>,
+  normalized: &NormalizedRequestDeviceOptions,
+  excluded: &HashSet<String>,
+) -> HashMap<String, T> {
+  cached
+    .into_iter()
+    .filter(|(device_id, _, properties)| !excluded.contains(device_id) && normalized.matches(properties))
+    .map(|(device_id, peripheral, _)| (device_id, peripheral))
+    .collect()
+}
+
+/// Records a device matched during the scan. Returns `false` when it is
+/// already listed, e.g. seeded from the cache and now re-advertising.
+fn insert_new_match<T: Clone>(matched: &mut HashMap<String, T>, device_id: &str, peripheral: &T) -> bool {
+  if matched.contains_key(device_id) {
+    return false;
+  }
+  matched.insert(device_id.to_string(), peripheral.clone());
+  true
+}
+
+/// Fails for an adapter that reports being powered off, before a scan or
+/// when judging availability. Backends that cannot report power state are
+/// let through.
+fn ensure_powered(state: btleplug::Result<CentralState>) -> Result<()> {
+  match state {
+    Ok(CentralState::PoweredOff) => Err(Error::AdapterPoweredOff),
+    _ => Ok(()),
+  }
+}
+
+/// Pairs each requested descriptor UUID with the characteristic's descriptor,
+/// or `None` when it lacks one. Without a request every descriptor is read.
+fn descriptor_targets(
+  characteristic: &Characteristic,
+  requested: Option<&[String]>,
+) -> Result<Vec<(String, Option<Descriptor>)>> {
+  match requested {
+    Some(uuids) => uuids
+      .iter()
+      .map(|uuid| {
+        let target = parse_uuid(uuid)?;
+        let descriptor = characteristic
+          .descriptors
+          .iter()
+          .find(|descriptor| descriptor.uuid == target)
+          .cloned();
+        Ok((format_uuid(&target), descriptor))
+      })
+      .collect(),
+    None => Ok(
+      characteristic
+        .descriptors
+        .iter()
+        .map(|descriptor| (format_uuid(&descriptor.uuid), Some(descriptor.clone())))
+        .collect(),
+    ),
+  }
+}
+
+/// Reads the targets in turn, capturing each failure in its entry.
+async fn read_descriptor_targets<F, Fut>(
+  device_id: &str,
+  targets: Vec<(String, Option<Descriptor>)>,
+  mut read: F,
+) -> HashMap<String, DescriptorReadResult>
+where
+  F: FnMut(Descriptor) -> Fut,
+  Fut: Future<Output = Result<Vec<u8>>>,
+{
+  let mut results = HashMap::with_capacity(targets.len());
+  for (uuid, descriptor) in targets {
+    let outcome = match descriptor {
+      Some(descriptor) => read(descriptor).await,
+      None => Err(Error::DescriptorNotFound {
+        device_id: device_id.to_string(),
+        descriptor_uuid: uuid.clone(),
+      }),
+    };
+    let result = DescriptorReadResult {
+      value: outcome.as_ref().ok().map(|bytes| BASE64_STANDARD.encode(bytes)),
+      error: outcome.err().map(|err| err.to_string()),
+    };
+    results.insert(uuid, result);
+  }
+  results
+}
+
+/// Whether a subscription has to connect first. Fails with `NotConnected`
+/// for a disconnected device unless connecting on subscribe is enabled.
+fn connect_before_subscribing(device_id: &str, connected: bool, connect_on_subscribe: bool) -> Result<bool> {
+  match (connected, connect_on_subscribe) {
+    (true, _) => Ok(false),
+    (false, true) => Ok(true),
+    (false, false) => Err(Error::NotConnected(device_id.to_string())),
+  }
+}
+
+/// Takes up to `excess` cached ids from the least recently used end of
+/// `recency`, skipping pinned ones, and removes them from it. Ids that are
+/// no longer cached are dropped on the way.
+fn lru_evictions(
+  recency: &mut VecDeque<String>,
+  mut excess: usize,
+  cached: impl Fn(&str) -> bool,
+  pinned: impl Fn(&str) -> bool,
+) -> Vec<String> {
+  let mut evicted = Vec::new();
+  let mut index = 0;
+  while excess > 0 && index < recency.len() {
+    let is_cached = cached(&recency[index]);
+    if is_cached && pinned(&recency[index]) {
+      index += 1;
+      continue;
+    }
+    if let Some(id) = recency.remove(index) {
+      if is_cached {
+        evicted.push(id);
+        excess -= 1;
+      }
+    }
+  }
+  evicted
+}
+
+/// Devices that were never granted have no entry and may use no service.
+fn service_allowed(allowed: Option<&HashSet<Uuid>>, service_uuid: &Uuid) -> bool {
+  allowed.is_some_and(|allowed| allowed.contains(service_uuid))
+}
+
+fn ensure_service_allowed(allowed: Option<&HashSet<Uuid>>, device_id: &str, service_uuid: &Uuid) -> Result<()> {
+  if service_allowed(allowed, service_uuid) {
+    Ok(())
+  } else {
+    Err(Error::SecurityError {
+      device_id: device_id.to_string(),
+      service_uuid: format_uuid(service_uuid),
+    })
+  }
+}
+
+fn decode_standard_descriptor(descriptor_uuid: Uuid, bytes: &[u8]) -> Option<StandardDescriptorValue> {
+  match descriptor_uuid {
+    EXTENDED_PROPERTIES_DESCRIPTOR_UUID => {
+      decode_extended_properties(bytes).map(StandardDescriptorValue::ExtendedProperties)
+    }
+    USER_DESCRIPTION_DESCRIPTOR_UUID => Some(StandardDescriptorValue::UserDescription(decode_user_description(bytes))),
+    CLIENT_CONFIGURATION_DESCRIPTOR_UUID => {
+      decode_client_configuration(bytes).map(StandardDescriptorValue::ClientConfiguration)
+    }
+    PRESENTATION_FORMAT_DESCRIPTOR_UUID => {
+      decode_presentation_format(bytes).map(StandardDescriptorValue::PresentationFormat)
+    }
+    _ => None,
+  }
+}
+
+fn standard_descriptor_uuid(value: &StandardDescriptorValue) -> Uuid {
+  match value {
+    StandardDescriptorValue::ExtendedProperties(_) => EXTENDED_PROPERTIES_DESCRIPTOR_UUID,
+    StandardDescriptorValue::UserDescription(_) => USER_DESCRIPTION_DESCRIPTOR_UUID,
+    StandardDescriptorValue::ClientConfiguration(_) => CLIENT_CONFIGURATION_DESCRIPTOR_UUID,
+    StandardDescriptorValue::PresentationFormat(_) => PRESENTATION_FORMAT_DESCRIPTOR_UUID,
   }
 }
 
-fn characteristic_to_model(characteristic: &Characteristic) -> BluetoothCharacteristic {
-  let flags = characteristic.properties;
-  BluetoothCharacteristic {
-    uuid: format_uuid(&characteristic.uuid),
-    properties: CharacteristicProperties {
-      broadcast: flags.contains(CharPropFlags::BROADCAST),
-      read: flags.contains(CharPropFlags::READ),
-      write_without_response: flags.contains(CharPropFlags::WRITE_WITHOUT_RESPONSE),
-      write: flags.contains(CharPropFlags::WRITE),
-      notify: flags.contains(CharPropFlags::NOTIFY),
-      indicate: flags.contains(CharPropFlags::INDICATE),
-      authenticated_signed_writes: flags.contains(CharPropFlags::AUTHENTICATED_SIGNED_WRITES),
-      reliable_write: false,
-      writable_auxiliaries: false,
-    },
-    descriptors: characteristic
-      .descriptors
-      .iter()
-      .map(|descriptor| BluetoothDescriptor {
-        uuid: format_uuid(&descriptor.uuid),
-      })
-      .collect(),
+async fn read_standard_descriptor(
+  peripheral: &Peripheral,
+  characteristic: &Characteristic,
+  descriptor_uuid: Uuid,
+) -> Option<Vec<u8>> {
+  let descriptor = characteristic
+    .descriptors
+    .iter()
+    .find(|descriptor| descriptor.uuid == descriptor_uuid)?;
+  match peripheral.read_descriptor(descriptor).await {
+    Ok(bytes) => Some(bytes),
+    Err(err) => {
+      log::debug!("Descriptor read failed | descriptor_uuid={} | err={:?}", descriptor_uuid, err);
+      None
+    }
   }
 }
 
@@ -1234,15 +6372,203 @@ fn normalize_uuid_string(input: &str) -> String {
   }
 }
 
+/// Writes `payload` in `chunk_size` pieces, in order. Every piece but the
+/// last goes without response when the characteristic allows it; the last
+/// uses `write_type`, so a `WithResponse` write is acknowledged once the
+/// whole value is sent.
+async fn write_chunked(
+  peripheral: &Peripheral,
+  characteristic: &Characteristic,
+  payload: &[u8],
+  chunk_size: usize,
+  write_type: WriteType,
+) -> Result<()> {
+  let intermediate = if characteristic.properties.contains(CharPropFlags::WRITE_WITHOUT_RESPONSE) {
+    WriteType::WithoutResponse
+  } else {
+    write_type
+  };
+  let mut chunks = payload.chunks(chunk_size).peekable();
+  while let Some(chunk) = chunks.next() {
+    let kind = if chunks.peek().is_some() { intermediate } else { write_type };
+    peripheral.write(characteristic, chunk, kind).await?;
+  }
+  Ok(())
+}
+
 fn notification_key(device_id: &str, characteristic_uuid: &str) -> String {
   format!("{device_id}:{characteristic_uuid}")
 }
 
-fn peripheral_key(peripheral: &Peripheral) -> String {
-  peripheral.address().to_string()
+/// Key of a characteristic's subscription, poll or wait. The UUID is
+/// normalized, so a GATT name, short form and full UUID share one key.
+fn characteristic_key(device_id: &str, characteristic_uuid: &str) -> Result<String> {
+  Ok(notification_key(device_id, &format_uuid(&parse_uuid(characteristic_uuid)?)))
+}
+
+/// Applies the duplicate policy to an existing subscription under `key`, aborting it on
+/// `Restart`. Returns whether a new subscription should be started.
+fn resolve_duplicate_subscription(
+  tasks: &mut HashMap<String, JoinHandle<()>>,
+  key: &str,
+  policy: DuplicateNotificationPolicy,
+  device_id: &str,
+  characteristic_uuid: &str,
+) -> Result<bool> {
+  if !tasks.contains_key(key) {
+    return Ok(true);
+  }
+  match policy {
+    DuplicateNotificationPolicy::Error => Err(Error::NotificationsAlreadyActive {
+      device_id: device_id.to_string(),
+      characteristic_uuid: characteristic_uuid.to_string(),
+    }),
+    DuplicateNotificationPolicy::Ignore => Ok(false),
+    DuplicateNotificationPolicy::Restart => {
+      if let Some(handle) = tasks.remove(key) {
+        handle.abort();
+      }
+      Ok(true)
+    }
+  }
+}
+
+/// Tasks in `notification_tasks` that emit events other than value changes.
+fn is_watcher_key(key: &str) -> bool {
+  key.ends_with(SERVICE_CHANGED_TASK_SUFFIX) || key.ends_with(RSSI_TASK_SUFFIX)
+}
+
+/// Derives the device ids handed to the frontend. Ids are computed from the
+/// platform peripheral. The peripherals of cached and granted devices are
+/// remembered, so a cache miss on them can be resolved without enumerating
+/// the adapter.
+struct DeviceIds {
+  strategy: DeviceIdStrategy,
+  /// Mixed into [`DeviceIdStrategy::Stable`] ids; empty when no salt store
+  /// could be used.
+  salt: String,
+  peripherals: StdMutex<HashMap<String, PeripheralId>>,
+}
+
+impl DeviceIds {
+  fn key(&self, peripheral: &Peripheral) -> String {
+    match self.strategy {
+      DeviceIdStrategy::Address => peripheral.address().to_string(),
+      DeviceIdStrategy::PeripheralId => peripheral.id().to_string(),
+      DeviceIdStrategy::Stable => stable_device_id(&self.salt, &peripheral.id().to_string()),
+    }
+  }
+
+  fn remember(&self, device_id: &str, peripheral_id: PeripheralId) {
+    self
+      .peripherals
+      .lock()
+      .unwrap_or_else(|err| err.into_inner())
+      .insert(device_id.to_string(), peripheral_id);
+  }
+
+  fn forget(&self, device_id: &str) {
+    self
+      .peripherals
+      .lock()
+      .unwrap_or_else(|err| err.into_inner())
+      .remove(device_id);
+  }
+
+  fn peripheral_id(&self, device_id: &str) -> Option<PeripheralId> {
+    self
+      .peripherals
+      .lock()
+      .unwrap_or_else(|err| err.into_inner())
+      .get(device_id)
+      .cloned()
+  }
+}
+
+/// FNV-1a over the salt and the platform identifier. Deterministic across
+/// runs, unlike `std`'s randomly seeded hashers; an empty salt yields the
+/// ids of earlier releases.
+fn stable_device_id(salt: &str, platform_id: &str) -> String {
+  let hash = salt
+    .bytes()
+    .chain(platform_id.bytes())
+    .fold(0xcbf2_9ce4_8422_2325u64, |hash, byte| {
+      (hash ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3)
+    });
+  format!("{hash:016x}")
+}
+
+/// Application-defined UUID names. Kept process-wide so the free parsing
+/// helpers can resolve them; they last for the rest of the session.
+static UUID_ALIASES: OnceLock<StdRwLock<UuidAliases>> = OnceLock::new();
+
+#[derive(Default)]
+struct UuidAliases {
+  by_name: HashMap<String, Uuid>,
+  by_uuid: HashMap<Uuid, String>,
+}
+
+fn uuid_aliases() -> &'static StdRwLock<UuidAliases> {
+  UUID_ALIASES.get_or_init(Default::default)
+}
+
+/// Validates every entry before merging any, so a bad map leaves the
+/// registry untouched. Re-registering a UUID replaces its previous name.
+pub(crate) fn register_uuid_aliases(aliases: HashMap<String, String>) -> Result<()> {
+  let mut parsed = Vec::with_capacity(aliases.len());
+  for (uuid, name) in aliases {
+    let name = name.trim().to_string();
+    if name.is_empty() {
+      return Err(Error::InvalidRequest(format!("Alias for {uuid} must not be empty")));
+    }
+    if parse_uuid_literal(&name).is_ok() {
+      return Err(Error::InvalidRequest(format!("Alias {name} must not itself be a UUID")));
+    }
+    // Aliases are looked up first, so this would shadow the standard name.
+    if standard_uuid(&name).is_some() {
+      return Err(Error::InvalidRequest(format!("Alias {name} is already a standard GATT name")));
+    }
+    parsed.push((parse_uuid_literal(&uuid)?, name));
+  }
+
+  let mut registry = uuid_aliases().write().unwrap_or_else(|err| err.into_inner());
+  for (uuid, name) in parsed {
+    if let Some(previous) = registry.by_uuid.insert(uuid, name.clone()) {
+      registry.by_name.remove(&previous);
+    }
+    if let Some(stale) = registry.by_name.insert(name, uuid) {
+      if stale != uuid {
+        registry.by_uuid.remove(&stale);
+      }
+    }
+  }
+  Ok(())
+}
+
+fn uuid_alias(uuid: &Uuid) -> Option<String> {
+  let registry = uuid_aliases().read().unwrap_or_else(|err| err.into_inner());
+  registry.by_uuid.get(uuid).cloned()
 }
 
+/// Accepts a registered alias, a standard GATT name like `heart_rate`, or a
+/// UUID literal, in that order.
 fn parse_uuid(input: &str) -> Result<Uuid> {
+  let alias = uuid_aliases()
+    .read()
+    .unwrap_or_else(|err| err.into_inner())
+    .by_name
+    .get(input.trim())
+    .copied();
+  if let Some(uuid) = alias {
+    return Ok(uuid);
+  }
+  match standard_uuid(input.trim()) {
+    Some(short) => Ok(uuid_from_u16(short)),
+    None => parse_uuid_literal(input),
+  }
+}
+
+fn parse_uuid_literal(input: &str) -> Result<Uuid> {
   let trimmed = input.trim().trim_start_matches("0x");
   let normalized = match trimmed.len() {
     4 => format!("0000{trimmed}-0000-1000-8000-00805f9b34fb"),
@@ -1256,12 +6582,56 @@ struct NormalizedRequestDeviceOptions {
   accept_all_devices: bool,
   filters: Vec<NormalizedDeviceFilter>,
   scan_timeout: Duration,
+  exclude_known_devices: KnownDeviceExclusion,
+  allowed_services: HashSet<Uuid>,
 }
 
 struct NormalizedDeviceFilter {
   services: Vec<Uuid>,
+  services_match: ServicesMatch,
   name: Option<String>,
   name_prefix: Option<String>,
+  manufacturer_data: Vec<(u16, DataPrefix)>,
+  service_data: Vec<(Uuid, DataPrefix)>,
+}
+
+/// Decoded `dataPrefix`/`mask` pair shared by manufacturer and service data
+/// filters.
+struct DataPrefix {
+  prefix: Vec<u8>,
+  mask: Vec<u8>,
+}
+
+impl DataPrefix {
+  fn decode(prefix: Option<&str>, mask: Option<&str>, filter_name: &str) -> Result<Self> {
+    let prefix = match prefix {
+      Some(prefix) => BASE64_STANDARD.decode(prefix)?,
+      None => Vec::new(),
+    };
+    let mask = match mask {
+      Some(mask) => BASE64_STANDARD.decode(mask)?,
+      None => vec![0xff; prefix.len()],
+    };
+    if mask.len() != prefix.len() {
+      return Err(Error::InvalidRequest(format!(
+        "{filter_name} mask must be as long as dataPrefix"
+      )));
+    }
+    Ok(Self { prefix, mask })
+  }
+
+  fn matches(&self, data: Option<&Vec<u8>>) -> bool {
+    let Some(data) = data else {
+      return false;
+    };
+    data.len() >= self.prefix.len()
+      && self
+        .prefix
+        .iter()
+        .zip(&self.mask)
+        .zip(data)
+        .all(|((prefix, mask), byte)| prefix & mask == byte & mask)
+  }
 }
 
 impl TryFrom<RequestDeviceOptions> for NormalizedRequestDeviceOptions {
@@ -1285,16 +6655,51 @@ impl TryFrom<RequestDeviceOptions> for NormalizedRequestDeviceOptions {
           .collect::<Result<Vec<_>>>()?;
         Ok(NormalizedDeviceFilter {
           services,
+          services_match: filter.services_match,
           name: filter.name,
           name_prefix: filter.name_prefix,
+          manufacturer_data: filter
+            .manufacturer_data
+            .iter()
+            .map(|entry| {
+              let prefix = DataPrefix::decode(
+                entry.data_prefix.as_deref(),
+                entry.mask.as_deref(),
+                &format!("manufacturerData {:#06x}", entry.company_identifier),
+              )?;
+              Ok((entry.company_identifier, prefix))
+            })
+            .collect::<Result<_>>()?,
+          service_data: filter
+            .service_data
+            .iter()
+            .map(|entry| {
+              let prefix = DataPrefix::decode(
+                entry.data_prefix.as_deref(),
+                entry.mask.as_deref(),
+                &format!("serviceData {}", entry.service),
+              )?;
+              Ok((parse_uuid(&entry.service)?, prefix))
+            })
+            .collect::<Result<_>>()?,
         })
       })
       .collect::<Result<Vec<_>>>()?;
+    let mut allowed_services: HashSet<Uuid> = options
+      .optional_services
+      .iter()
+      .map(|value| parse_uuid(value))
+      .collect::<Result<_>>()?;
+    for filter in &filters {
+      allowed_services.extend(filter.services.iter().copied());
+    }
 
     Ok(Self {
       accept_all_devices: options.accept_all_devices,
       filters,
-      scan_timeout: Duration::from_millis(options.scan_timeout_ms.max(1)),
+      scan_timeout: Duration::from_millis(options.scan_timeout_ms.unwrap_or(DEFAULT_SCAN_TIMEOUT_MS).max(1)),
+      exclude_known_devices: options.exclude_known_devices,
+      allowed_services,
     })
   }
 }
@@ -1306,6 +6711,26 @@ impl NormalizedRequestDeviceOptions {
     }
     self.filters.iter().any(|filter| filter.matches(properties))
   }
+
+  /// Lets the OS drop advertisements early when every filter requires
+  /// services. Any device a filter can match advertises at least one service
+  /// in the union, so the result is a superset of the matches.
+  fn scan_filter(&self) -> ScanFilter {
+    if self.accept_all_devices || self.filters.iter().any(|filter| filter.services.is_empty()) {
+      return ScanFilter::default();
+    }
+    let mut services: Vec<Uuid> = Vec::new();
+    for uuid in self.filters.iter().flat_map(|filter| &filter.services) {
+      if !services.contains(uuid) {
+        services.push(*uuid);
+      }
+    }
+    ScanFilter { services }
+  }
+
+  fn matched_filter_index(&self, properties: &PeripheralProperties) -> Option<usize> {
+    self.filters.iter().position(|filter| filter.matches(properties))
+  }
 }
 
 impl NormalizedDeviceFilter {
@@ -1325,12 +6750,605 @@ impl NormalizedDeviceFilter {
         return false;
       }
     }
+    if !self
+      .manufacturer_data
+      .iter()
+      .all(|(company, prefix)| prefix.matches(properties.manufacturer_data.get(company)))
+    {
+      return false;
+    }
+    if !self
+      .service_data
+      .iter()
+      .all(|(service, prefix)| prefix.matches(properties.service_data.get(service)))
+    {
+      return false;
+    }
     if !self.services.is_empty() {
       let present: HashSet<Uuid> = properties.services.iter().cloned().collect();
-      if !self.services.iter().all(|uuid| present.contains(uuid)) {
+      let matched = match self.services_match {
+        ServicesMatch::All => self.services.iter().all(|uuid| present.contains(uuid)),
+        ServicesMatch::Any => self.services.iter().any(|uuid| present.contains(uuid)),
+      };
+      if !matched {
         return false;
       }
     }
     true
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use futures::executor::block_on;
+
+  fn normalized(options: serde_json::Value) -> NormalizedRequestDeviceOptions {
+    NormalizedRequestDeviceOptions::try_from(serde_json::from_value::<RequestDeviceOptions>(options).unwrap()).unwrap()
+  }
+
+  fn advertising(services: &[u16]) -> PeripheralProperties {
+    PeripheralProperties {
+      services: services.iter().map(|service| uuid_from_u16(*service)).collect(),
+      ..Default::default()
+    }
+  }
+
+  fn closed_flag() -> (Arc<AtomicBool>, impl FnOnce()) {
+    let closed = Arc::new(AtomicBool::new(false));
+    let flag = closed.clone();
+    (closed, move || flag.store(true, Ordering::SeqCst))
+  }
+
+  #[test]
+  fn empty_scan_leaves_an_answered_handler_alone() {
+    let (closed, close) = closed_flag();
+    let future: MultiSelectionFuture = Box::pin(async { panic!("selection polled after it resolved") });
+    let err = block_on(finish_empty_selection(Some(Some(vec!["a".into()])), future, close));
+    assert!(matches!(err, Error::NoDevicesFound));
+    assert!(!closed.load(Ordering::SeqCst));
+  }
+
+  #[test]
+  fn empty_scan_prefers_a_pending_cancel() {
+    let (closed, close) = closed_flag();
+    let future: MultiSelectionFuture = Box::pin(async { Ok(None) });
+    let err = block_on(finish_empty_selection(None, future, close));
+    assert!(matches!(err, Error::SelectionCancelled));
+    assert!(!closed.load(Ordering::SeqCst));
+  }
+
+  #[test]
+  fn empty_scan_closes_a_waiting_handler() {
+    let (tx, rx) = tokio::sync::oneshot::channel::<()>();
+    let future: MultiSelectionFuture = Box::pin(async move {
+      let _ = rx.await;
+      Ok(None)
+    });
+    let err = block_on(finish_empty_selection(None, future, move || {
+      let _ = tx.send(());
+    }));
+    assert!(matches!(err, Error::NoDevicesFound));
+  }
+
+  #[test]
+  fn services_outside_the_grant_are_denied() {
+    let allowed = HashSet::from([BATTERY_SERVICE_UUID]);
+    assert!(ensure_service_allowed(Some(&allowed), "device", &BATTERY_SERVICE_UUID).is_ok());
+    let err = ensure_service_allowed(Some(&allowed), "device", &DEVICE_INFORMATION_SERVICE_UUID).unwrap_err();
+    assert!(matches!(err, Error::SecurityError { .. }));
+  }
+
+  #[test]
+  fn ungranted_devices_may_use_no_service() {
+    let err = ensure_service_allowed(None, "device", &BATTERY_SERVICE_UUID).unwrap_err();
+    assert!(matches!(err, Error::SecurityError { .. }));
+  }
+
+  #[test]
+  fn theme_colors_cannot_escape_their_declaration() {
+    let css = SelectionDialogTheme::new()
+      .accent("red; } body { display: none")
+      .background("</style><script>")
+      .text("rgb(1, 2, 3)")
+      .css_variables();
+    assert!(css.contains("--accent: #0082f6;"));
+    assert!(css.contains("--background: #f4f5f7;"));
+    assert!(css.contains("--text: rgb(1, 2, 3);"));
+  }
+
+  #[test]
+  fn ping_samples_stay_within_bounds() {
+    assert_eq!(ping_samples(1).unwrap(), 1);
+    assert_eq!(ping_samples(MAX_PING_SAMPLES).unwrap(), MAX_PING_SAMPLES);
+    assert!(matches!(ping_samples(0), Err(Error::InvalidRequest(_))));
+    assert!(matches!(ping_samples(MAX_PING_SAMPLES + 1), Err(Error::InvalidRequest(_))));
+  }
+
+  #[test]
+  fn gatt_queue_runs_ten_concurrent_reads_one_at_a_time() {
+    let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+    let queues = KeyedLocks::default();
+    let in_flight = AtomicUsize::new(0);
+    let peak = AtomicUsize::new(0);
+    let order = StdMutex::new(Vec::new());
+    let reads = (0..10).map(|index| {
+      let (queues, in_flight, peak, order) = (&queues, &in_flight, &peak, &order);
+      async move {
+        let _queued = queues.lock("device").await;
+        let running = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+        peak.fetch_max(running, Ordering::SeqCst);
+        // Give the other reads a chance to barge in.
+        tokio::task::yield_now().await;
+        order.lock().unwrap().push(index);
+        in_flight.fetch_sub(1, Ordering::SeqCst);
+      }
+    });
+    runtime.block_on(futures::future::join_all(reads));
+    assert_eq!(peak.load(Ordering::SeqCst), 1);
+    assert_eq!(*order.lock().unwrap(), (0..10).collect::<Vec<_>>());
+  }
+
+  #[test]
+  fn gatt_queues_of_different_devices_do_not_block_each_other() {
+    let queues = KeyedLocks::default();
+    let first = queues.get("first").try_lock_owned().unwrap();
+    assert!(queues.get("second").try_lock_owned().is_ok());
+    assert!(queues.get("first").try_lock_owned().is_err());
+    drop(first);
+    queues.remove(|key| key == "first");
+    assert_eq!(queues.all().len(), 1);
+  }
+
+  #[test]
+  fn device_id_salt_is_created_once_and_reused() {
+    let dir = std::env::temp_dir().join(format!("web-bluetooth-salt-{}", std::process::id()));
+    let path = dir.join("nested").join(DEVICE_ID_SALT_FILE);
+    let salt = load_or_create_salt(&path);
+    assert_eq!(salt.len(), 32);
+    assert_eq!(load_or_create_salt(&path), salt);
+    assert_ne!(stable_device_id(&salt, "peripheral"), stable_device_id("", "peripheral"));
+    std::fs::remove_dir_all(&dir).unwrap();
+  }
+
+  #[test]
+  fn services_match_all_requires_every_service() {
+    let options = normalized(serde_json::json!({ "filters": [{ "services": ["0x180d", "0x180f"] }] }));
+    assert!(options.matches(&advertising(&[0x180D, 0x180F, 0x1800])));
+    assert!(!options.matches(&advertising(&[0x180D])));
+  }
+
+  #[test]
+  fn services_match_any_accepts_one_service() {
+    let options = normalized(serde_json::json!({
+      "filters": [{ "services": ["0x180d", "0x180f"], "servicesMatch": "any" }]
+    }));
+    assert!(options.matches(&advertising(&[0x180F])));
+    assert!(!options.matches(&advertising(&[0x1800])));
+  }
+
+  #[test]
+  fn uuid_aliases_round_trip() {
+    let uuid = "6e400001-b5a3-f393-e0a9-e50e24dcca9e";
+    register_uuid_aliases(HashMap::from([(uuid.to_string(), "alias_round_trip".to_string())])).unwrap();
+    let parsed = parse_uuid("alias_round_trip").unwrap();
+    assert_eq!(format_uuid(&parsed), uuid);
+    assert_eq!(uuid_alias(&parsed).as_deref(), Some("alias_round_trip"));
+  }
+
+  #[test]
+  fn uuid_aliases_reject_bad_entries_without_merging_any() {
+    let aliases = HashMap::from([
+      ("6e400002-b5a3-f393-e0a9-e50e24dcca9e".to_string(), "alias_rejected".to_string()),
+      ("6e400003-b5a3-f393-e0a9-e50e24dcca9e".to_string(), "0x180d".to_string()),
+    ]);
+    assert!(matches!(register_uuid_aliases(aliases), Err(Error::InvalidRequest(_))));
+    assert!(parse_uuid("alias_rejected").is_err());
+  }
+
+  #[test]
+  fn uuid_aliases_cannot_shadow_standard_names() {
+    let aliases = HashMap::from([("6e400004-b5a3-f393-e0a9-e50e24dcca9e".to_string(), " heart_rate ".to_string())]);
+    assert!(matches!(register_uuid_aliases(aliases), Err(Error::InvalidRequest(_))));
+    assert_eq!(parse_uuid("heart_rate").unwrap(), uuid_from_u16(0x180D));
+  }
+
+  #[test]
+  fn lru_eviction_skips_pinned_and_stale_devices() {
+    let mut recency: VecDeque<String> = ["stale", "a", "connected", "b", "c", "new"].map(String::from).into();
+    let cached = |id: &str| id != "stale";
+    let pinned = |id: &str| id == "connected" || id == "new";
+    let evicted = lru_evictions(&mut recency, 2, cached, pinned);
+    assert_eq!(evicted, ["a", "b"]);
+    assert_eq!(recency, ["connected", "c", "new"]);
+  }
+
+  #[test]
+  fn lru_eviction_stops_when_everything_left_is_pinned() {
+    let mut recency: VecDeque<String> = ["a", "b"].map(String::from).into();
+    let evicted = lru_evictions(&mut recency, 1, |_| true, |_| true);
+    assert!(evicted.is_empty());
+    assert_eq!(recency.len(), 2);
+  }
+
+  fn active_subscription(key: &str) -> HashMap<String, JoinHandle<()>> {
+    let handle = tauri::async_runtime::spawn(futures::future::pending::<()>());
+    HashMap::from([(key.to_string(), handle)])
+  }
+
+  #[test]
+  fn first_subscription_starts_under_every_policy() {
+    for policy in [
+      DuplicateNotificationPolicy::Error,
+      DuplicateNotificationPolicy::Ignore,
+      DuplicateNotificationPolicy::Restart,
+    ] {
+      let mut tasks = HashMap::new();
+      assert!(resolve_duplicate_subscription(&mut tasks, "dev|2a37", policy, "dev", "2a37").unwrap());
+    }
+  }
+
+  #[test]
+  fn duplicate_subscription_fails_under_error_policy() {
+    let mut tasks = active_subscription("dev|2a37");
+    let result =
+      resolve_duplicate_subscription(&mut tasks, "dev|2a37", DuplicateNotificationPolicy::Error, "dev", "2a37");
+    assert!(matches!(result, Err(Error::NotificationsAlreadyActive { .. })));
+    assert!(tasks.contains_key("dev|2a37"));
+  }
+
+  #[test]
+  fn duplicate_subscription_is_kept_under_ignore_policy() {
+    let mut tasks = active_subscription("dev|2a37");
+    let result =
+      resolve_duplicate_subscription(&mut tasks, "dev|2a37", DuplicateNotificationPolicy::Ignore, "dev", "2a37");
+    assert!(!result.unwrap());
+    assert!(tasks.contains_key("dev|2a37"));
+  }
+
+  #[test]
+  fn duplicate_subscription_is_replaced_under_restart_policy() {
+    let mut tasks = active_subscription("dev|2a37");
+    let result =
+      resolve_duplicate_subscription(&mut tasks, "dev|2a37", DuplicateNotificationPolicy::Restart, "dev", "2a37");
+    assert!(result.unwrap());
+    assert!(tasks.is_empty());
+  }
+
+  fn manufacturer_filter(prefix: &str, mask: Option<&str>) -> NormalizedRequestDeviceOptions {
+    normalized(serde_json::json!({
+      "filters": [{"manufacturerData": [{"companyIdentifier": 0x004C, "dataPrefix": prefix, "mask": mask}]}]
+    }))
+  }
+
+  fn manufacturer_data(data: &[u8]) -> PeripheralProperties {
+    PeripheralProperties {
+      manufacturer_data: HashMap::from([(0x004C, data.to_vec())]),
+      ..Default::default()
+    }
+  }
+
+  #[test]
+  fn data_prefix_mask_ignores_cleared_bits() {
+    // dataPrefix 01 02, mask ff 00: only the first byte is compared.
+    let options = manufacturer_filter("AQI=", Some("/wA="));
+    assert!(options.matches(&manufacturer_data(&[0x01, 0x99, 0x33])));
+    assert!(!options.matches(&manufacturer_data(&[0x02, 0x02])));
+    assert!(!options.matches(&manufacturer_data(&[0x01])));
+    assert!(!options.matches(&PeripheralProperties::default()));
+  }
+
+  #[test]
+  fn data_prefix_without_mask_compares_every_bit() {
+    let options = manufacturer_filter("AQI=", None);
+    assert!(options.matches(&manufacturer_data(&[0x01, 0x02, 0x03])));
+    assert!(!options.matches(&manufacturer_data(&[0x01, 0x03])));
+  }
+
+  #[test]
+  fn data_prefix_rejects_a_mask_of_another_length() {
+    let options = serde_json::from_value::<RequestDeviceOptions>(serde_json::json!({
+      "filters": [{"manufacturerData": [{"companyIdentifier": 0x004C, "dataPrefix": "AQI=", "mask": "/w=="}]}]
+    }))
+    .unwrap();
+    assert!(matches!(
+      NormalizedRequestDeviceOptions::try_from(options),
+      Err(Error::InvalidRequest(_))
+    ));
+  }
+
+  #[test]
+  fn service_data_filter_matches_the_named_service_only() {
+    let options = normalized(serde_json::json!({
+      "filters": [{"serviceData": [{"service": "battery_service", "dataPrefix": "ZA=="}]}]
+    }));
+    let with_data = |service: u16, data: &[u8]| PeripheralProperties {
+      service_data: HashMap::from([(uuid_from_u16(service), data.to_vec())]),
+      ..Default::default()
+    };
+    assert!(options.matches(&with_data(0x180F, &[0x64, 0x01])));
+    assert!(!options.matches(&with_data(0x180F, &[0x32])));
+    assert!(!options.matches(&with_data(0x180D, &[0x64])));
+    assert!(!options.matches(&PeripheralProperties::default()));
+  }
+
+  #[test]
+  fn omitted_scan_timeout_uses_the_configured_default() {
+    let options =
+      serde_json::from_value::<RequestDeviceOptions>(serde_json::json!({"acceptAllDevices": true})).unwrap();
+    let options = with_default_scan_timeout(options, 2500);
+    assert_eq!(options.scan_timeout_ms, Some(2500));
+    let normalized = NormalizedRequestDeviceOptions::try_from(options).unwrap();
+    assert_eq!(normalized.scan_timeout, Duration::from_millis(2500));
+  }
+
+  #[test]
+  fn explicit_scan_timeout_overrides_the_configured_default() {
+    let options = serde_json::from_value::<RequestDeviceOptions>(serde_json::json!({
+      "acceptAllDevices": true,
+      "scanTimeoutMs": 800
+    }))
+    .unwrap();
+    assert_eq!(with_default_scan_timeout(options, 2500).scan_timeout_ms, Some(800));
+  }
+
+  #[test]
+  fn standard_gatt_names_parse_like_their_uuids() {
+    assert_eq!(parse_uuid("heart_rate").unwrap(), parse_uuid("180d").unwrap());
+    assert_eq!(parse_uuid(" battery_level ").unwrap(), uuid_from_u16(0x2A19));
+  }
+
+  fn device_named(id: &str) -> BluetoothDevice {
+    serde_json::from_value(serde_json::json!({"id": id, "name": null, "connected": false})).unwrap()
+  }
+
+  #[test]
+  fn devices_that_fail_to_describe_are_skipped() {
+    let devices = vec![("a".to_string(), 1), ("b".to_string(), 2), ("c".to_string(), 3)];
+    let described = block_on(describe_skipping_failures(devices, 2, |index| async move {
+      match index {
+        2 => Err(Error::NotConnected("b".into())),
+        _ => Ok(device_named(&index.to_string())),
+      }
+    }));
+    let mut ids: Vec<String> = described.into_iter().map(|device| device.id).collect();
+    ids.sort();
+    assert_eq!(ids, ["1", "3"]);
+  }
+
+  #[test]
+  fn devices_are_described_completely_within_the_concurrency_bound() {
+    let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+    let in_flight = Arc::new(AtomicUsize::new(0));
+    let peak = Arc::new(AtomicUsize::new(0));
+    let devices: Vec<(String, usize)> = (0..8).map(|index| (index.to_string(), index)).collect();
+    let described = runtime.block_on(describe_skipping_failures(devices, 3, |index| {
+      let in_flight = in_flight.clone();
+      let peak = peak.clone();
+      async move {
+        let running = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+        peak.fetch_max(running, Ordering::SeqCst);
+        // Later devices finish first, so completion order differs from input order.
+        for _ in 0..(8 - index) {
+          tokio::task::yield_now().await;
+        }
+        in_flight.fetch_sub(1, Ordering::SeqCst);
+        Ok(device_named(&index.to_string()))
+      }
+    }));
+    let mut ids: Vec<usize> = described.iter().map(|device| device.id.parse().unwrap()).collect();
+    assert_ne!(ids, (0..8).collect::<Vec<_>>());
+    ids.sort();
+    assert_eq!(ids, (0..8).collect::<Vec<_>>());
+    assert_eq!(peak.load(Ordering::SeqCst), 3);
+  }
+
+  #[test]
+  fn ended_notification_stream_drops_its_subscription() {
+    let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+    let heart_rate = uuid_from_u16(0x2A37);
+    let notification = |uuid, value: &[u8]| ValueNotification { uuid, value: value.to_vec() };
+    let mut stream = futures::stream::iter([
+      notification(heart_rate, &[60]),
+      notification(uuid_from_u16(0x2A19), &[99]),
+      notification(heart_rate, &[61]),
+    ]);
+    let queue = NotificationQueue::new(4, NotificationOverflowPolicy::Block, Arc::default());
+    let gate = Arc::new(NotificationGate::default());
+    let stats = CharacteristicCounters::default();
+    let tasks = Mutex::new(active_subscription("dev|2a37"));
+    let gates = Mutex::new(HashMap::from([("dev|2a37".to_string(), gate.clone())]));
+    let mut emitted = Vec::new();
+    runtime.block_on(async {
+      forward_notifications(&mut stream, heart_rate, &queue, &gate, &stats, |value| emitted.push(value.to_vec())).await;
+      end_subscription(&tasks, &gates, "dev|2a37").await;
+    });
+    assert_eq!(emitted, [vec![60], vec![61]]);
+    assert_eq!(stats.snapshot().notification_count, 2);
+    assert!(runtime.block_on(tasks.lock()).is_empty());
+    assert!(runtime.block_on(gates.lock()).is_empty());
+  }
+
+  #[test]
+  fn cached_matches_seed_the_scan_once() {
+    let options = normalized(serde_json::json!({"filters": [{"services": ["heart_rate"]}]}));
+    let cached = vec![
+      ("strap".to_string(), 1, advertising(&[0x180D])),
+      ("scale".to_string(), 2, advertising(&[0x181D])),
+      ("excluded".to_string(), 3, advertising(&[0x180D])),
+    ];
+    let mut matched = seed_matches(cached, &options, &HashSet::from(["excluded".to_string()]));
+    assert_eq!(matched, HashMap::from([("strap".to_string(), 1)]));
+    assert!(!insert_new_match(&mut matched, "strap", &1));
+    assert!(insert_new_match(&mut matched, "watch", &4));
+    assert_eq!(matched.len(), 2);
+  }
+
+  #[test]
+  fn scanning_a_powered_off_adapter_fails_early() {
+    assert!(matches!(ensure_powered(Ok(CentralState::PoweredOff)), Err(Error::AdapterPoweredOff)));
+    assert!(ensure_powered(Ok(CentralState::PoweredOn)).is_ok());
+    assert!(ensure_powered(Ok(CentralState::Unknown)).is_ok());
+    assert!(ensure_powered(Err(btleplug::Error::NotSupported("adapter state".into()))).is_ok());
+  }
+
+  fn adapter_infos(infos: &[&str]) -> Vec<AdapterInfo> {
+    infos
+      .iter()
+      .enumerate()
+      .map(|(index, info)| AdapterInfo {
+        index,
+        info: info.to_string(),
+        ..Default::default()
+      })
+      .collect()
+  }
+
+  #[test]
+  fn preferred_adapter_picks_the_first_match() {
+    let adapters = adapter_infos(&["hci0 (Intel)", "hci1 (USB dongle)", "hci2 (USB dongle)"]);
+    let selector = AdapterSelector::preferring(|info| info.contains("USB"));
+    assert_eq!(selector.select(&adapters), Some(1));
+  }
+
+  #[test]
+  fn preferred_adapter_falls_back_to_the_first_adapter() {
+    let adapters = adapter_infos(&["hci0 (Intel)", "hci1 (Realtek)"]);
+    let selector = AdapterSelector::preferring(|info| info.contains("USB"));
+    assert_eq!(selector.select(&adapters), Some(0));
+  }
+
+  fn characteristic_with_descriptors(descriptors: &[u16]) -> Characteristic {
+    let uuid = uuid_from_u16(0x2A37);
+    let service_uuid = uuid_from_u16(0x180D);
+    Characteristic {
+      uuid,
+      service_uuid,
+      properties: CharPropFlags::NOTIFY,
+      descriptors: descriptors
+        .iter()
+        .map(|descriptor| Descriptor {
+          uuid: uuid_from_u16(*descriptor),
+          service_uuid,
+          characteristic_uuid: uuid,
+        })
+        .collect(),
+    }
+  }
+
+  #[test]
+  fn every_descriptor_is_read_with_failures_reported_per_entry() {
+    let characteristic = characteristic_with_descriptors(&[0x2901, 0x2902, 0x2904]);
+    let targets = descriptor_targets(&characteristic, None).unwrap();
+    let results = block_on(read_descriptor_targets("dev", targets, |descriptor| async move {
+      if descriptor.uuid == uuid_from_u16(0x2904) {
+        Err(Error::Btleplug(btleplug::Error::NotConnected))
+      } else {
+        Ok(descriptor.uuid.as_bytes()[2..4].to_vec())
+      }
+    }));
+    assert_eq!(results.len(), 3);
+    let user_description = &results[&format_uuid(&uuid_from_u16(0x2901))];
+    assert_eq!(user_description.value.as_deref(), Some("KQE="));
+    assert!(user_description.error.is_none());
+    let presentation = &results[&format_uuid(&uuid_from_u16(0x2904))];
+    assert!(presentation.value.is_none());
+    assert!(presentation.error.is_some());
+  }
+
+  #[test]
+  fn requested_descriptors_the_characteristic_lacks_are_reported() {
+    let characteristic = characteristic_with_descriptors(&[0x2902]);
+    let requested = ["2902".to_string(), "2901".to_string()];
+    let targets = descriptor_targets(&characteristic, Some(&requested)).unwrap();
+    let results = block_on(read_descriptor_targets("dev", targets, |_| async { Ok(vec![1, 0]) }));
+    assert_eq!(results[&format_uuid(&uuid_from_u16(0x2902))].value.as_deref(), Some("AQA="));
+    let missing = &results[&format_uuid(&uuid_from_u16(0x2901))];
+    assert!(missing.value.is_none());
+    assert!(missing.error.is_some());
+  }
+
+  #[test]
+  fn subscribing_while_disconnected_fails_unless_connecting_is_enabled() {
+    assert!(matches!(
+      connect_before_subscribing("dev", false, false),
+      Err(Error::NotConnected(device_id)) if device_id == "dev"
+    ));
+    assert!(connect_before_subscribing("dev", false, true).unwrap());
+    assert!(!connect_before_subscribing("dev", true, false).unwrap());
+    assert!(!connect_before_subscribing("dev", true, true).unwrap());
+  }
+
+  fn scheme_request(uri: &str) -> tauri::http::Request<Vec<u8>> {
+    tauri::http::Request::builder().uri(uri).body(Vec::new()).unwrap()
+  }
+
+  #[test]
+  fn selection_scheme_serves_the_stored_page() {
+    let store = Arc::new(StdMutex::new(HashMap::from([(
+      "req-1".to_string(),
+      "<html>picker</html>".to_string(),
+    )])));
+    let response = handle_selection_scheme_request(store.clone(), scheme_request("bluetooth-picker://localhost/req-1"));
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()[CONTENT_TYPE], "text/html; charset=utf-8");
+    assert_eq!(response.body().as_slice(), b"<html>picker</html>");
+
+    let missing = handle_selection_scheme_request(store, scheme_request("bluetooth-picker://localhost/req-2"));
+    assert_eq!(missing.status(), StatusCode::NOT_FOUND);
+  }
+
+  #[test]
+  fn characteristic_keys_ignore_how_the_uuid_is_spelled() {
+    let full = characteristic_key("dev", "00002a37-0000-1000-8000-00805f9b34fb").unwrap();
+    assert_eq!(characteristic_key("dev", "heart_rate_measurement").unwrap(), full);
+    assert_eq!(characteristic_key("dev", "2A37").unwrap(), full);
+    assert_ne!(characteristic_key("other", "2a37").unwrap(), full);
+  }
+
+  #[test]
+  fn clearing_a_device_leaves_devices_with_a_longer_id_alone() {
+    let tasks = Mutex::new(HashMap::from([
+      ("ab:2a37".to_string(), tauri::async_runtime::spawn(futures::future::pending::<()>())),
+      ("abc:2a37".to_string(), tauri::async_runtime::spawn(futures::future::pending::<()>())),
+    ]));
+    block_on(clear_notifications_for(&tasks, "ab"));
+    let remaining: Vec<String> = block_on(tasks.lock()).keys().cloned().collect();
+    assert_eq!(remaining, ["abc:2a37"]);
+  }
+
+  #[test]
+  fn selection_names_follow_the_injected_request_id() {
+    let generator: RequestIdGenerator = Arc::new(|| "fixed-id".to_string());
+    let names = SelectionNames::new(&generator, &SelectionPageConfig::new(), None);
+    assert_eq!(names.request_id, "fixed-id");
+    assert_eq!(names.selection_event, "web-bluetooth://select-bluetooth-device/fixed-id");
+    assert_eq!(names.update_event, "web-bluetooth://select-bluetooth-device/fixed-iddevices");
+    assert_eq!(names.window_label, "web-bluetooth-selector-fixed-id");
+
+    let page = SelectionPageConfig::new().window_label_prefix("picker-");
+    assert_eq!(SelectionNames::new(&generator, &page, None).window_label, "picker-fixed-id");
+    let custom = SelectionNames::new(&generator, &page, Some("app-picker".into()));
+    assert_eq!(custom.window_label, "app-picker");
+  }
+
+  #[test]
+  fn secondary_channels_map_to_phy_capabilities() {
+    let channels = |names: &[&str]| names.iter().map(|name| name.to_string()).collect::<Vec<_>>();
+
+    let all = capabilities_from_secondary_channels(&channels(&["1M", "2M", "Coded"]));
+    assert_eq!(all.extended_advertising, Some(true));
+    assert_eq!(all.le_2m_phy, Some(true));
+    assert_eq!(all.coded_phy, Some(true));
+    assert_eq!(all.max_connections, None);
+
+    let no_coded = capabilities_from_secondary_channels(&channels(&["1M", "2M"]));
+    assert_eq!(no_coded.coded_phy, Some(false));
+    assert_eq!(no_coded.le_2m_phy, Some(true));
+
+    let legacy = capabilities_from_secondary_channels(&[]);
+    assert_eq!(legacy.extended_advertising, Some(false));
+    assert_eq!(legacy.le_2m_phy, None);
+    assert_eq!(legacy.coded_phy, None);
+  }
+}
@@ -1,66 +1,237 @@
-use serde::{ser::Serializer, Serialize};
+use serde::{
+  ser::{SerializeStruct, Serializer},
+  Serialize,
+};
 
 pub type Result<T> = std::result::Result<T, Error>;
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
+  /// `UnknownError`
   #[error(transparent)]
   Io(#[from] std::io::Error),
+  /// Depends on the btleplug error: `NotAllowedError`, `NotFoundError`,
+  /// `NetworkError`, `NotSupportedError`, `TimeoutError` or `TypeError`.
   #[error(transparent)]
   Btleplug(#[from] btleplug::Error),
+  /// `TypeError`
   #[error(transparent)]
   UuidParse(#[from] uuid::Error),
+  /// `TypeError`
   #[error(transparent)]
   Base64Decode(#[from] base64::DecodeError),
+  /// `NotFoundError`
   #[error("Bluetooth adapter is not available on this system")]
   NoAdapter,
+  /// `InvalidStateError`
+  #[error("Bluetooth adapter is powered off")]
+  AdapterPoweredOff,
+  /// `InvalidStateError`
+  #[error("Cannot switch adapters while a device request is in progress")]
+  AdapterBusy,
+  /// `NotFoundError`
   #[error("Device {0} not found")]
   DeviceNotFound(String),
+  /// `NetworkError`
+  #[error("Device {0} is not connected")]
+  NotConnected(String),
+  /// `NotFoundError`
+  #[error("No devices matched the provided filters")]
+  NoDevicesFound,
+  /// `NotFoundError`, as for a dismissed Web Bluetooth chooser.
   #[error("Device selection was cancelled by the user")]
   SelectionCancelled,
+  /// `NotFoundError`
   #[error("Service {service_uuid} not found for device {device_id}")]
   ServiceNotFound {
     device_id: String,
     service_uuid: String,
   },
+  /// `NotFoundError`
   #[error("Characteristic {characteristic_uuid} not found for device {device_id}")]
   CharacteristicNotFound {
     device_id: String,
     characteristic_uuid: String,
   },
+  /// `NotFoundError`
   #[error("Descriptor {descriptor_uuid} not found for device {device_id}")]
   DescriptorNotFound {
     device_id: String,
     descriptor_uuid: String,
   },
+  /// `SecurityError`
+  #[error("Service {service_uuid} on device {device_id} is not allowed; list it in filters or optionalServices")]
+  SecurityError {
+    device_id: String,
+    service_uuid: String,
+  },
+  /// `TypeError`
   #[error("{0}")]
   InvalidRequest(String),
+  /// `TimeoutError`
+  #[error("Operation timed out")]
+  OperationTimeout,
+  /// `DataError`
   #[error(transparent)]
   Json(#[from] serde_json::Error),
+  /// `InvalidStateError`
   #[error("Notifications already active for {characteristic_uuid} on device {device_id}")]
   NotificationsAlreadyActive {
     device_id: String,
     characteristic_uuid: String,
   },
+  /// `InvalidStateError`
   #[error("Notifications not active for {characteristic_uuid} on device {device_id}")]
   NotificationsNotActive {
     device_id: String,
     characteristic_uuid: String,
   },
+  /// `NotSupportedError`
   #[error("Web Bluetooth is not implemented for this platform yet")]
   UnsupportedPlatform,
+  /// `UnknownError`
   #[error(transparent)]
   Tauri(#[from] tauri::Error),
+  /// `UnknownError`
   #[cfg(mobile)]
   #[error(transparent)]
   PluginInvoke(#[from] tauri::plugin::mobile::PluginInvokeError),
 }
 
+impl Error {
+  /// The DOMException name Web Bluetooth would reject with for this error,
+  /// so frontends can branch on `error.name` as they would in a browser.
+  pub fn name(&self) -> &'static str {
+    match self {
+      Self::Btleplug(err) => match err {
+        btleplug::Error::PermissionDenied => "NotAllowedError",
+        btleplug::Error::DeviceNotFound | btleplug::Error::NoSuchCharacteristic => "NotFoundError",
+        btleplug::Error::NotSupported(_) => "NotSupportedError",
+        btleplug::Error::TimedOut(_) => "TimeoutError",
+        btleplug::Error::Uuid(_) | btleplug::Error::InvalidBDAddr(_) => "TypeError",
+        _ => "NetworkError",
+      },
+      Self::UuidParse(_) | Self::Base64Decode(_) | Self::InvalidRequest(_) => "TypeError",
+      Self::NoAdapter
+      | Self::DeviceNotFound(_)
+      | Self::NoDevicesFound
+      | Self::SelectionCancelled
+      | Self::ServiceNotFound { .. }
+      | Self::CharacteristicNotFound { .. }
+      | Self::DescriptorNotFound { .. } => "NotFoundError",
+      Self::AdapterPoweredOff
+      | Self::AdapterBusy
+      | Self::NotificationsAlreadyActive { .. }
+      | Self::NotificationsNotActive { .. } => "InvalidStateError",
+      Self::NotConnected(_) => "NetworkError",
+      Self::SecurityError { .. } => "SecurityError",
+      Self::OperationTimeout => "TimeoutError",
+      Self::Json(_) => "DataError",
+      Self::UnsupportedPlatform => "NotSupportedError",
+      Self::Io(_) | Self::Tauri(_) => "UnknownError",
+      #[cfg(mobile)]
+      Self::PluginInvoke(_) => "UnknownError",
+    }
+  }
+}
+
+/// Serialized as `{ name, message }`; `Display` stays the plain message used
+/// in logs.
 impl Serialize for Error {
   fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
   where
     S: Serializer,
   {
-    serializer.serialize_str(self.to_string().as_ref())
+    let mut state = serializer.serialize_struct("Error", 2)?;
+    state.serialize_field("name", self.name())?;
+    state.serialize_field("message", &self.to_string())?;
+    state.end()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn serialized(err: Error) -> serde_json::Value {
+    serde_json::to_value(err).unwrap()
+  }
+
+  #[test]
+  fn every_variant_serializes_to_name_and_message() {
+    let device_id = || "device".to_string();
+    let uuid = || "0000180f-0000-1000-8000-00805f9b34fb".to_string();
+    let cases = [
+      (Error::Io(std::io::Error::other("disk")), "UnknownError"),
+      (Error::Btleplug(btleplug::Error::PermissionDenied), "NotAllowedError"),
+      (Error::Btleplug(btleplug::Error::DeviceNotFound), "NotFoundError"),
+      (Error::Btleplug(btleplug::Error::NotSupported("x".into())), "NotSupportedError"),
+      (
+        Error::Btleplug(btleplug::Error::TimedOut(std::time::Duration::from_secs(1))),
+        "TimeoutError",
+      ),
+      (Error::Btleplug(btleplug::Error::NotConnected), "NetworkError"),
+      (Error::UuidParse(uuid::Uuid::parse_str("nope").unwrap_err()), "TypeError"),
+      (Error::Base64Decode(base64::DecodeError::InvalidLength(1)), "TypeError"),
+      (Error::NoAdapter, "NotFoundError"),
+      (Error::AdapterPoweredOff, "InvalidStateError"),
+      (Error::AdapterBusy, "InvalidStateError"),
+      (Error::DeviceNotFound(device_id()), "NotFoundError"),
+      (Error::NotConnected(device_id()), "NetworkError"),
+      (Error::NoDevicesFound, "NotFoundError"),
+      (Error::SelectionCancelled, "NotFoundError"),
+      (
+        Error::ServiceNotFound {
+          device_id: device_id(),
+          service_uuid: uuid(),
+        },
+        "NotFoundError",
+      ),
+      (
+        Error::CharacteristicNotFound {
+          device_id: device_id(),
+          characteristic_uuid: uuid(),
+        },
+        "NotFoundError",
+      ),
+      (
+        Error::DescriptorNotFound {
+          device_id: device_id(),
+          descriptor_uuid: uuid(),
+        },
+        "NotFoundError",
+      ),
+      (
+        Error::SecurityError {
+          device_id: device_id(),
+          service_uuid: uuid(),
+        },
+        "SecurityError",
+      ),
+      (Error::InvalidRequest("bad".into()), "TypeError"),
+      (Error::OperationTimeout, "TimeoutError"),
+      (Error::Json(serde_json::from_str::<u8>("x").unwrap_err()), "DataError"),
+      (
+        Error::NotificationsAlreadyActive {
+          device_id: device_id(),
+          characteristic_uuid: uuid(),
+        },
+        "InvalidStateError",
+      ),
+      (
+        Error::NotificationsNotActive {
+          device_id: device_id(),
+          characteristic_uuid: uuid(),
+        },
+        "InvalidStateError",
+      ),
+      (Error::UnsupportedPlatform, "NotSupportedError"),
+      (Error::Tauri(tauri::Error::InvalidWindowHandle), "UnknownError"),
+    ];
+    for (err, name) in cases {
+      let message = err.to_string();
+      let value = serialized(err);
+      assert_eq!(value, serde_json::json!({ "name": name, "message": message }));
+    }
   }
 }
@@ -0,0 +1,199 @@
+/// Resolves a Web Bluetooth GATT name, such as `heart_rate` or
+/// `battery_level`, to its 16-bit assigned number. Covers the common SIG
+/// services, characteristics and descriptors. Names are looked up without
+/// knowing what kind of attribute is expected, so `current_time` resolves to
+/// the service; the characteristic of the same name needs `0x2a2b`.
+pub(crate) fn standard_uuid(name: &str) -> Option<u16> {
+  let uuid = match name {
+    // Services
+    "generic_access" => 0x1800,
+    "generic_attribute" => 0x1801,
+    "immediate_alert" => 0x1802,
+    "link_loss" => 0x1803,
+    "tx_power" => 0x1804,
+    "current_time" => 0x1805,
+    "reference_time_update" => 0x1806,
+    "next_dst_change" => 0x1807,
+    "glucose" => 0x1808,
+    "health_thermometer" => 0x1809,
+    "device_information" => 0x180A,
+    "heart_rate" => 0x180D,
+    "phone_alert_status" => 0x180E,
+    "battery_service" => 0x180F,
+    "blood_pressure" => 0x1810,
+    "alert_notification" => 0x1811,
+    "human_interface_device" => 0x1812,
+    "scan_parameters" => 0x1813,
+    "running_speed_and_cadence" => 0x1814,
+    "automation_io" => 0x1815,
+    "cycling_speed_and_cadence" => 0x1816,
+    "cycling_power" => 0x1818,
+    "location_and_navigation" => 0x1819,
+    "environmental_sensing" => 0x181A,
+    "body_composition" => 0x181B,
+    "user_data" => 0x181C,
+    "weight_scale" => 0x181D,
+    "bond_management" => 0x181E,
+    "continuous_glucose_monitoring" => 0x181F,
+    "internet_protocol_support" => 0x1820,
+    "indoor_positioning" => 0x1821,
+    "pulse_oximeter" => 0x1822,
+    "http_proxy" => 0x1823,
+    "transport_discovery" => 0x1824,
+    "object_transfer" => 0x1825,
+    "fitness_machine" => 0x1826,
+    "mesh_provisioning" => 0x1827,
+    "mesh_proxy" => 0x1828,
+    "reconnection_configuration" => 0x1829,
+    // Characteristics
+    "gap.device_name" => 0x2A00,
+    "gap.appearance" => 0x2A01,
+    "gap.peripheral_privacy_flag" => 0x2A02,
+    "gap.reconnection_address" => 0x2A03,
+    "gap.peripheral_preferred_connection_parameters" => 0x2A04,
+    "gatt.service_changed" => 0x2A05,
+    "alert_level" => 0x2A06,
+    "tx_power_level" => 0x2A07,
+    "date_time" => 0x2A08,
+    "day_of_week" => 0x2A09,
+    "day_date_time" => 0x2A0A,
+    "exact_time_256" => 0x2A0C,
+    "dst_offset" => 0x2A0D,
+    "time_zone" => 0x2A0E,
+    "local_time_information" => 0x2A0F,
+    "time_with_dst" => 0x2A11,
+    "time_accuracy" => 0x2A12,
+    "time_source" => 0x2A13,
+    "reference_time_information" => 0x2A14,
+    "time_update_control_point" => 0x2A16,
+    "time_update_state" => 0x2A17,
+    "glucose_measurement" => 0x2A18,
+    "battery_level" => 0x2A19,
+    "temperature_measurement" => 0x2A1C,
+    "temperature_type" => 0x2A1D,
+    "intermediate_temperature" => 0x2A1E,
+    "measurement_interval" => 0x2A21,
+    "boot_keyboard_input_report" => 0x2A22,
+    "system_id" => 0x2A23,
+    "model_number_string" => 0x2A24,
+    "serial_number_string" => 0x2A25,
+    "firmware_revision_string" => 0x2A26,
+    "hardware_revision_string" => 0x2A27,
+    "software_revision_string" => 0x2A28,
+    "manufacturer_name_string" => 0x2A29,
+    "ieee_11073-20601_regulatory_certification_data_list" => 0x2A2A,
+    "scan_refresh" => 0x2A31,
+    "boot_keyboard_output_report" => 0x2A32,
+    "boot_mouse_input_report" => 0x2A33,
+    "glucose_measurement_context" => 0x2A34,
+    "blood_pressure_measurement" => 0x2A35,
+    "intermediate_cuff_pressure" => 0x2A36,
+    "heart_rate_measurement" => 0x2A37,
+    "body_sensor_location" => 0x2A38,
+    "heart_rate_control_point" => 0x2A39,
+    "alert_status" => 0x2A3F,
+    "ringer_control_point" => 0x2A40,
+    "ringer_setting" => 0x2A41,
+    "alert_category_id_bit_mask" => 0x2A42,
+    "alert_category_id" => 0x2A43,
+    "alert_notification_control_point" => 0x2A44,
+    "unread_alert_status" => 0x2A45,
+    "new_alert" => 0x2A46,
+    "supported_new_alert_category" => 0x2A47,
+    "supported_unread_alert_category" => 0x2A48,
+    "blood_pressure_feature" => 0x2A49,
+    "hid_information" => 0x2A4A,
+    "report_map" => 0x2A4B,
+    "hid_control_point" => 0x2A4C,
+    "report" => 0x2A4D,
+    "protocol_mode" => 0x2A4E,
+    "scan_interval_window" => 0x2A4F,
+    "pnp_id" => 0x2A50,
+    "glucose_feature" => 0x2A51,
+    "record_access_control_point" => 0x2A52,
+    "rsc_measurement" => 0x2A53,
+    "rsc_feature" => 0x2A54,
+    "sc_control_point" => 0x2A55,
+    "csc_measurement" => 0x2A5B,
+    "csc_feature" => 0x2A5C,
+    "sensor_location" => 0x2A5D,
+    "plx_spot_check_measurement" => 0x2A5E,
+    "plx_continuous_measurement" => 0x2A5F,
+    "plx_features" => 0x2A60,
+    "cycling_power_measurement" => 0x2A63,
+    "cycling_power_vector" => 0x2A64,
+    "cycling_power_feature" => 0x2A65,
+    "cycling_power_control_point" => 0x2A66,
+    "location_and_speed" => 0x2A67,
+    "navigation" => 0x2A68,
+    "position_quality" => 0x2A69,
+    "ln_feature" => 0x2A6A,
+    "ln_control_point" => 0x2A6B,
+    "elevation" => 0x2A6C,
+    "pressure" => 0x2A6D,
+    "temperature" => 0x2A6E,
+    "humidity" => 0x2A6F,
+    "true_wind_speed" => 0x2A70,
+    "true_wind_direction" => 0x2A71,
+    "apparent_wind_speed" => 0x2A72,
+    "apparent_wind_direction" => 0x2A73,
+    "gust_factor" => 0x2A74,
+    "pollen_concentration" => 0x2A75,
+    "uv_index" => 0x2A76,
+    "irradiance" => 0x2A77,
+    "rainfall" => 0x2A78,
+    "wind_chill" => 0x2A79,
+    "heat_index" => 0x2A7A,
+    "dew_point" => 0x2A7B,
+    "descriptor_value_changed" => 0x2A7D,
+    "weight" => 0x2A98,
+    "body_composition_feature" => 0x2A9B,
+    "body_composition_measurement" => 0x2A9C,
+    "weight_measurement" => 0x2A9D,
+    "weight_scale_feature" => 0x2A9E,
+    "central_address_resolution" => 0x2AA6,
+    "fitness_machine_feature" => 0x2ACC,
+    "treadmill_data" => 0x2ACD,
+    "indoor_bike_data" => 0x2AD2,
+    "training_status" => 0x2AD3,
+    "supported_speed_range" => 0x2AD4,
+    "fitness_machine_control_point" => 0x2AD9,
+    "fitness_machine_status" => 0x2ADA,
+    // Descriptors
+    "gatt.characteristic_extended_properties" => 0x2900,
+    "gatt.characteristic_user_description" => 0x2901,
+    "gatt.client_characteristic_configuration" => 0x2902,
+    "gatt.server_characteristic_configuration" => 0x2903,
+    "gatt.characteristic_presentation_format" => 0x2904,
+    "gatt.characteristic_aggregate_format" => 0x2905,
+    "valid_range" => 0x2906,
+    "external_report_reference" => 0x2907,
+    "report_reference" => 0x2908,
+    "number_of_digitals" => 0x2909,
+    "value_trigger_setting" => 0x290A,
+    "es_configuration" => 0x290B,
+    "es_measurement" => 0x290C,
+    "es_trigger_setting" => 0x290D,
+    "time_trigger_setting" => 0x290E,
+    _ => return None,
+  };
+  Some(uuid)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn standard_names_resolve_to_assigned_numbers() {
+    assert_eq!(standard_uuid("heart_rate"), Some(0x180D));
+    assert_eq!(standard_uuid("battery_level"), Some(0x2A19));
+    assert_eq!(standard_uuid("heart_rate_measurement"), Some(0x2A37));
+  }
+
+  #[test]
+  fn unknown_names_resolve_to_none() {
+    assert_eq!(standard_uuid("heart-rate"), None);
+    assert_eq!(standard_uuid("180d"), None);
+  }
+}
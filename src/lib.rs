@@ -12,18 +12,38 @@ mod desktop;
 #[cfg(mobile)]
 mod mobile;
 
+mod appearance;
 mod commands;
+#[cfg(desktop)]
+mod company_ids;
+#[cfg(desktop)]
+mod descriptors;
+#[cfg(desktop)]
+mod gatt_names;
 mod error;
 mod models;
+#[cfg(all(desktop, feature = "scan-replay"))]
+mod scan_replay;
+#[cfg(desktop)]
+mod value_format;
 
 pub use error::{Error, Result};
 
 #[cfg(desktop)]
 pub use desktop::{
+  AdapterSelector,
+  DeviceIdStrategy,
+  EmptyScanBehavior,
   DeviceSelectionContext,
   DeviceSelectionHandler,
+  EventSelectionHandler,
+  IndexSelectionHandler,
   NativeDialogSelectionHandler,
+  ReconnectPolicy,
+  SelectionDialogStrings,
+  SelectionDialogTheme,
   SelectionHandler,
+  SelectionPageConfig,
 };
 
 #[cfg(desktop)]
@@ -44,25 +64,312 @@ impl<R: Runtime, T: Manager<R>> crate::WebBluetoothExt<R> for T {
 
 /// Initializes the plugin.
 pub fn init<R: Runtime>() -> TauriPlugin<R> {
-  init_with_config(InitConfig::<R>::default())
+  PluginConfig::<R>::default().build()
 }
 
 /// Initializes the plugin with a custom device selection handler on desktop targets.
 #[cfg(desktop)]
 pub fn init_with_selection_handler<R: Runtime>(selection_handler: SelectionHandler<R>) -> TauriPlugin<R> {
-  init_with_config(InitConfig { selection_handler })
+  PluginConfig::default().selection_handler(selection_handler).build()
+}
+
+/// Configures the plugin before it is registered with the app.
+///
+/// ```rust,ignore
+/// tauri_plugin_web_bluetooth::PluginConfig::default()
+///   .device_id_strategy(tauri_plugin_web_bluetooth::DeviceIdStrategy::Address)
+///   .build()
+/// ```
+pub struct PluginConfig<R: Runtime> {
+  config: InitConfig<R>,
+}
+
+impl<R: Runtime> Default for PluginConfig<R> {
+  fn default() -> Self {
+    Self {
+      config: InitConfig::default(),
+    }
+  }
+}
+
+impl<R: Runtime> PluginConfig<R> {
+  /// Sets the handler that picks a device in `request_device`.
+  #[cfg(desktop)]
+  pub fn selection_handler(mut self, selection_handler: SelectionHandler<R>) -> Self {
+    self.config.selection_handler = selection_handler;
+    self
+  }
+
+  /// Sets how device identifiers are derived. Defaults to [`DeviceIdStrategy::Stable`].
+  #[cfg(desktop)]
+  pub fn device_id_strategy(mut self, strategy: DeviceIdStrategy) -> Self {
+    self.config.device_id_strategy = strategy;
+    self
+  }
+
+  /// Remembers the devices granted through `request_device` in the app data
+  /// directory, with their services and names, so `get_devices` lists them
+  /// after a restart and they can be used without picking them again, like
+  /// Chrome's persistent permissions. `forget_device` revokes a grant. Off
+  /// by default.
+  #[cfg(desktop)]
+  pub fn persist_granted_devices(mut self, enabled: bool) -> Self {
+    self.config.persist_granted_devices = enabled;
+    self
+  }
+
+  /// Keeps the salt of [`DeviceIdStrategy::Stable`] ids in `path` instead of
+  /// `web-bluetooth-device-id-salt` in the app data directory. The salt is a
+  /// random value created on first launch, so ids stay stable for this
+  /// install but cannot be correlated with other installs. Moving it to a new
+  /// path changes every id once.
+  #[cfg(desktop)]
+  pub fn device_id_salt_store(mut self, path: impl Into<std::path::PathBuf>) -> Self {
+    self.config.device_id_salt_store = Some(path.into());
+    self
+  }
+
+  /// While the streaming picker is open, starts connecting to the matching
+  /// device with the strongest signal so the connection is ready if the user
+  /// picks it; otherwise it is disconnected. Off by default because it uses
+  /// the radio on a device the user may not choose.
+  #[cfg(desktop)]
+  pub fn prewarm_connection(mut self, enabled: bool) -> Self {
+    self.config.prewarm_connection = enabled;
+    self
+  }
+
+  /// Lets `start_notifications` connect a device that is not connected yet
+  /// instead of failing with `NotConnected`. Off by default, matching Web
+  /// Bluetooth, where subscribing needs a connected GATT server.
+  #[cfg(desktop)]
+  pub fn connect_on_subscribe(mut self, enabled: bool) -> Self {
+    self.config.connect_on_subscribe = enabled;
+    self
+  }
+
+  /// Caps how many cached devices `get_devices` queries concurrently. Defaults
+  /// to [`DEFAULT_DEVICE_QUERY_CONCURRENCY`].
+  #[cfg(desktop)]
+  pub fn device_query_concurrency(mut self, limit: usize) -> Self {
+    self.config.device_query_concurrency = limit.max(1);
+    self
+  }
+
+  /// Bounds the per-subscription queue between a notification stream and the
+  /// event emitter, and picks what happens when it fills up. Defaults to
+  /// [`DEFAULT_NOTIFICATION_QUEUE_CAPACITY`] and [`NotificationOverflowPolicy::DropOldest`].
+  #[cfg(desktop)]
+  pub fn notification_queue(mut self, capacity: usize, policy: NotificationOverflowPolicy) -> Self {
+    self.config.notification_queue_capacity = capacity.max(1);
+    self.config.notification_overflow_policy = policy;
+    self
+  }
+
+  /// Chooses how `start_notifications` treats an already active subscription
+  /// when the request does not say. Defaults to [`DuplicateNotificationPolicy::Error`].
+  #[cfg(desktop)]
+  pub fn duplicate_notification_policy(mut self, policy: DuplicateNotificationPolicy) -> Self {
+    self.config.duplicate_notification_policy = policy;
+    self
+  }
+
+  /// Renames the custom URI scheme, host and window labels used by the
+  /// built-in picker page. Defaults to `web-bluetooth-selector://dialog/{id}`
+  /// in windows labelled `web-bluetooth-selector-{id}`.
+  #[cfg(desktop)]
+  pub fn selection_page(mut self, selection_page: SelectionPageConfig) -> Self {
+    self.config.selection_page = selection_page;
+    self
+  }
+
+  /// Chooses whether `request_device` gives up or keeps scanning when the scan
+  /// window ends with no matches. Defaults to [`EmptyScanBehavior::Close`].
+  #[cfg(desktop)]
+  pub fn empty_scan_behavior(mut self, behavior: EmptyScanBehavior) -> Self {
+    self.config.empty_scan_behavior = behavior;
+    self
+  }
+
+  /// Cuts each manufacturer and service data blob to `max_len` bytes in
+  /// device and advertisement payloads, marking them `truncated`, to keep IPC
+  /// small in beacon-heavy environments. Filters still see the full data.
+  /// Unlimited by default.
+  #[cfg(desktop)]
+  pub fn max_advertisement_data_len(mut self, max_len: usize) -> Self {
+    self.config.max_advertisement_data_len = Some(max_len);
+    self
+  }
+
+  /// Splits characteristic writes longer than `chunk_size` bytes into
+  /// sequential writes, for stacks that reject values beyond the MTU. This is
+  /// the fallback for when no MTU is known: on BlueZ, `get_mtu`, `refresh_mtu`
+  /// and `connect_gatt` store the MTU, which then bounds writes without
+  /// response instead. 20 bytes fits the minimum ATT MTU. Off by default.
+  #[cfg(desktop)]
+  pub fn write_chunk_size(mut self, chunk_size: usize) -> Self {
+    self.config.write_chunk_size = Some(chunk_size.max(1));
+    self
+  }
+
+  /// Caps how many peripherals stay cached, evicting the least recently used
+  /// disconnected ones first. Evicted devices are reloaded from the adapter
+  /// on their next use. Unbounded by default.
+  #[cfg(desktop)]
+  pub fn max_cached_devices(mut self, max: usize) -> Self {
+    self.config.max_cached_devices = Some(max);
+    self
+  }
+
+  /// Starts the adapter event listener at startup. Defaults to `true`; apps
+  /// that never need disconnect events can turn it off and call
+  /// `start_event_monitor` later.
+  #[cfg(desktop)]
+  pub fn event_monitor(mut self, enabled: bool) -> Self {
+    self.config.event_monitor = enabled;
+    self
+  }
+
+  /// Stops every characteristic subscription, monitor and poll once no
+  /// frontend listener for value events has been alive for `grace`, so a
+  /// page that navigates away without `stop_notifications` does not leak
+  /// radio traffic. Listeners registered through the guest bindings report
+  /// themselves with heartbeats. Off by default.
+  #[cfg(desktop)]
+  pub fn auto_unsubscribe(mut self, grace: std::time::Duration) -> Self {
+    self.config.auto_unsubscribe_grace = Some(grace);
+    self
+  }
+
+  /// Reconnects devices connected with `connect_gatt` when they drop
+  /// unexpectedly, then restores their notification subscriptions. Emits
+  /// `gattserver-connected` on success and `reconnect-failed` once the
+  /// policy's attempts run out. Off by default.
+  #[cfg(desktop)]
+  pub fn auto_reconnect(mut self, policy: ReconnectPolicy) -> Self {
+    self.config.reconnect_policy = Some(policy);
+    self
+  }
+
+  /// Picks the adapter used at startup: the first one whose `adapter_info`
+  /// string satisfies `predicate`, e.g. `|info| info.contains("USB")`. Falls
+  /// back to the first adapter when none matches. Shorthand for
+  /// `adapter(AdapterSelector::preferring(predicate))`.
+  #[cfg(desktop)]
+  pub fn preferred_adapter<F>(mut self, predicate: F) -> Self
+  where
+    F: Fn(&str) -> bool + Send + Sync + 'static,
+  {
+    self.config.adapter_selector = Some(AdapterSelector::preferring(predicate));
+    self
+  }
+
+  /// Picks the adapter used at startup by index, address, name or a closure
+  /// over `get_adapters`' list. Initialization fails with `Error::NoAdapter`
+  /// when nothing matches. Replaces a selector set by `preferred_adapter`,
+  /// and vice versa.
+  #[cfg(desktop)]
+  pub fn adapter(mut self, selector: AdapterSelector) -> Self {
+    self.config.adapter_selector = Some(selector);
+    self
+  }
+
+  /// Fills `BluetoothDevice::manufacturer_name` from the company identifier in
+  /// the device's manufacturer data, using a bundled table of common vendors.
+  /// Off by default.
+  #[cfg(desktop)]
+  pub fn manufacturer_names(mut self, enabled: bool) -> Self {
+    self.config.manufacturer_names = enabled;
+    self
+  }
+
+  /// Enables the `get_debug_snapshot` command. Defaults to on in debug builds
+  /// and off in release builds.
+  #[cfg(desktop)]
+  pub fn debug_snapshot(mut self, enabled: bool) -> Self {
+    self.config.debug_snapshot = enabled;
+    self
+  }
+
+  /// Registers application-defined UUID names at startup, the same as calling
+  /// `register_uuid_aliases` from the frontend. Keys are UUID strings.
+  #[cfg(desktop)]
+  pub fn uuid_aliases<I, K, V>(mut self, aliases: I) -> Self
+  where
+    I: IntoIterator<Item = (K, V)>,
+    K: Into<String>,
+    V: Into<String>,
+  {
+    self
+      .config
+      .uuid_aliases
+      .extend(aliases.into_iter().map(|(uuid, name)| (uuid.into(), name.into())));
+    self
+  }
+
+  /// Writes every `request_device` scan to `path` as JSON, overwriting the
+  /// previous one, so it can be fed back through the picker with
+  /// `replay_scan`.
+  #[cfg(all(desktop, feature = "scan-replay"))]
+  pub fn record_scan(mut self, path: impl Into<std::path::PathBuf>) -> Self {
+    self.config.scan_recording = Some(path.into());
+    self
+  }
+
+  /// Lets `replay_scan` load recordings from `dir`. Without it only the
+  /// `record_scan` file can be replayed.
+  #[cfg(all(desktop, feature = "scan-replay"))]
+  pub fn replay_scan_dir(mut self, dir: impl Into<std::path::PathBuf>) -> Self {
+    self.config.scan_replay_dir = Some(dir.into());
+    self
+  }
+
+  /// Also emits every device event on `web-bluetooth://event`, wrapped in an
+  /// `EventEnvelope` of `{ kind, deviceId, timestampMs, data }`, so the
+  /// frontend can handle them all with one listener. The individual events
+  /// are still emitted. Off by default.
+  #[cfg(desktop)]
+  pub fn unified_events(mut self, enabled: bool) -> Self {
+    self.config.unified_events = enabled;
+    self
+  }
+
+  /// Persists the device returned by `get_last_device` to `path` as JSON, so
+  /// a "reconnect last device" button keeps working across restarts.
+  /// Without it the last device is only remembered for the session.
+  #[cfg(desktop)]
+  pub fn last_device_store(mut self, path: impl Into<std::path::PathBuf>) -> Self {
+    self.config.last_device_store = Some(path.into());
+    self
+  }
+
+  /// Replaces the random UUIDs used to name `request_device` selection events
+  /// and windows, so tests can assert exact names.
+  #[cfg(all(desktop, any(test, feature = "test-utils")))]
+  pub fn request_id_generator<F>(mut self, generator: F) -> Self
+  where
+    F: Fn() -> String + Send + Sync + 'static,
+  {
+    self.config.request_id_generator = std::sync::Arc::new(generator);
+    self
+  }
+
+  /// Builds the Tauri plugin.
+  pub fn build(self) -> TauriPlugin<R> {
+    init_with_config(self.config)
+  }
 }
 
 fn init_with_config<R: Runtime>(config: InitConfig<R>) -> TauriPlugin<R> {
   let builder = Builder::new("web-bluetooth").invoke_handler(commands::handlers());
   #[cfg(desktop)]
-  let builder = desktop::register_selection_scheme_protocol(builder);
+  let builder = desktop::register_selection_scheme_protocol(builder, &config.selection_page);
   builder
     .setup(move |app, api| {
       #[cfg(mobile)]
       let web_bluetooth = mobile::init(app, api)?;
       #[cfg(desktop)]
-      let web_bluetooth = desktop::init(app, api, config.selection_handler.clone())?;
+      let web_bluetooth = desktop::init(app, api, config)?;
       app.manage(web_bluetooth);
       Ok(())
     })
@@ -70,8 +377,36 @@ fn init_with_config<R: Runtime>(config: InitConfig<R>) -> TauriPlugin<R> {
 }
 
 #[cfg(desktop)]
-struct InitConfig<R: Runtime> {
-  selection_handler: SelectionHandler<R>,
+pub(crate) struct InitConfig<R: Runtime> {
+  pub(crate) selection_handler: SelectionHandler<R>,
+  pub(crate) device_id_strategy: DeviceIdStrategy,
+  pub(crate) device_id_salt_store: Option<std::path::PathBuf>,
+  pub(crate) persist_granted_devices: bool,
+  pub(crate) device_query_concurrency: usize,
+  pub(crate) prewarm_connection: bool,
+  pub(crate) connect_on_subscribe: bool,
+  pub(crate) notification_queue_capacity: usize,
+  pub(crate) notification_overflow_policy: NotificationOverflowPolicy,
+  pub(crate) duplicate_notification_policy: DuplicateNotificationPolicy,
+  pub(crate) request_id_generator: desktop::RequestIdGenerator,
+  pub(crate) empty_scan_behavior: EmptyScanBehavior,
+  pub(crate) uuid_aliases: std::collections::HashMap<String, String>,
+  pub(crate) max_cached_devices: Option<usize>,
+  pub(crate) write_chunk_size: Option<usize>,
+  pub(crate) max_advertisement_data_len: Option<usize>,
+  pub(crate) debug_snapshot: bool,
+  pub(crate) event_monitor: bool,
+  pub(crate) manufacturer_names: bool,
+  pub(crate) adapter_selector: Option<AdapterSelector>,
+  pub(crate) auto_unsubscribe_grace: Option<std::time::Duration>,
+  pub(crate) reconnect_policy: Option<ReconnectPolicy>,
+  pub(crate) selection_page: SelectionPageConfig,
+  pub(crate) last_device_store: Option<std::path::PathBuf>,
+  pub(crate) unified_events: bool,
+  #[cfg(feature = "scan-replay")]
+  pub(crate) scan_recording: Option<std::path::PathBuf>,
+  #[cfg(feature = "scan-replay")]
+  pub(crate) scan_replay_dir: Option<std::path::PathBuf>,
 }
 
 #[cfg(desktop)]
@@ -79,12 +414,40 @@ impl<R: Runtime> Default for InitConfig<R> {
   fn default() -> Self {
     Self {
       selection_handler: SelectionHandler::default(),
+      device_id_strategy: DeviceIdStrategy::default(),
+      device_id_salt_store: None,
+      persist_granted_devices: false,
+      device_query_concurrency: DEFAULT_DEVICE_QUERY_CONCURRENCY,
+      prewarm_connection: false,
+      connect_on_subscribe: false,
+      notification_queue_capacity: DEFAULT_NOTIFICATION_QUEUE_CAPACITY,
+      notification_overflow_policy: NotificationOverflowPolicy::default(),
+      duplicate_notification_policy: DuplicateNotificationPolicy::default(),
+      request_id_generator: desktop::random_request_id_generator(),
+      empty_scan_behavior: EmptyScanBehavior::default(),
+      uuid_aliases: Default::default(),
+      max_cached_devices: None,
+      write_chunk_size: None,
+      max_advertisement_data_len: None,
+      debug_snapshot: cfg!(debug_assertions),
+      event_monitor: true,
+      manufacturer_names: false,
+      adapter_selector: None,
+      auto_unsubscribe_grace: None,
+      reconnect_policy: None,
+      selection_page: SelectionPageConfig::default(),
+      #[cfg(feature = "scan-replay")]
+      scan_recording: None,
+      #[cfg(feature = "scan-replay")]
+      scan_replay_dir: None,
+      last_device_store: None,
+      unified_events: false,
     }
   }
 }
 
 #[cfg(not(desktop))]
-struct InitConfig<R: Runtime>(PhantomData<R>);
+pub(crate) struct InitConfig<R: Runtime>(PhantomData<R>);
 
 #[cfg(not(desktop))]
 impl<R: Runtime> Default for InitConfig<R> {
@@ -1,5 +1,8 @@
+use std::collections::HashMap;
+
 use serde::de::DeserializeOwned;
 use tauri::{
+  ipc::Channel,
   plugin::{PluginApi, PluginHandle},
   AppHandle, Runtime,
 };
@@ -37,7 +40,7 @@ impl<R: Runtime> WebBluetooth<R> {
     Err(Error::UnsupportedPlatform)
   }
 
-  pub async fn connect_gatt(&self, _request: DeviceRequest) -> Result<GattServerInfo> {
+  pub async fn connect_gatt(&self, _request: ConnectRequest) -> Result<GattServerInfo> {
     Err(Error::UnsupportedPlatform)
   }
 
@@ -72,4 +75,275 @@ impl<R: Runtime> WebBluetooth<R> {
   pub async fn stop_notifications(&self, _request: NotificationRequest) -> Result<()> {
     Err(Error::UnsupportedPlatform)
   }
+
+  pub async fn refresh_gatt(&self, _request: DeviceRequest) -> Result<GattServerInfo> {
+    Err(Error::UnsupportedPlatform)
+  }
+
+  pub async fn monitor_characteristic(&self, _request: MonitorRequest) -> Result<MonitorInfo> {
+    Err(Error::UnsupportedPlatform)
+  }
+
+  pub async fn stop_monitoring(&self, _request: NotificationRequest) -> Result<()> {
+    Err(Error::UnsupportedPlatform)
+  }
+
+  pub async fn get_adapter_capabilities(&self) -> Result<AdapterCapabilities> {
+    Err(Error::UnsupportedPlatform)
+  }
+
+  pub async fn get_bonded_devices(&self) -> Result<Vec<BluetoothDevice>> {
+    Err(Error::UnsupportedPlatform)
+  }
+
+  pub async fn set_preferred_phy(&self, _request: SetPhyRequest) -> Result<()> {
+    Err(Error::UnsupportedPlatform)
+  }
+
+  pub async fn get_phy(&self, _request: DeviceRequest) -> Result<PhyInfo> {
+    Err(Error::UnsupportedPlatform)
+  }
+
+  pub async fn write_sequence(&self, _request: WriteSequenceRequest) -> Result<WriteSequenceResult> {
+    Err(Error::UnsupportedPlatform)
+  }
+
+  pub async fn get_last_selection(&self) -> Result<Option<SelectionInfo>> {
+    Err(Error::UnsupportedPlatform)
+  }
+
+  pub async fn get_notification_stats(&self) -> Result<NotificationStats> {
+    Err(Error::UnsupportedPlatform)
+  }
+
+  pub async fn read_appearance(&self, _request: DeviceRequest) -> Result<Appearance> {
+    Err(Error::UnsupportedPlatform)
+  }
+
+  pub async fn release_device(&self, _request: DeviceRequest) -> Result<()> {
+    Err(Error::UnsupportedPlatform)
+  }
+
+  pub async fn read_and_emit(&self, _request: ReadValueRequest) -> Result<BluetoothValue> {
+    Err(Error::UnsupportedPlatform)
+  }
+
+  pub async fn connect_matching(&self, _options: RequestDeviceOptions) -> Result<GattServerInfo> {
+    Err(Error::UnsupportedPlatform)
+  }
+
+  pub async fn get_selection_info(&self) -> Result<SelectionHandlerInfo> {
+    Err(Error::UnsupportedPlatform)
+  }
+
+  pub async fn read_across_devices(&self, _request: BatchReadRequest) -> Result<Vec<BatchReadResult>> {
+    Err(Error::UnsupportedPlatform)
+  }
+
+  pub async fn register_uuid_aliases(&self, _request: RegisterUuidAliasesRequest) -> Result<()> {
+    Err(Error::UnsupportedPlatform)
+  }
+
+  pub async fn read_long_streamed(
+    &self,
+    _request: ReadLongStreamedRequest,
+    _on_chunk: Channel<ReadChunk>,
+  ) -> Result<ReadLongSummary> {
+    Err(Error::UnsupportedPlatform)
+  }
+
+  pub async fn await_disconnect(&self, _request: AwaitDisconnectRequest) -> Result<()> {
+    Err(Error::UnsupportedPlatform)
+  }
+
+  pub async fn describe_characteristic(&self, _request: ReadValueRequest) -> Result<CharacteristicDetails> {
+    Err(Error::UnsupportedPlatform)
+  }
+
+  pub async fn pause_notifications(&self, _request: NotificationRequest) -> Result<()> {
+    Err(Error::UnsupportedPlatform)
+  }
+
+  pub async fn resume_notifications(&self, _request: ResumeNotificationsRequest) -> Result<()> {
+    Err(Error::UnsupportedPlatform)
+  }
+
+  pub async fn read_revisions(&self, _request: DeviceRequest) -> Result<DeviceRevisions> {
+    Err(Error::UnsupportedPlatform)
+  }
+
+  pub async fn get_debug_snapshot(&self) -> Result<DebugSnapshot> {
+    Err(Error::UnsupportedPlatform)
+  }
+
+  pub async fn watch_service_changed(&self, _request: DeviceRequest) -> Result<()> {
+    Err(Error::UnsupportedPlatform)
+  }
+
+  pub async fn unwatch_service_changed(&self, _request: DeviceRequest) -> Result<()> {
+    Err(Error::UnsupportedPlatform)
+  }
+
+  pub async fn get_advertised_services(&self, _request: DeviceRequest) -> Result<Vec<String>> {
+    Err(Error::UnsupportedPlatform)
+  }
+
+  pub async fn start_event_monitor(&self) -> Result<()> {
+    Err(Error::UnsupportedPlatform)
+  }
+
+  pub async fn stop_event_monitor(&self) -> Result<()> {
+    Err(Error::UnsupportedPlatform)
+  }
+
+  pub async fn register_characteristic_format(&self, _request: RegisterFormatRequest) -> Result<()> {
+    Err(Error::UnsupportedPlatform)
+  }
+
+  pub async fn ping_device(&self, _request: PingDeviceRequest) -> Result<PingResult> {
+    Err(Error::UnsupportedPlatform)
+  }
+
+  pub async fn poll_characteristic(&self, _request: PollRequest) -> Result<()> {
+    Err(Error::UnsupportedPlatform)
+  }
+
+  pub async fn stop_polling(&self, _request: NotificationRequest) -> Result<()> {
+    Err(Error::UnsupportedPlatform)
+  }
+
+  pub async fn request_permission(&self) -> Result<PermissionStatus> {
+    Err(Error::UnsupportedPlatform)
+  }
+
+  pub async fn read_descriptor_value(&self, _request: DescriptorRequest) -> Result<DescriptorValue> {
+    Err(Error::UnsupportedPlatform)
+  }
+
+  pub async fn write_descriptor_value(&self, _request: WriteDescriptorRequest) -> Result<()> {
+    Err(Error::UnsupportedPlatform)
+  }
+
+  pub async fn request_devices(&self, _options: RequestDeviceOptions) -> Result<Vec<BluetoothDevice>> {
+    Err(Error::UnsupportedPlatform)
+  }
+
+  pub async fn get_descriptors(&self, _request: DescriptorsRequest) -> Result<Vec<BluetoothDescriptor>> {
+    Err(Error::UnsupportedPlatform)
+  }
+
+  pub async fn watch_rssi(&self, _request: WatchRssiRequest) -> Result<()> {
+    Err(Error::UnsupportedPlatform)
+  }
+
+  pub async fn unwatch_rssi(&self, _request: DeviceRequest) -> Result<()> {
+    Err(Error::UnsupportedPlatform)
+  }
+
+  pub async fn dismiss_selection(&self, _request: DismissSelectionRequest) -> Result<()> {
+    Err(Error::UnsupportedPlatform)
+  }
+
+  pub async fn get_characteristic_stats(&self, _request: CharacteristicStatsRequest) -> Result<CharacteristicStats> {
+    Err(Error::UnsupportedPlatform)
+  }
+
+  pub async fn read_descriptors(
+    &self,
+    _request: ReadDescriptorsRequest,
+  ) -> Result<HashMap<String, DescriptorReadResult>> {
+    Err(Error::UnsupportedPlatform)
+  }
+
+  pub async fn watch_advertisements(&self, _request: DeviceRequest) -> Result<()> {
+    Err(Error::UnsupportedPlatform)
+  }
+
+  pub async fn unwatch_advertisements(&self, _request: DeviceRequest) -> Result<()> {
+    Err(Error::UnsupportedPlatform)
+  }
+
+  #[cfg(feature = "scan-replay")]
+  pub async fn replay_scan(&self, _request: ReplayScanRequest) -> Result<BluetoothDevice> {
+    Err(Error::UnsupportedPlatform)
+  }
+
+  pub async fn get_local_adapter_address(&self) -> Result<LocalAdapterAddress> {
+    Err(Error::UnsupportedPlatform)
+  }
+
+  pub async fn notification_listener_heartbeat(&self, _request: ListenerHeartbeatRequest) -> Result<()> {
+    Err(Error::UnsupportedPlatform)
+  }
+
+  pub async fn set_default_scan_timeout(&self, _request: ScanTimeoutRequest) -> Result<()> {
+    Err(Error::UnsupportedPlatform)
+  }
+
+  pub async fn get_default_scan_timeout(&self) -> Result<u64> {
+    Err(Error::UnsupportedPlatform)
+  }
+
+  pub async fn list_active_tasks(&self) -> Result<ActiveTasks> {
+    Err(Error::UnsupportedPlatform)
+  }
+
+  pub async fn get_mtu(&self, _request: DeviceRequest) -> Result<u16> {
+    Err(Error::UnsupportedPlatform)
+  }
+
+  pub async fn await_value(&self, _request: AwaitValueRequest) -> Result<BluetoothValue> {
+    Err(Error::UnsupportedPlatform)
+  }
+
+  pub async fn get_characteristic_capabilities(
+    &self,
+    _request: DeviceRequest,
+  ) -> Result<Vec<CharacteristicCapabilities>> {
+    Err(Error::UnsupportedPlatform)
+  }
+
+  pub async fn read_all_battery_levels(&self, _request: DeviceRequest) -> Result<Vec<BatteryLevel>> {
+    Err(Error::UnsupportedPlatform)
+  }
+
+  pub async fn get_last_device(&self) -> Result<Option<LastDevice>> {
+    Err(Error::UnsupportedPlatform)
+  }
+
+  pub async fn start_scan(&self, _options: RequestDeviceOptions) -> Result<()> {
+    Err(Error::UnsupportedPlatform)
+  }
+
+  pub async fn stop_scan(&self) -> Result<()> {
+    Err(Error::UnsupportedPlatform)
+  }
+
+  pub async fn get_connected_devices(&self) -> Result<Vec<BluetoothDevice>> {
+    Err(Error::UnsupportedPlatform)
+  }
+
+  pub async fn get_connection_state(&self, _request: DeviceRequest) -> Result<ConnectionState> {
+    Err(Error::UnsupportedPlatform)
+  }
+
+  pub async fn disconnect_all(&self) -> Result<()> {
+    Err(Error::UnsupportedPlatform)
+  }
+
+  pub async fn get_adapters(&self) -> Result<Vec<AdapterInfo>> {
+    Err(Error::UnsupportedPlatform)
+  }
+
+  pub async fn set_adapter(&self, _request: SetAdapterRequest) -> Result<AdapterInfo> {
+    Err(Error::UnsupportedPlatform)
+  }
+
+  pub async fn get_adapter_info(&self) -> Result<AdapterInfo> {
+    Err(Error::UnsupportedPlatform)
+  }
+
+  pub async fn get_primary_service(&self, _request: ServiceRequest) -> Result<BluetoothService> {
+    Err(Error::UnsupportedPlatform)
+  }
 }
@@ -1,7 +1,21 @@
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
 
 pub const EVENT_NOTIFICATION: &str = "web-bluetooth://characteristic-value-changed";
 pub const EVENT_GATT_DISCONNECTED: &str = "web-bluetooth://gattserver-disconnected";
+pub const EVENT_REQUEST_DEVICE: &str = "web-bluetooth://request-device";
+pub const EVENT_GATT_CONNECTED: &str = "web-bluetooth://gattserver-connected";
+pub const EVENT_RECONNECT_FAILED: &str = "web-bluetooth://reconnect-failed";
+pub const EVENT_NOTIFICATION_ENDED: &str = "web-bluetooth://notifications-ended";
+pub const EVENT_SERVICE_CHANGED: &str = "web-bluetooth://service-changed";
+pub const EVENT_RSSI_CHANGED: &str = "web-bluetooth://rssi-changed";
+pub const EVENT_ADVERTISEMENT_RECEIVED: &str = "web-bluetooth://advertisement-received";
+pub const EVENT_DEVICE_DISCOVERED: &str = "web-bluetooth://device-discovered";
+pub const EVENT_DEVICE_UPDATED: &str = "web-bluetooth://device-updated";
+pub const EVENT_AVAILABILITY_CHANGED: &str = "web-bluetooth://availability-changed";
+/// Carries an [`EventEnvelope`] for every device event when unified events are enabled.
+pub const EVENT_WEB_BLUETOOTH: &str = "web-bluetooth://event";
 
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -24,21 +38,101 @@ pub struct RequestDeviceOptions {
   pub filters: Vec<DeviceFilter>,
   #[serde(default)]
   pub optional_services: Vec<String>,
-  #[serde(default = "default_scan_timeout_ms")]
-  pub scan_timeout_ms: u64,
+  /// Falls back to the app-wide default set with `set_default_scan_timeout`.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub scan_timeout_ms: Option<u64>,
+  /// Hides devices the app already knows about, e.g. for an "add a new
+  /// device" flow.
+  #[serde(default)]
+  pub exclude_known_devices: KnownDeviceExclusion,
 }
 
-fn default_scan_timeout_ms() -> u64 {
-  10_000
+/// Which already-known devices `request_device` leaves out of the picker.
+/// The set is taken when the request starts.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum KnownDeviceExclusion {
+  #[default]
+  None,
+  /// Devices connected to this host.
+  Connected,
+  /// Every device the plugin has cached, i.e. previously returned or looked up.
+  Cached,
 }
 
+/// Scan timeout used until `set_default_scan_timeout` changes it.
+pub(crate) const DEFAULT_SCAN_TIMEOUT_MS: u64 = 10_000;
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct DeviceFilter {
   #[serde(default)]
   pub services: Vec<String>,
+  /// Whether a device must advertise all of `services` (the Web Bluetooth
+  /// behavior) or any one of them.
+  #[serde(default)]
+  pub services_match: ServicesMatch,
   pub name: Option<String>,
   pub name_prefix: Option<String>,
+  /// Every entry must match the device's manufacturer specific data.
+  #[serde(default)]
+  pub manufacturer_data: Vec<ManufacturerDataFilter>,
+  /// Every entry must match the device's service data.
+  #[serde(default)]
+  pub service_data: Vec<ServiceDataFilter>,
+}
+
+/// Matches manufacturer specific data for one company. The data must start
+/// with `data_prefix` after both are masked with `mask`; without a prefix any
+/// data from the company matches.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ManufacturerDataFilter {
+  pub company_identifier: u16,
+  /// base64 encoded
+  #[serde(default)]
+  pub data_prefix: Option<String>,
+  /// base64 encoded, same length as `data_prefix`; defaults to all ones.
+  #[serde(default)]
+  pub mask: Option<String>,
+}
+
+/// Matches the service data advertised for one service UUID, with the same
+/// prefix and mask rules as [`ManufacturerDataFilter`].
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServiceDataFilter {
+  pub service: String,
+  /// base64 encoded
+  #[serde(default)]
+  pub data_prefix: Option<String>,
+  /// base64 encoded, same length as `data_prefix`; defaults to all ones.
+  #[serde(default)]
+  pub mask: Option<String>,
+}
+
+#[cfg(feature = "scan-replay")]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayScanRequest {
+  /// File written by `PluginConfig::record_scan`.
+  pub path: String,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DismissSelectionRequest {
+  /// Request to dismiss; `None` dismisses whichever request is active.
+  #[serde(default)]
+  pub request_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ServicesMatch {
+  #[default]
+  All,
+  Any,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -51,6 +145,112 @@ pub struct BluetoothDevice {
   #[serde(default)]
   pub watching_advertisements: bool,
   pub connected: bool,
+  /// Populated once the GAP Appearance has been read for this device.
+  #[serde(default)]
+  pub appearance: Option<Appearance>,
+  /// Vendor named by the lowest company identifier in the manufacturer data.
+  /// Only set when enabled through `PluginConfig::manufacturer_names`.
+  #[serde(default)]
+  pub manufacturer_name: Option<String>,
+  /// Signal strength in dBm from the latest advertisement seen while scanning.
+  #[serde(default)]
+  pub rssi: Option<i16>,
+  /// Advertised transmit power in dBm.
+  #[serde(default)]
+  pub tx_power: Option<i16>,
+  /// Manufacturer specific data keyed by company identifier, base64 encoded.
+  #[serde(default)]
+  pub manufacturer_data: HashMap<u16, String>,
+  /// Set when a data blob was cut to `PluginConfig::max_advertisement_data_len`.
+  #[serde(default)]
+  pub truncated: bool,
+  /// Bonded with the host OS. Only reported by `get_bonded_devices`.
+  #[serde(default)]
+  pub paired: bool,
+}
+
+/// Revision strings from the Device Information Service. Each is `None` when
+/// the device does not expose the characteristic.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeviceRevisions {
+  pub firmware: Option<String>,
+  pub hardware: Option<String>,
+  pub software: Option<String>,
+}
+
+/// GAP Appearance code with its assigned-numbers labels.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Appearance {
+  pub value: u16,
+  pub category: String,
+  pub subcategory: Option<String>,
+}
+
+/// Kind of device picker the plugin was configured with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SelectionMode {
+  /// The plugin opens its own picker window.
+  NativeDialog,
+  /// The first matching device is picked without any UI.
+  FirstMatch,
+  /// The device at a fixed index is picked without any UI.
+  Index,
+  /// The app renders its own picker from `request-device` events.
+  Event,
+  /// An application-provided handler decides.
+  Custom,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SelectionHandlerInfo {
+  pub mode: SelectionMode,
+  pub wants_full_scan: bool,
+}
+
+/// Advertisement data seen for a device. Binary values are base64 encoded.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdvertisementSnapshot {
+  pub name: Option<String>,
+  pub rssi: Option<i16>,
+  pub tx_power: Option<i16>,
+  #[serde(default)]
+  pub uuids: Vec<String>,
+  /// Keyed by company identifier.
+  #[serde(default)]
+  pub manufacturer_data: HashMap<u16, String>,
+  /// Keyed by service UUID.
+  #[serde(default)]
+  pub service_data: HashMap<String, String>,
+  /// Set when a data blob was cut to `PluginConfig::max_advertisement_data_len`.
+  #[serde(default)]
+  pub truncated: bool,
+}
+
+/// Details about the device picked by the most recent `request_device` call.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SelectionInfo {
+  pub device: BluetoothDevice,
+  /// First entry of `RequestDeviceOptions::filters` that matched; `None` when the
+  /// device was only accepted through `acceptAllDevices`.
+  pub matched_filter_index: Option<usize>,
+  pub advertisement: AdvertisementSnapshot,
+}
+
+/// The device most recently picked through `request_device`, kept so the UI
+/// can offer to reconnect it by id even when it is out of range.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LastDevice {
+  pub device_id: String,
+  pub name: Option<String>,
+  /// Milliseconds since the Unix epoch.
+  pub selected_at_ms: u64,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -60,12 +260,32 @@ pub struct GattServerInfo {
   pub connected: bool,
   #[serde(default)]
   pub services: Vec<BluetoothService>,
+  /// `true` when `services` only lists the subset requested on connect
+  /// rather than the whole attribute table.
+  #[serde(default)]
+  pub partial: bool,
+  /// Discovery failures that were tolerated because some services were
+  /// still enumerated. Empty when discovery completed.
+  #[serde(default)]
+  pub discovery_errors: Vec<DiscoveryError>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiscoveryError {
+  /// Service or characteristic that failed to enumerate; `None` when the
+  /// backend does not say which attribute failed.
+  pub uuid: Option<String>,
+  pub message: String,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct BluetoothService {
   pub uuid: String,
+  /// Application-registered name for `uuid`, if any.
+  #[serde(default)]
+  pub alias: Option<String>,
   #[serde(default)]
   pub is_primary: bool,
   #[serde(default)]
@@ -76,6 +296,9 @@ pub struct BluetoothService {
 #[serde(rename_all = "camelCase")]
 pub struct BluetoothCharacteristic {
   pub uuid: String,
+  /// Application-registered name for `uuid`, if any.
+  #[serde(default)]
+  pub alias: Option<String>,
   #[serde(default)]
   pub properties: CharacteristicProperties,
   #[serde(default)]
@@ -102,12 +325,300 @@ pub struct BluetoothDescriptor {
   pub uuid: String,
 }
 
+/// Battery Level (0x2A19) of one Battery Service instance, in percent.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatteryLevel {
+  /// Distinguishes instances on the same device, e.g. `service0010` on
+  /// BlueZ. Opaque and only stable while the attribute table is unchanged.
+  pub service_instance_id: String,
+  pub level: u8,
+}
+
+/// What a characteristic supports, for enabling only valid actions in a UI.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CharacteristicCapabilities {
+  pub service_uuid: String,
+  pub uuid: String,
+  pub properties: CharacteristicProperties,
+  /// Whether a Client Characteristic Configuration descriptor (0x2902) is
+  /// present, i.e. notifications can be toggled.
+  pub has_client_configuration: bool,
+  pub has_descriptors: bool,
+}
+
+/// A characteristic together with its decoded standard descriptors. Each
+/// descriptor field is `None` when the descriptor is absent or unreadable.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CharacteristicDetails {
+  pub service_uuid: String,
+  pub characteristic: BluetoothCharacteristic,
+  /// Characteristic User Description (0x2901).
+  pub user_description: Option<String>,
+  /// Client Characteristic Configuration (0x2902).
+  pub client_configuration: Option<ClientConfiguration>,
+  /// Characteristic Presentation Format (0x2904).
+  pub presentation_format: Option<PresentationFormat>,
+  /// Characteristic Extended Properties (0x2900).
+  pub extended_properties: Option<ExtendedProperties>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClientConfiguration {
+  pub notifications: bool,
+  pub indications: bool,
+}
+
+/// Raw presentation format fields; `format` and `unit` are Bluetooth
+/// Assigned Numbers and the value scales by `10^exponent`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PresentationFormat {
+  pub format: u8,
+  pub exponent: i8,
+  pub unit: u16,
+  pub namespace: u8,
+  pub description: u16,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExtendedProperties {
+  pub reliable_write: bool,
+  pub writable_auxiliaries: bool,
+}
+
+/// Redacted view of the plugin's internal state for support diagnostics. It
+/// never includes characteristic values or advertisement payloads.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DebugSnapshot {
+  pub adapter_index: usize,
+  pub adapter_info: Option<String>,
+  pub cached_device_ids: Vec<String>,
+  pub connected_device_ids: Vec<String>,
+  pub discovered_service_device_ids: Vec<String>,
+  /// Keys (`deviceId:characteristicUuid`) of active subscriptions and polling monitors.
+  pub notification_keys: Vec<String>,
+  pub paused_notification_keys: Vec<String>,
+  /// Keys of internal Service Changed watchers.
+  pub watcher_keys: Vec<String>,
+  pub active_request_id: Option<String>,
+  /// Whether a `request_device` scan is in flight.
+  pub scanning: bool,
+}
+
+/// Background tasks currently running, grouped by kind. Lists are sorted.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActiveTasks {
+  /// Subscriptions started by `start_notifications` or a notifying `monitor_characteristic`.
+  pub notifications: Vec<CharacteristicTask>,
+  /// Reads driven by `poll_characteristic` or a polling `monitor_characteristic`.
+  pub polling: Vec<CharacteristicTask>,
+  /// Device ids with a `watch_rssi` task.
+  pub rssi_watchers: Vec<String>,
+  /// Device ids with a Service Changed watch.
+  pub service_changed_watchers: Vec<String>,
+  /// Device ids with a `watch_advertisements` task.
+  pub advertisement_watchers: Vec<String>,
+  /// Device ids being reconnected after an unexpected disconnect.
+  pub reconnects: Vec<String>,
+  /// Whether the adapter event listener is running.
+  pub event_listener: bool,
+  /// Whether a `start_scan` background scan is running.
+  pub background_scan: bool,
+  /// Request id of the `request_device` scan in flight, if any.
+  pub active_request_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CharacteristicTask {
+  pub device_id: String,
+  pub characteristic_uuid: String,
+  /// Only ever `true` for notifications paused with `pause_notifications`.
+  pub paused: bool,
+}
+
+/// Outcome of `request_permission`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PermissionStatus {
+  Granted,
+  Denied,
+}
+
+/// Outcome of `get_connection_state`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ConnectionState {
+  Disconnected,
+  /// A `connect_gatt` call, a connect before subscribing or an automatic
+  /// reconnect is in progress.
+  Connecting,
+  Connected,
+}
+
+/// Controller features reported by the host. Each field is `None` when the
+/// platform does not expose it.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdapterCapabilities {
+  pub extended_advertising: Option<bool>,
+  pub coded_phy: Option<bool>,
+  pub le_2m_phy: Option<bool>,
+  pub max_connections: Option<u32>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScanTimeoutRequest {
+  pub timeout_ms: u64,
+}
+
+/// Identity of the local controller. Each field is `None` when the platform
+/// does not expose it.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LocalAdapterAddress {
+  /// Controller BD_ADDR.
+  pub address: Option<String>,
+  /// `true` when the controller uses a random private address, which may
+  /// change and should not be used to identify the machine.
+  pub random_address: Option<bool>,
+  /// Friendly name the controller advertises.
+  pub name: Option<String>,
+}
+
+/// A Bluetooth adapter of the host, as listed by `get_adapters`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdapterInfo {
+  /// Position in the platform's adapter list, for `AdapterSelector::Index`.
+  pub index: usize,
+  /// Platform identifier, e.g. `hci1` on Linux.
+  pub id: String,
+  /// Raw `adapter_info` string reported by the platform.
+  pub info: String,
+  /// Controller BD_ADDR. Only reported on Linux.
+  pub address: Option<String>,
+  /// Friendly name of the controller. Only reported on Linux.
+  pub name: Option<String>,
+  /// `None` when the platform cannot tell.
+  pub powered: Option<bool>,
+  /// Whether the adapter can be used, i.e. it is not powered off. Matches
+  /// `get_availability` for the selected adapter.
+  pub available: bool,
+  /// Whether the plugin uses this adapter.
+  pub selected: bool,
+}
+
+/// Maps UUID strings (full, 16-bit or 32-bit form) to application-defined names.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RegisterUuidAliasesRequest {
+  pub aliases: HashMap<String, String>,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct DeviceRequest {
   pub device_id: String,
 }
 
+/// Adapter to switch to with `set_adapter`: its `index` in `get_adapters`,
+/// or its platform id, name or address.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum AdapterRef {
+  Index(usize),
+  Id(String),
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetAdapterRequest {
+  pub adapter: AdapterRef,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConnectRequest {
+  pub device_id: String,
+  /// Services the app needs. When set, only these are reported; backends
+  /// without targeted discovery still discover the full table.
+  #[serde(default)]
+  pub services: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PingDeviceRequest {
+  pub device_id: String,
+  /// Number of probe reads to average over.
+  #[serde(default = "default_ping_samples")]
+  pub samples: u32,
+  /// Characteristic to read as the probe. Must be given together with
+  /// `characteristic_uuid`; defaults to the first readable characteristic,
+  /// preferring the GAP Device Name.
+  #[serde(default)]
+  pub service_uuid: Option<String>,
+  #[serde(default)]
+  pub characteristic_uuid: Option<String>,
+}
+
+fn default_ping_samples() -> u32 {
+  1
+}
+
+/// Round-trip times of the probe reads, in milliseconds. An approximation:
+/// it includes the OS stack and connection interval, not just the radio.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PingResult {
+  pub average_ms: f64,
+  pub min_ms: f64,
+  pub max_ms: f64,
+  pub samples: u32,
+  pub probe_characteristic_uuid: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AwaitDisconnectRequest {
+  pub device_id: String,
+  pub timeout_ms: u64,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AwaitValueRequest {
+  pub device_id: String,
+  pub service_uuid: String,
+  pub characteristic_uuid: String,
+  pub condition: ValueCondition,
+  pub timeout_ms: u64,
+  /// Read interval for characteristics that cannot notify.
+  #[serde(default = "default_await_poll_interval_ms")]
+  pub poll_interval_ms: u64,
+}
+
+fn default_await_poll_interval_ms() -> u64 {
+  100
+}
+
+/// Condition `await_value` waits for. Values are base64 encoded.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum ValueCondition {
+  Equals { value: String },
+  HasPrefix { prefix: String },
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ServiceRequest {
@@ -123,6 +634,15 @@ pub struct CharacteristicsRequest {
   pub characteristic_uuid: Option<String>,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DescriptorsRequest {
+  pub device_id: String,
+  pub service_uuid: String,
+  pub characteristic_uuid: String,
+  pub descriptor_uuid: Option<String>,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct DescriptorRequest {
@@ -130,6 +650,69 @@ pub struct DescriptorRequest {
   pub service_uuid: String,
   pub characteristic_uuid: String,
   pub descriptor_uuid: String,
+  /// Also decode standard descriptors into [`StandardDescriptorValue`].
+  #[serde(default)]
+  pub interpret: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WriteDescriptorRequest {
+  pub device_id: String,
+  pub service_uuid: String,
+  pub characteristic_uuid: String,
+  pub descriptor_uuid: String,
+  /// base64 encoded payload. Exactly one of `value` and `interpreted` must be set.
+  #[serde(default)]
+  pub value: Option<String>,
+  /// Typed payload for a standard descriptor; its kind must match `descriptor_uuid`.
+  #[serde(default)]
+  pub interpreted: Option<StandardDescriptorValue>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReadDescriptorsRequest {
+  pub device_id: String,
+  pub service_uuid: String,
+  pub characteristic_uuid: String,
+  /// Descriptors to read; every descriptor of the characteristic when omitted.
+  #[serde(default)]
+  pub descriptor_uuids: Option<Vec<String>>,
+}
+
+/// Outcome of one read in `read_descriptors`; exactly one of `value` and
+/// `error` is set.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DescriptorReadResult {
+  /// base64 encoded value
+  pub value: Option<String>,
+  pub error: Option<String>,
+}
+
+/// A descriptor value read by `read_descriptor_value`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DescriptorValue {
+  /// base64 encoded raw value
+  pub value: String,
+  /// Set when `interpret` was requested and the descriptor is a standard one.
+  pub interpreted: Option<StandardDescriptorValue>,
+}
+
+/// Typed value of a standard descriptor, tagged by `kind`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(tag = "kind", content = "value", rename_all = "camelCase")]
+pub enum StandardDescriptorValue {
+  /// Characteristic Extended Properties (0x2900).
+  ExtendedProperties(ExtendedProperties),
+  /// Characteristic User Description (0x2901).
+  UserDescription(String),
+  /// Client Characteristic Configuration (0x2902).
+  ClientConfiguration(ClientConfiguration),
+  /// Characteristic Presentation Format (0x2904).
+  PresentationFormat(PresentationFormat),
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -140,6 +723,62 @@ pub struct ReadValueRequest {
   pub characteristic_uuid: String,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchReadRequest {
+  pub reads: Vec<ReadValueRequest>,
+  /// Upper bound on reads in flight at once.
+  #[serde(default = "default_batch_concurrency")]
+  pub max_concurrency: usize,
+}
+
+fn default_batch_concurrency() -> usize {
+  4
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReadLongStreamedRequest {
+  pub device_id: String,
+  pub service_uuid: String,
+  pub characteristic_uuid: String,
+  /// Bytes per streamed chunk; defaults to the 512-byte attribute maximum.
+  #[serde(default = "default_read_chunk_size")]
+  pub chunk_size: usize,
+}
+
+fn default_read_chunk_size() -> usize {
+  512
+}
+
+/// One slice of a streamed read. The last chunk has `done` set.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReadChunk {
+  pub offset: usize,
+  /// base64 encoded slice
+  pub chunk_base64: String,
+  pub done: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReadLongSummary {
+  pub total_bytes: usize,
+}
+
+/// Outcome of one read in a batch; exactly one of `value` and `error` is set.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchReadResult {
+  pub device_id: String,
+  pub service_uuid: String,
+  pub characteristic_uuid: String,
+  /// base64 encoded value
+  pub value: Option<String>,
+  pub error: Option<String>,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct WriteValueRequest {
@@ -148,20 +787,220 @@ pub struct WriteValueRequest {
   pub characteristic_uuid: String,
   /// base64 encoded payload
   pub value: String,
+  /// Legacy switch between `withResponse` and `withoutResponse`, used when
+  /// `write_type` is not given.
   #[serde(default = "default_with_response")]
   pub with_response: bool,
+  #[serde(default)]
+  pub write_type: Option<WriteTypeKind>,
+  /// Splits values longer than this many bytes into sequential writes.
+  /// Overrides `PluginConfig::write_chunk_size`; must be at least 1.
+  #[serde(default)]
+  pub chunk_size: Option<usize>,
 }
 
 fn default_with_response() -> bool {
   true
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum WriteTypeKind {
+  WithResponse,
+  WithoutResponse,
+  /// Authenticated signed write without response. Requires the
+  /// characteristic's `authenticatedSignedWrites` property.
+  SignedWithoutResponse,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SequenceWrite {
+  pub service_uuid: String,
+  pub characteristic_uuid: String,
+  /// base64 encoded payload
+  pub value: String,
+  #[serde(default = "default_with_response")]
+  pub with_response: bool,
+}
+
+/// Ordered writes against one device. GATT has no transactions, so this is
+/// not atomic: a failure leaves earlier writes applied unless they can be
+/// rolled back.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WriteSequenceRequest {
+  pub device_id: String,
+  pub writes: Vec<SequenceWrite>,
+  /// Read each readable characteristic before writing it and restore the
+  /// previous values if a later write fails.
+  #[serde(default)]
+  pub rollback_on_failure: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WriteSequenceResult {
+  /// Number of writes that succeeded before the sequence stopped.
+  pub completed: usize,
+  pub failed_index: Option<usize>,
+  pub error: Option<String>,
+  /// `true` when every earlier write was restored after a failure.
+  #[serde(default)]
+  pub rolled_back: bool,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct NotificationRequest {
   pub device_id: String,
   pub service_uuid: String,
   pub characteristic_uuid: String,
+  /// Overrides the configured [`DuplicateNotificationPolicy`] for `start_notifications`.
+  #[serde(default)]
+  pub on_duplicate: Option<DuplicateNotificationPolicy>,
+}
+
+/// What `start_notifications` does when the characteristic is already subscribed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DuplicateNotificationPolicy {
+  /// Fail with `NotificationsAlreadyActive`.
+  #[default]
+  Error,
+  /// Succeed without touching the existing subscription.
+  Ignore,
+  /// Tear down the existing subscription task and start a new one.
+  Restart,
+}
+
+/// How to decode a characteristic value. Numbers are little endian, as in GATT.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ValueFormat {
+  Uint8,
+  Int8,
+  Uint16,
+  Int16,
+  Uint32,
+  Int32,
+  Uint64,
+  Int64,
+  Float32,
+  Float64,
+  Utf8,
+  Hex,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RegisterFormatRequest {
+  pub characteristic_uuid: String,
+  pub format: ValueFormat,
+  /// Limits the registration to one device. Device-scoped formats win over
+  /// global ones and are dropped by `forget_device`.
+  #[serde(default)]
+  pub device_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResumeNotificationsRequest {
+  pub device_id: String,
+  pub service_uuid: String,
+  pub characteristic_uuid: String,
+  /// Emit the most recent value that arrived while paused, if any.
+  #[serde(default)]
+  pub emit_latest: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MonitorRequest {
+  pub device_id: String,
+  pub service_uuid: String,
+  pub characteristic_uuid: String,
+  /// Interval used when the characteristic can only be read. Polling keeps the
+  /// link busy and costs considerably more power than notifications.
+  #[serde(default = "default_poll_interval_ms")]
+  pub poll_interval_ms: u64,
+}
+
+fn default_poll_interval_ms() -> u64 {
+  1_000
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PollRequest {
+  pub device_id: String,
+  pub service_uuid: String,
+  pub characteristic_uuid: String,
+  #[serde(default = "default_poll_interval_ms")]
+  pub interval_ms: u64,
+  /// Emit one aggregated value per window instead of every read.
+  #[serde(default)]
+  pub aggregate: Option<PollAggregate>,
+}
+
+/// Downsampling applied by `poll_characteristic`. Reads are decoded with
+/// `format`, which must be numeric; samples that fail to decode are skipped.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PollAggregate {
+  pub function: AggregateFunction,
+  /// Number of successful samples per emitted value.
+  pub window: usize,
+  pub format: ValueFormat,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum AggregateFunction {
+  Min,
+  Max,
+  Avg,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum MonitorMode {
+  Notify,
+  Poll,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MonitorInfo {
+  pub mode: MonitorMode,
+  /// Value read when monitoring started, if the characteristic is readable.
+  pub initial_value: Option<BluetoothValue>,
+}
+
+/// LE physical layer. Unknown values are rejected when the request is deserialized.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum BluetoothPhy {
+  #[serde(rename = "le1m")]
+  Le1M,
+  #[serde(rename = "le2m")]
+  Le2M,
+  #[serde(rename = "leCoded")]
+  LeCoded,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetPhyRequest {
+  pub device_id: String,
+  pub tx_phy: BluetoothPhy,
+  pub rx_phy: BluetoothPhy,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PhyInfo {
+  pub tx_phy: BluetoothPhy,
+  pub rx_phy: BluetoothPhy,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -171,6 +1010,62 @@ pub struct BluetoothValue {
   pub value: String,
 }
 
+/// Default number of devices `get_devices` queries at once.
+pub const DEFAULT_DEVICE_QUERY_CONCURRENCY: usize = 4;
+pub const DEFAULT_NOTIFICATION_QUEUE_CAPACITY: usize = 256;
+
+/// What to do when a notification arrives while the emit queue is full.
+///
+/// - `DropOldest` keeps the freshest values, which suits sensors where only
+///   the latest reading matters.
+/// - `DropNewest` preserves the order of what was already queued but loses the
+///   most recent values.
+/// - `Block` never drops, but stalls reading from the device until the queue
+///   drains; the backend may buffer or drop on its side instead.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum NotificationOverflowPolicy {
+  #[default]
+  DropOldest,
+  DropNewest,
+  Block,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationStats {
+  pub emitted: u64,
+  pub dropped: u64,
+  pub queue_capacity: usize,
+  pub overflow_policy: NotificationOverflowPolicy,
+}
+
+/// Sent periodically by each frontend listener for value events while it is
+/// registered, and once with `active: false` when it is removed.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListenerHeartbeatRequest {
+  pub listener_id: String,
+  pub active: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CharacteristicStatsRequest {
+  pub device_id: String,
+  pub characteristic_uuid: String,
+}
+
+/// Values delivered by the most recent subscription to a characteristic.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CharacteristicStats {
+  pub notification_count: u64,
+  /// Unix time in milliseconds of the last value, if any arrived.
+  pub last_value_ms: Option<u64>,
+  pub bytes_total: u64,
+}
+
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct NotificationEventPayload {
@@ -178,6 +1073,56 @@ pub struct NotificationEventPayload {
   pub service_uuid: String,
   pub characteristic_uuid: String,
   pub value: String,
+  /// `true` when the value came from a read rather than a notification.
+  pub from_read: bool,
+  /// `value` decoded with the format registered for the characteristic, if any.
+  pub interpreted: Option<serde_json::Value>,
+}
+
+/// Device events that are also delivered through [`EVENT_WEB_BLUETOOTH`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum EventKind {
+  CharacteristicValueChanged,
+  NotificationsEnded,
+  GattServerDisconnected,
+  GattServerConnected,
+  ReconnectFailed,
+  ServiceChanged,
+  RssiChanged,
+  AdvertisementReceived,
+  DeviceDiscovered,
+  DeviceUpdated,
+}
+
+impl EventKind {
+  /// Name of the individual event of this kind.
+  pub fn event_name(self) -> &'static str {
+    match self {
+      Self::CharacteristicValueChanged => EVENT_NOTIFICATION,
+      Self::NotificationsEnded => EVENT_NOTIFICATION_ENDED,
+      Self::GattServerDisconnected => EVENT_GATT_DISCONNECTED,
+      Self::GattServerConnected => EVENT_GATT_CONNECTED,
+      Self::ReconnectFailed => EVENT_RECONNECT_FAILED,
+      Self::ServiceChanged => EVENT_SERVICE_CHANGED,
+      Self::RssiChanged => EVENT_RSSI_CHANGED,
+      Self::AdvertisementReceived => EVENT_ADVERTISEMENT_RECEIVED,
+      Self::DeviceDiscovered => EVENT_DEVICE_DISCOVERED,
+      Self::DeviceUpdated => EVENT_DEVICE_UPDATED,
+    }
+  }
+}
+
+/// Common wrapper emitted on [`EVENT_WEB_BLUETOOTH`]. `data` is the payload
+/// of the individual event named by `kind`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EventEnvelope<T> {
+  pub kind: EventKind,
+  pub device_id: String,
+  /// Milliseconds since the Unix epoch.
+  pub timestamp_ms: u64,
+  pub data: T,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -185,3 +1130,88 @@ pub struct NotificationEventPayload {
 pub struct DeviceEventPayload {
   pub device_id: String,
 }
+
+/// Emitted as [`EVENT_REQUEST_DEVICE`] when an `EventSelectionHandler` needs
+/// the app to show its picker. The app answers by emitting
+/// `{ deviceId }` or `{ deviceIds }` on `selection_event`, or
+/// `{ deviceId: null }` to cancel. Later scan results arrive on
+/// `update_event` as `{ devices, completed }`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeviceRequestPayload {
+  pub request_id: String,
+  pub selection_event: String,
+  pub update_event: String,
+  pub options: RequestDeviceOptions,
+  pub devices: Vec<BluetoothDevice>,
+  /// Whether the scan is still running and updates will follow.
+  pub scanning: bool,
+  /// Set for `request_devices`, where several devices may be picked.
+  pub multiple: bool,
+}
+
+/// Emitted as [`EVENT_AVAILABILITY_CHANGED`] when the adapter appears,
+/// disappears or is powered on or off, like Web Bluetooth's
+/// `availabilitychanged`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AvailabilityChangedPayload {
+  pub value: bool,
+}
+
+/// Emitted as [`EVENT_RECONNECT_FAILED`] once automatic reconnection gives up.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReconnectFailedPayload {
+  pub device_id: String,
+  pub attempts: u32,
+}
+
+/// Attribute handle range reported by a Service Changed indication. Handles
+/// are `None` when the indication is malformed.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServiceChangedPayload {
+  pub device_id: String,
+  pub start_handle: Option<u16>,
+  pub end_handle: Option<u16>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WatchRssiRequest {
+  pub device_id: String,
+  /// Minimum change in dBm before another event is emitted.
+  #[serde(default = "default_rssi_threshold")]
+  pub threshold: u16,
+  /// How often the last seen RSSI is sampled.
+  #[serde(default = "default_poll_interval_ms")]
+  pub interval_ms: u64,
+}
+
+fn default_rssi_threshold() -> u16 {
+  2
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RssiChangedPayload {
+  pub device_id: String,
+  pub rssi: i16,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdvertisementReceivedPayload {
+  pub device_id: String,
+  #[serde(flatten)]
+  pub advertisement: AdvertisementSnapshot,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CharacteristicEventPayload {
+  pub device_id: String,
+  pub service_uuid: String,
+  pub characteristic_uuid: String,
+}
@@ -0,0 +1,122 @@
+use std::{
+  path::{Path, PathBuf},
+  time::Instant,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{models::BluetoothDevice, Error, Result};
+
+/// A `request_device` scan as the picker saw it. Each update carries the full
+/// device list at that moment.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RecordedScan {
+  pub(crate) updates: Vec<RecordedUpdate>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RecordedUpdate {
+  /// Time since the scan started.
+  pub(crate) elapsed_ms: u64,
+  pub(crate) devices: Vec<BluetoothDevice>,
+}
+
+impl RecordedScan {
+  pub(crate) fn load(path: &Path) -> Result<Self> {
+    let json = std::fs::read(path)?;
+    Ok(serde_json::from_slice(&json)?)
+  }
+}
+
+/// Resolves a `replay_scan` path, which must be the `record_scan` file or lie
+/// inside the `replay_scan_dir`. Both sides are canonicalized so `..` and
+/// symlinks cannot step outside them.
+pub(crate) fn replay_path(requested: &Path, recording: Option<&Path>, dir: Option<&Path>) -> Result<PathBuf> {
+  let requested = requested.canonicalize()?;
+  let is_recording = recording
+    .and_then(|recording| recording.canonicalize().ok())
+    .is_some_and(|recording| recording == requested);
+  let in_dir = dir
+    .and_then(|dir| dir.canonicalize().ok())
+    .is_some_and(|dir| requested.starts_with(dir));
+  if is_recording || in_dir {
+    Ok(requested)
+  } else {
+    Err(Error::InvalidRequest(format!(
+      "{} is neither the scan recording nor inside the replay directory",
+      requested.display()
+    )))
+  }
+}
+
+/// Collects the device lists emitted during one scan and writes them to the
+/// configured file once the scan ends.
+pub(crate) struct ScanRecorder {
+  path: PathBuf,
+  started: Instant,
+  scan: RecordedScan,
+}
+
+impl ScanRecorder {
+  pub(crate) fn new(path: PathBuf) -> Self {
+    Self {
+      path,
+      started: Instant::now(),
+      scan: RecordedScan::default(),
+    }
+  }
+
+  pub(crate) fn record(&mut self, devices: &[BluetoothDevice]) {
+    self.scan.updates.push(RecordedUpdate {
+      elapsed_ms: self.started.elapsed().as_millis() as u64,
+      devices: devices.to_vec(),
+    });
+  }
+
+  /// Overwrites the recording file. Failures are logged so a broken path
+  /// never fails the request being recorded.
+  pub(crate) fn save(self) {
+    let written = serde_json::to_vec_pretty(&self.scan)
+      .map_err(crate::Error::from)
+      .and_then(|json| Ok(std::fs::write(&self.path, json)?));
+    match written {
+      Ok(()) => log::info!(
+        "Scan recorded | path={} | updates={}",
+        self.path.display(),
+        self.scan.updates.len()
+      ),
+      Err(err) => log::warn!("Failed to save scan recording | path={} | err={:?}", self.path.display(), err),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn replay_paths_are_limited_to_the_recording_and_directory() {
+    let root = std::env::temp_dir().join(format!("web-bluetooth-replay-{}", std::process::id()));
+    let dir = root.join("replays");
+    std::fs::create_dir_all(&dir).unwrap();
+    let recording = root.join("scan.json");
+    let replay = dir.join("replay.json");
+    let other = root.join("other.json");
+    for path in [&recording, &replay, &other] {
+      std::fs::write(path, "{}").unwrap();
+    }
+
+    assert!(replay_path(&recording, Some(&recording), None).is_ok());
+    assert!(replay_path(&replay, None, Some(&dir)).is_ok());
+    let escaped = dir.join("..").join("other.json");
+    assert!(matches!(
+      replay_path(&escaped, Some(&recording), Some(&dir)),
+      Err(Error::InvalidRequest(_))
+    ));
+    assert!(matches!(replay_path(&other, None, None), Err(Error::InvalidRequest(_))));
+
+    std::fs::remove_dir_all(&root).unwrap();
+  }
+}
@@ -0,0 +1,27 @@
+use serde_json::Value;
+
+use crate::models::ValueFormat;
+
+/// Decodes a characteristic value. GATT numbers are little endian; `None`
+/// means the value is too short for the format.
+pub(crate) fn interpret(format: ValueFormat, bytes: &[u8]) -> Option<Value> {
+  let value = match format {
+    ValueFormat::Uint8 => Value::from(*bytes.first()?),
+    ValueFormat::Int8 => Value::from(*bytes.first()? as i8),
+    ValueFormat::Uint16 => Value::from(u16::from_le_bytes(take(bytes)?)),
+    ValueFormat::Int16 => Value::from(i16::from_le_bytes(take(bytes)?)),
+    ValueFormat::Uint32 => Value::from(u32::from_le_bytes(take(bytes)?)),
+    ValueFormat::Int32 => Value::from(i32::from_le_bytes(take(bytes)?)),
+    ValueFormat::Uint64 => Value::from(u64::from_le_bytes(take(bytes)?)),
+    ValueFormat::Int64 => Value::from(i64::from_le_bytes(take(bytes)?)),
+    ValueFormat::Float32 => Value::from(f32::from_le_bytes(take(bytes)?)),
+    ValueFormat::Float64 => Value::from(f64::from_le_bytes(take(bytes)?)),
+    ValueFormat::Utf8 => Value::from(String::from_utf8_lossy(bytes).trim_end_matches('\0')),
+    ValueFormat::Hex => Value::from(bytes.iter().map(|byte| format!("{byte:02x}")).collect::<String>()),
+  };
+  Some(value)
+}
+
+fn take<const N: usize>(bytes: &[u8]) -> Option<[u8; N]> {
+  bytes.get(..N)?.try_into().ok()
+}